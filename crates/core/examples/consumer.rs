@@ -16,6 +16,18 @@ async fn read_frame(stream: &mut TcpStream) -> Result<String, Box<dyn std::error
     String::from_utf8(buffer).map_err(|e| format!("Invalid UTF-8: {}", e).into())
 }
 
+async fn send_frame(stream: &mut TcpStream, frame: &str) -> Result<(), Box<dyn std::error::Error>> {
+    let length = frame.len() as u32;
+
+    let mut v = Vec::new();
+    v.extend_from_slice(&length.to_be_bytes());
+    v.extend_from_slice(frame.as_bytes());
+
+    stream.write_all(&v).await?;
+    stream.flush().await?;
+    Ok(())
+}
+
 async fn send_consume_next(
     stream: &mut TcpStream,
     topic: &str,
@@ -25,15 +37,37 @@ async fn send_consume_next(
         r#"{{"ConsumeNext": {{"topic":"{}","partition_id":{}}}}}"#,
         topic, partition_id
     );
-    let length = frame.len();
+    send_frame(stream, &frame).await
+}
 
-    let mut v = Vec::new();
-    v.extend_from_slice(&length.to_be_bytes());
-    v.extend_from_slice(frame.as_bytes());
+/// Metadata 요청으로 토픽의 파티션 목록 조회
+async fn fetch_partition_ids(
+    stream: &mut TcpStream,
+    topic: &str,
+) -> Result<Vec<usize>, Box<dyn std::error::Error>> {
+    let frame = format!(r#"{{"Metadata": {{"topics":["{}"]}}}}"#, topic);
+    send_frame(stream, &frame).await?;
 
-    stream.write_all(&v).await?;
-    stream.flush().await?;
-    Ok(())
+    let response: Value = serde_json::from_str(&read_frame(stream).await?)?;
+    let data = response["Response"]["data"]
+        .as_array()
+        .ok_or("Metadata request failed")?
+        .iter()
+        .map(|b| b.as_u64().unwrap_or(0) as u8)
+        .collect::<Vec<_>>();
+
+    let metadata: Value = serde_json::from_slice(&data)?;
+    let partition_ids = metadata["topics"][0]["partitions"]
+        .as_array()
+        .map(|partitions| {
+            partitions
+                .iter()
+                .filter_map(|p| p["partition_id"].as_u64().map(|id| id as usize))
+                .collect()
+        })
+        .unwrap_or_default();
+
+    Ok(partition_ids)
 }
 
 #[tokio::main]
@@ -42,19 +76,19 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
     stream.set_nodelay(true)?;
 
     let topic = "topic-1";
-    let partition_id = 1;
+    let partition_ids = fetch_partition_ids(&mut stream, topic).await?;
 
     println!(
-        "Consumer started. Polling for messages from topic: {}, partition: {}",
-        topic, partition_id
+        "Consumer started. Polling for messages from topic: {}, partitions: {:?}",
+        topic, partition_ids
     );
     println!("Press Ctrl+C to stop.\n");
 
     let mut message_count = 0;
 
-    loop {
+    for partition_id in partition_ids.iter().cycle() {
         // ConsumeNext 요청 보내기
-        if let Err(e) = send_consume_next(&mut stream, topic, partition_id).await {
+        if let Err(e) = send_consume_next(&mut stream, topic, *partition_id).await {
             eprintln!("Failed to send ConsumeNext request: {}", e);
             break;
         }
@@ -36,7 +36,7 @@ impl AsyncConnectionPool {
             .await
             .map_err(|e| format!("Failed to acquire connection permit: {}", e))?;
 
-        let stream = TcpStream::connect("127.0.0.1:2369").await?;
+        let stream = TcpStream::connect((self.host.as_str(), self.port)).await?;
 
         stream.set_nodelay(true)?;
         Ok(stream)
@@ -100,7 +100,6 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
     let num_messages = 1_000_000;
     let num_tasks = 200; // 비동기 태스크 수
 
-    let start = Instant::now();
     let pool = Arc::new(AsyncConnectionPool::new(
         "127.0.0.1".to_string(),
         2369,
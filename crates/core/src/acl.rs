@@ -0,0 +1,570 @@
+use std::{
+    collections::BTreeSet,
+    fs,
+    path::{Path, PathBuf},
+};
+use tokio::sync::RwLock;
+
+use crate::{
+    Frame, MeierError, Result,
+    auth::Principal,
+    config::AclConfig,
+    controller::store::write_atomic,
+    protocol::{AclBinding, AclFilter, AclOperation, AclPermission, PatternType, ResourceType},
+    storage::TopicManager,
+};
+
+/// ResourceType::Cluster 리소스의 이름
+pub const CLUSTER_RESOURCE: &str = "meier-cluster";
+
+/// 인증하지 않은 연결의 principal
+pub const ANONYMOUS: &str = "User:ANONYMOUS";
+
+/// 단일 브로커에서 ACL 을 저장하는 파일(data_dir 기준)
+pub const ACL_FILE: &str = "acls.json";
+
+/// 요청 하나에 필요한 권한
+type Permission = (AclOperation, ResourceType, String);
+
+/// ACL 저장과 요청 인가
+///
+/// 클러스터 모드에서는 ACL 변경이 메타데이터 로그에 기록되어 모든 브로커에 같은 순서로
+/// 적용되고, 단일 브로커에서는 변경할 때마다 파일에 저장한다.
+/// 설정이 없으면 ACL 을 저장만 하고 모든 요청을 허용한다.
+pub struct Authorizer {
+    config: Option<AclConfig>,
+    acls: RwLock<BTreeSet<AclBinding>>,
+    path: Option<PathBuf>,
+}
+
+impl Authorizer {
+    /// path 가 있으면 저장된 ACL 을 읽고 변경 시 다시 저장
+    pub fn new(config: Option<&AclConfig>, path: Option<PathBuf>) -> Result<Self> {
+        let acls = match &path {
+            Some(path) => load(path)?,
+            None => BTreeSet::new(),
+        };
+
+        Ok(Self {
+            config: config.cloned(),
+            acls: RwLock::new(acls),
+            path,
+        })
+    }
+
+    pub fn is_enabled(&self) -> bool {
+        self.config.is_some()
+    }
+
+    /// 요청에 필요한 권한을 모두 가졌는지 확인
+    pub async fn authorize_frame(
+        &self,
+        principal: Option<&Principal>,
+        frame: &Frame,
+    ) -> Result<()> {
+        if !self.is_enabled() {
+            return Ok(());
+        }
+        for (operation, resource_type, name) in required_permissions(frame) {
+            self.authorize(principal, operation, resource_type, &name)
+                .await?;
+        }
+        Ok(())
+    }
+
+    /// Produce, AddPartitionsToTxn 은 없는 토픽을 자동 생성하므로 그 토픽의 Create 권한도 확인
+    pub async fn authorize_auto_create(
+        &self,
+        principal: Option<&Principal>,
+        frame: &Frame,
+        topic_manager: &TopicManager,
+    ) -> Result<()> {
+        if !self.is_enabled() {
+            return Ok(());
+        }
+        for name in auto_created_topics(frame) {
+            if topic_manager.get_topic(name).await.is_none() {
+                self.authorize(principal, AclOperation::Create, ResourceType::Topic, name)
+                    .await?;
+            }
+        }
+        Ok(())
+    }
+
+    pub async fn authorize(
+        &self,
+        principal: Option<&Principal>,
+        operation: AclOperation,
+        resource_type: ResourceType,
+        name: &str,
+    ) -> Result<()> {
+        if self
+            .is_authorized(principal, operation, resource_type, name)
+            .await
+        {
+            return Ok(());
+        }
+        Err(MeierError::NotAuthorized(format!(
+            "{} is not allowed to {:?} {:?} {}",
+            principal_name(principal),
+            operation,
+            resource_type,
+            name
+        )))
+    }
+
+    /// Deny 가 하나라도 일치하면 거부하고, 그렇지 않으면 Allow 가 있어야 허용
+    pub async fn is_authorized(
+        &self,
+        principal: Option<&Principal>,
+        operation: AclOperation,
+        resource_type: ResourceType,
+        name: &str,
+    ) -> bool {
+        let Some(config) = &self.config else {
+            return true;
+        };
+        let principal = principal_name(principal);
+        if config.super_users.contains(&principal) {
+            return true;
+        }
+
+        let acls = self.acls.read().await;
+        let mut resource_acls = acls
+            .iter()
+            .filter(|acl| acl.resource_type == resource_type && matches_resource(acl, name))
+            .peekable();
+        if resource_acls.peek().is_none() {
+            return config.allow_if_no_acls;
+        }
+
+        let mut allowed = false;
+        for acl in resource_acls {
+            if !matches_principal(acl, &principal) || !implies(acl.operation, operation) {
+                continue;
+            }
+            match acl.permission {
+                AclPermission::Deny => return false,
+                AclPermission::Allow => allowed = true,
+            }
+        }
+        allowed
+    }
+
+    pub async fn list(&self, filter: &AclFilter) -> Vec<AclBinding> {
+        self.acls
+            .read()
+            .await
+            .iter()
+            .filter(|acl| filter.matches(acl))
+            .cloned()
+            .collect()
+    }
+
+    /// 조건 중 하나라도 맞는 ACL
+    pub async fn matching(&self, filters: &[AclFilter]) -> Vec<AclBinding> {
+        self.acls
+            .read()
+            .await
+            .iter()
+            .filter(|acl| filters.iter().any(|filter| filter.matches(acl)))
+            .cloned()
+            .collect()
+    }
+
+    pub async fn contains_all(&self, acls: &[AclBinding]) -> bool {
+        let current = self.acls.read().await;
+        acls.iter().all(|acl| current.contains(acl))
+    }
+
+    pub async fn contains_any(&self, acls: &[AclBinding]) -> bool {
+        let current = self.acls.read().await;
+        acls.iter().any(|acl| current.contains(acl))
+    }
+
+    pub async fn add(&self, acls: Vec<AclBinding>) -> Result<()> {
+        let mut current = self.acls.write().await;
+        current.extend(acls);
+        self.save(&current)
+    }
+
+    pub async fn remove(&self, acls: &[AclBinding]) -> Result<()> {
+        let mut current = self.acls.write().await;
+        for acl in acls {
+            current.remove(acl);
+        }
+        self.save(&current)
+    }
+
+    fn save(&self, acls: &BTreeSet<AclBinding>) -> Result<()> {
+        let Some(path) = &self.path else {
+            return Ok(());
+        };
+        let bytes = serde_json::to_vec_pretty(acls)
+            .map_err(|e| MeierError::Storage(format!("Serialization error: {}", e)))?;
+
+        path.parent()
+            .map_or(Ok(()), fs::create_dir_all)
+            .map_err(|e| {
+                MeierError::Storage(format!("Failed to write {}: {}", path.display(), e))
+            })?;
+        write_atomic(path, &bytes)
+    }
+}
+
+/// CreateAcls 로 추가할 ACL 확인
+pub fn validate(acl: &AclBinding) -> Result<()> {
+    let invalid = |reason: &str| Err(MeierError::Protocol(format!("Invalid ACL: {}", reason)));
+
+    match acl.principal.strip_prefix("User:") {
+        Some(name) if !name.is_empty() => {}
+        _ => return invalid("principal must be User:<name> or User:*"),
+    }
+    if acl.resource_name.is_empty() {
+        return invalid("resource_name must not be empty");
+    }
+    if acl.resource_type == ResourceType::Cluster
+        && (acl.resource_name != CLUSTER_RESOURCE || acl.pattern_type != PatternType::Literal)
+    {
+        return invalid(&format!(
+            "cluster resource must be the literal name {}",
+            CLUSTER_RESOURCE
+        ));
+    }
+    if acl.operation == AclOperation::ClusterAction && acl.resource_type != ResourceType::Cluster {
+        return invalid("ClusterAction applies only to the cluster resource");
+    }
+    Ok(())
+}
+
+fn principal_name(principal: Option<&Principal>) -> String {
+    principal.map_or_else(|| ANONYMOUS.to_string(), Principal::to_string)
+}
+
+fn matches_principal(acl: &AclBinding, principal: &str) -> bool {
+    acl.principal == principal || acl.principal == "User:*"
+}
+
+fn matches_resource(acl: &AclBinding, name: &str) -> bool {
+    match acl.pattern_type {
+        PatternType::Literal => acl.resource_name == name || acl.resource_name == "*",
+        PatternType::Prefixed => name.starts_with(&acl.resource_name),
+    }
+}
+
+/// granted 권한이 requested 를 포함하는지
+fn implies(granted: AclOperation, requested: AclOperation) -> bool {
+    granted == requested
+        || granted == AclOperation::All
+        || (requested == AclOperation::Describe
+            && matches!(
+                granted,
+                AclOperation::Read
+                    | AclOperation::Write
+                    | AclOperation::Delete
+                    | AclOperation::Alter
+            ))
+}
+
+/// 요청 종류별로 필요한 권한
+///
+/// 전체 토픽 Metadata 요청은 Describe 권한이 있는 토픽만 응답하도록 서버에서 걸러낸다.
+fn required_permissions(frame: &Frame) -> Vec<Permission> {
+    let topic = |operation, name: &String| (operation, ResourceType::Topic, name.clone());
+    let group = |operation, name: &String| (operation, ResourceType::Group, name.clone());
+    let transactional_id =
+        |operation, name: &String| (operation, ResourceType::TransactionalId, name.clone());
+    let cluster = |operation| {
+        (
+            operation,
+            ResourceType::Cluster,
+            CLUSTER_RESOURCE.to_string(),
+        )
+    };
+
+    match frame {
+        Frame::Produce {
+            topic: name,
+            transactional_id: txn,
+            ..
+        } => {
+            let mut permissions = vec![topic(AclOperation::Write, name)];
+            if let Some(txn) = txn {
+                permissions.push(transactional_id(AclOperation::Write, txn));
+            }
+            permissions
+        }
+        Frame::Consume { topic: name, .. } | Frame::ConsumeNext { topic: name, .. } => {
+            vec![topic(AclOperation::Read, name)]
+        }
+        Frame::ListOffsets { topic: name, .. }
+        | Frame::DescribePartitions { topic: name }
+        | Frame::DescribeConfigs { topic: name } => vec![topic(AclOperation::Describe, name)],
+        Frame::Metadata { topics } => topics
+            .iter()
+            .flatten()
+            .map(|name| topic(AclOperation::Describe, name))
+            .collect(),
+        Frame::CreateTopic { topic: name, .. } => vec![topic(AclOperation::Create, name)],
+        Frame::DeleteTopic { topic: name } | Frame::DeleteRecords { topic: name, .. } => {
+            vec![topic(AclOperation::Delete, name)]
+        }
+        Frame::AlterConfigs { topic: name, .. } => vec![topic(AclOperation::Alter, name)],
+        Frame::BeginTxn {
+            transactional_id: txn,
+        }
+        | Frame::CommitTxn {
+            transactional_id: txn,
+        }
+        | Frame::AbortTxn {
+            transactional_id: txn,
+        } => {
+            vec![transactional_id(AclOperation::Write, txn)]
+        }
+        Frame::AddPartitionsToTxn {
+            transactional_id: txn,
+            partitions,
+        } => std::iter::once(transactional_id(AclOperation::Write, txn))
+            .chain(
+                partitions
+                    .iter()
+                    .map(|partition| topic(AclOperation::Write, &partition.topic)),
+            )
+            .collect(),
+        Frame::OffsetCommit {
+            group: name,
+            topic: topic_name,
+            ..
+        } => vec![
+            group(AclOperation::Read, name),
+            topic(AclOperation::Read, topic_name),
+        ],
+        Frame::OffsetFetch {
+            group: name,
+            topic: topic_name,
+            ..
+        } => vec![
+            group(AclOperation::Describe, name),
+            topic(AclOperation::Describe, topic_name),
+        ],
+        Frame::DescribeConsumerLag { group: Some(name) } => {
+            vec![group(AclOperation::Describe, name)]
+        }
+        Frame::DescribeConsumerLag { group: None }
+        | Frame::DescribeCluster
+        | Frame::ListReassignments
+        | Frame::DescribeAcls { .. } => vec![cluster(AclOperation::Describe)],
+        Frame::ReassignPartitions { .. }
+        | Frame::DecommissionBroker { .. }
+        | Frame::CreateAcls { .. }
+        | Frame::DeleteAcls { .. } => vec![cluster(AclOperation::Alter)],
+        Frame::ReplicaFetch { .. } | Frame::RaftVote { .. } | Frame::RaftAppend { .. } => {
+            vec![cluster(AclOperation::ClusterAction)]
+        }
+        Frame::SaslHandshake { .. }
+        | Frame::SaslAuthenticate { .. }
+        | Frame::SetClientId { .. }
+        | Frame::Response { .. }
+        | Frame::Ping
+        | Frame::Pong => Vec::new(),
+    }
+}
+
+/// 핸들러가 없으면 생성하는 토픽
+fn auto_created_topics(frame: &Frame) -> Vec<&str> {
+    match frame {
+        Frame::Produce { topic, .. } => vec![topic.as_str()],
+        Frame::AddPartitionsToTxn { partitions, .. } => partitions
+            .iter()
+            .map(|partition| partition.topic.as_str())
+            .collect(),
+        _ => Vec::new(),
+    }
+}
+
+fn load(path: &Path) -> Result<BTreeSet<AclBinding>> {
+    match fs::read(path) {
+        Ok(bytes) => serde_json::from_slice(&bytes)
+            .map_err(|e| MeierError::Storage(format!("Corrupt {}: {}", path.display(), e))),
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(BTreeSet::new()),
+        Err(e) => Err(MeierError::Storage(format!(
+            "Failed to read {}: {}",
+            path.display(),
+            e
+        ))),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::SaslMechanism;
+
+    fn acl(
+        principal: &str,
+        name: &str,
+        pattern_type: PatternType,
+        operation: AclOperation,
+        permission: AclPermission,
+    ) -> AclBinding {
+        AclBinding {
+            principal: principal.to_string(),
+            resource_type: ResourceType::Topic,
+            resource_name: name.to_string(),
+            pattern_type,
+            operation,
+            permission,
+        }
+    }
+
+    fn user(name: &str) -> Principal {
+        Principal {
+            name: name.to_string(),
+            mechanism: SaslMechanism::Plain,
+        }
+    }
+
+    async fn authorizer(allow_if_no_acls: bool, acls: Vec<AclBinding>) -> Authorizer {
+        let config = AclConfig {
+            super_users: vec!["User:admin".to_string()],
+            allow_if_no_acls,
+        };
+        let authorizer = Authorizer::new(Some(&config), None).unwrap();
+        authorizer.add(acls).await.unwrap();
+        authorizer
+    }
+
+    async fn can(
+        authorizer: &Authorizer,
+        name: &str,
+        operation: AclOperation,
+        topic: &str,
+    ) -> bool {
+        authorizer
+            .is_authorized(Some(&user(name)), operation, ResourceType::Topic, topic)
+            .await
+    }
+
+    #[test]
+    fn describe_is_implied_by_other_operations() {
+        for granted in [
+            AclOperation::Read,
+            AclOperation::Write,
+            AclOperation::Delete,
+            AclOperation::Alter,
+            AclOperation::All,
+        ] {
+            assert!(implies(granted, AclOperation::Describe));
+        }
+        assert!(!implies(AclOperation::Create, AclOperation::Describe));
+        assert!(!implies(AclOperation::Describe, AclOperation::Read));
+        assert!(!implies(AclOperation::Read, AclOperation::Write));
+        assert!(implies(AclOperation::All, AclOperation::ClusterAction));
+    }
+
+    #[test]
+    fn resource_names_match_by_pattern_type() {
+        let literal = acl(
+            "User:*",
+            "orders",
+            PatternType::Literal,
+            AclOperation::Read,
+            AclPermission::Allow,
+        );
+        assert!(matches_resource(&literal, "orders"));
+        assert!(!matches_resource(&literal, "orders-eu"));
+
+        let wildcard = acl(
+            "User:*",
+            "*",
+            PatternType::Literal,
+            AclOperation::Read,
+            AclPermission::Allow,
+        );
+        assert!(matches_resource(&wildcard, "anything"));
+
+        let prefixed = acl(
+            "User:*",
+            "orders-",
+            PatternType::Prefixed,
+            AclOperation::Read,
+            AclPermission::Allow,
+        );
+        assert!(matches_resource(&prefixed, "orders-eu"));
+        assert!(!matches_resource(&prefixed, "orders"));
+    }
+
+    #[tokio::test]
+    async fn deny_takes_precedence_over_allow() {
+        let authorizer = authorizer(
+            false,
+            vec![
+                acl(
+                    "User:*",
+                    "orders-",
+                    PatternType::Prefixed,
+                    AclOperation::All,
+                    AclPermission::Allow,
+                ),
+                acl(
+                    "User:bob",
+                    "orders-eu",
+                    PatternType::Literal,
+                    AclOperation::Write,
+                    AclPermission::Deny,
+                ),
+            ],
+        )
+        .await;
+
+        assert!(can(&authorizer, "alice", AclOperation::Write, "orders-eu").await);
+        assert!(can(&authorizer, "bob", AclOperation::Read, "orders-eu").await);
+        assert!(!can(&authorizer, "bob", AclOperation::Write, "orders-eu").await);
+        assert!(can(&authorizer, "bob", AclOperation::Write, "orders-us").await);
+        assert!(can(&authorizer, "admin", AclOperation::Write, "payments").await);
+    }
+
+    #[tokio::test]
+    async fn allow_if_no_acls_applies_only_to_resources_without_acls() {
+        let acls = vec![acl(
+            "User:alice",
+            "orders",
+            PatternType::Literal,
+            AclOperation::Read,
+            AclPermission::Allow,
+        )];
+
+        let open = authorizer(true, acls.clone()).await;
+        assert!(can(&open, "bob", AclOperation::Write, "payments").await);
+        assert!(!can(&open, "bob", AclOperation::Read, "orders").await);
+
+        let closed = authorizer(false, acls).await;
+        assert!(!can(&closed, "bob", AclOperation::Write, "payments").await);
+        assert!(can(&closed, "alice", AclOperation::Read, "orders").await);
+        assert!(
+            !closed
+                .is_authorized(None, AclOperation::Read, ResourceType::Topic, "orders")
+                .await
+        );
+    }
+
+    #[tokio::test]
+    async fn acls_are_saved_and_reloaded() {
+        let dir = std::env::temp_dir().join(format!("meier-acl-{}", std::process::id()));
+        let path = dir.join(ACL_FILE);
+        let binding = acl(
+            "User:alice",
+            "orders",
+            PatternType::Literal,
+            AclOperation::Read,
+            AclPermission::Allow,
+        );
+
+        let authorizer = Authorizer::new(None, Some(path.clone())).unwrap();
+        authorizer.add(vec![binding.clone()]).await.unwrap();
+
+        let reloaded = Authorizer::new(None, Some(path)).unwrap();
+        assert!(reloaded.contains_all(&[binding]).await);
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
@@ -0,0 +1,722 @@
+use base64::{Engine, engine::general_purpose::STANDARD as BASE64};
+use futures::{SinkExt, StreamExt};
+use ring::{
+    digest, hmac, pbkdf2,
+    rand::{SecureRandom, SystemRandom},
+};
+use serde::{Deserialize, Serialize};
+use std::{
+    collections::{BTreeMap, HashMap},
+    fmt, fs,
+    num::NonZeroU32,
+    path::Path,
+    sync::Arc,
+};
+use tokio::io::{AsyncRead, AsyncWrite};
+use tokio_util::codec::Framed;
+
+use crate::{
+    Frame, MeierCodec, MeierError, Result,
+    config::{SaslConfig, SaslCredentials, SaslMechanism},
+};
+
+/// 비밀번호로 등록한 사용자의 SCRAM 반복 횟수
+pub const DEFAULT_SCRAM_ITERATIONS: u32 = 4096;
+
+const SALT_LEN: usize = 16;
+const NONCE_LEN: usize = 24;
+/// SCRAM-SHA-256 키 길이
+const KEY_LEN: usize = 32;
+
+/// 인증을 마친 사용자
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Principal {
+    pub name: String,
+    pub mechanism: SaslMechanism,
+}
+
+impl fmt::Display for Principal {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "User:{}", self.name)
+    }
+}
+
+/// 자격 증명 파일
+///
+/// ```toml
+/// [users.orders-service]
+/// password = "secret"
+///
+/// [users.billing]
+/// scram_sha_256 = { salt = "...", iterations = 4096, stored_key = "...", server_key = "..." }
+/// ```
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct CredentialsFile {
+    #[serde(default)]
+    pub users: BTreeMap<String, UserEntry>,
+}
+
+/// 사용자 항목(password 와 scram_sha_256 중 하나)
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct UserEntry {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub password: Option<String>,
+    /// 비밀번호 대신 저장하는 SCRAM 키(base64)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub scram_sha_256: Option<ScramEntry>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ScramEntry {
+    pub salt: String,
+    pub iterations: u32,
+    pub stored_key: String,
+    pub server_key: String,
+}
+
+/// SCRAM-SHA-256 저장 키
+#[derive(Clone)]
+pub struct ScramCredential {
+    salt: Vec<u8>,
+    iterations: u32,
+    stored_key: Vec<u8>,
+    server_key: Vec<u8>,
+}
+
+impl ScramCredential {
+    /// 임의의 salt 로 비밀번호에서 키 생성
+    pub fn new(password: &str, iterations: u32) -> Result<Self> {
+        Ok(Self::derive(password, random_bytes(SALT_LEN)?, iterations))
+    }
+
+    fn derive(password: &str, salt: Vec<u8>, iterations: u32) -> Self {
+        let salted = salted_password(password, &salt, iterations);
+        let client_key = hmac_sign(&salted, b"Client Key");
+        Self {
+            salt,
+            iterations,
+            stored_key: digest::digest(&digest::SHA256, &client_key)
+                .as_ref()
+                .to_vec(),
+            server_key: hmac_sign(&salted, b"Server Key"),
+        }
+    }
+
+    fn from_entry(entry: &ScramEntry) -> std::result::Result<Self, String> {
+        let decode = |value: &str| BASE64.decode(value).map_err(|e| e.to_string());
+        let credential = Self {
+            salt: decode(&entry.salt)?,
+            iterations: entry.iterations,
+            stored_key: decode(&entry.stored_key)?,
+            server_key: decode(&entry.server_key)?,
+        };
+        if credential.iterations == 0 {
+            return Err("iterations must be greater than 0".to_string());
+        }
+        if credential.stored_key.len() != KEY_LEN || credential.server_key.len() != KEY_LEN {
+            return Err(format!(
+                "stored_key and server_key must be {} bytes",
+                KEY_LEN
+            ));
+        }
+        Ok(credential)
+    }
+
+    pub fn to_entry(&self) -> ScramEntry {
+        ScramEntry {
+            salt: BASE64.encode(&self.salt),
+            iterations: self.iterations,
+            stored_key: BASE64.encode(&self.stored_key),
+            server_key: BASE64.encode(&self.server_key),
+        }
+    }
+
+    fn verify_password(&self, password: &str) -> bool {
+        let derived = Self::derive(password, self.salt.clone(), self.iterations);
+        constant_time_eq(&derived.stored_key, &self.stored_key)
+    }
+
+    /// 없는 사용자도 같은 과정을 거친 뒤 실패하도록 쓰는 임의의 키
+    fn dummy() -> Result<Self> {
+        Ok(Self {
+            salt: random_bytes(SALT_LEN)?,
+            iterations: DEFAULT_SCRAM_ITERATIONS,
+            stored_key: random_bytes(KEY_LEN)?,
+            server_key: random_bytes(KEY_LEN)?,
+        })
+    }
+}
+
+/// 자격 증명 파일을 읽어 사용자별 SCRAM 키 생성
+fn load_credentials(path: &Path) -> Result<HashMap<String, ScramCredential>> {
+    let content = fs::read_to_string(path)
+        .map_err(|e| MeierError::Config(format!("Failed to read {}: {}", path.display(), e)))?;
+    let file: CredentialsFile = toml::from_str(&content)
+        .map_err(|e| MeierError::Config(format!("Failed to parse {}: {}", path.display(), e)))?;
+
+    let mut credentials = HashMap::new();
+    for (name, entry) in file.users {
+        let credential = match (&entry.password, &entry.scram_sha_256) {
+            (Some(password), None) => ScramCredential::new(password, DEFAULT_SCRAM_ITERATIONS)?,
+            (None, Some(scram)) => ScramCredential::from_entry(scram).map_err(|e| {
+                MeierError::Config(format!("{}: users.{}: {}", path.display(), name, e))
+            })?,
+            _ => {
+                return Err(MeierError::Config(format!(
+                    "{}: users.{}: exactly one of password or scram_sha_256 is required",
+                    path.display(),
+                    name
+                )));
+            }
+        };
+        credentials.insert(name, credential);
+    }
+    Ok(credentials)
+}
+
+struct AuthenticatorInner {
+    mechanisms: Vec<SaslMechanism>,
+    credentials: HashMap<String, ScramCredential>,
+}
+
+/// 리스너 SASL 인증(설정이 없으면 인증 없이 요청 처리)
+#[derive(Clone, Default)]
+pub struct Authenticator {
+    inner: Option<Arc<AuthenticatorInner>>,
+}
+
+impl Authenticator {
+    pub fn new(config: Option<&SaslConfig>) -> Result<Self> {
+        let Some(config) = config else {
+            return Ok(Self::default());
+        };
+
+        Ok(Self {
+            inner: Some(Arc::new(AuthenticatorInner {
+                mechanisms: config.mechanisms.clone(),
+                credentials: load_credentials(&config.credentials_path)?,
+            })),
+        })
+    }
+
+    pub fn is_enabled(&self) -> bool {
+        self.inner.is_some()
+    }
+
+    pub fn session(&self) -> Session {
+        Session {
+            authenticator: self.clone(),
+            state: State::Start,
+            principal: None,
+        }
+    }
+}
+
+enum State {
+    Start,
+    Plain,
+    ScramClientFirst,
+    ScramClientFinal(Box<ScramExchange>),
+    Done,
+}
+
+/// client-final-message 확인에 필요한 SCRAM 교환 내용
+struct ScramExchange {
+    principal: Principal,
+    credential: ScramCredential,
+    /// 자격 증명 파일에 있는 사용자인지(없으면 임의의 키로 진행하고 실패)
+    known: bool,
+    gs2_header: String,
+    nonce: String,
+    client_first_bare: String,
+    server_first: String,
+}
+
+/// 연결별 인증 상태
+pub struct Session {
+    authenticator: Authenticator,
+    state: State,
+    principal: Option<Principal>,
+}
+
+impl Session {
+    /// 인증을 마쳤거나 인증이 필요 없는 연결
+    pub fn is_authenticated(&self) -> bool {
+        !self.authenticator.is_enabled() || self.principal.is_some()
+    }
+
+    pub fn principal(&self) -> Option<&Principal> {
+        self.principal.as_ref()
+    }
+
+    /// SaslHandshake 처리(응답 data 는 허용하는 메커니즘 목록)
+    pub fn handshake(&mut self, mechanism: &str) -> Result<Frame> {
+        let Some(inner) = &self.authenticator.inner else {
+            return Err(MeierError::Authentication(
+                "SASL is not enabled on this broker".to_string(),
+            ));
+        };
+        if self.principal.is_some() {
+            return Err(MeierError::Authentication(
+                "Connection is already authenticated".to_string(),
+            ));
+        }
+
+        let names: Vec<&str> = inner.mechanisms.iter().map(SaslMechanism::name).collect();
+        self.state = match SaslMechanism::from_name(mechanism) {
+            Some(SaslMechanism::Plain) if inner.mechanisms.contains(&SaslMechanism::Plain) => {
+                State::Plain
+            }
+            Some(SaslMechanism::ScramSha256)
+                if inner.mechanisms.contains(&SaslMechanism::ScramSha256) =>
+            {
+                State::ScramClientFirst
+            }
+            _ => {
+                return Err(MeierError::Authentication(format!(
+                    "Unsupported mechanism {} (enabled: {})",
+                    mechanism,
+                    names.join(", ")
+                )));
+            }
+        };
+        Frame::response_ok_json(&names)
+    }
+
+    /// SaslAuthenticate 처리(실패하면 연결을 닫아야 한다)
+    pub fn authenticate(&mut self, auth_bytes: &[u8]) -> Result<Frame> {
+        let Some(inner) = self.authenticator.inner.clone() else {
+            return Err(MeierError::Authentication(
+                "SASL is not enabled on this broker".to_string(),
+            ));
+        };
+
+        match std::mem::replace(&mut self.state, State::Done) {
+            State::Plain => {
+                let principal = verify_plain(&inner, auth_bytes)?;
+                self.principal = Some(principal);
+                Ok(Frame::response_ok(None))
+            }
+            State::ScramClientFirst => {
+                let message = utf8(auth_bytes)?;
+                let (gs2_header, client_first_bare) = split_gs2_header(message)?;
+                let mut username = None;
+                let mut client_nonce = None;
+                for (key, value) in attributes(client_first_bare)? {
+                    match key {
+                        'n' => username = Some(unescape_username(value)?),
+                        'r' => client_nonce = Some(value),
+                        'm' => return Err(scram_error("extensions are not supported")),
+                        _ => {}
+                    }
+                }
+                let username = username.ok_or_else(|| scram_error("missing username"))?;
+                let client_nonce = client_nonce.ok_or_else(|| scram_error("missing nonce"))?;
+                if let Some(authzid) = gs2_header
+                    .split(',')
+                    .nth(1)
+                    .and_then(|a| a.strip_prefix("a="))
+                    && unescape_username(authzid)? != username
+                {
+                    return Err(MeierError::Authentication(
+                        "Authorization identity must match the user".to_string(),
+                    ));
+                }
+
+                let (credential, known) = match inner.credentials.get(&username) {
+                    Some(credential) => (credential.clone(), true),
+                    None => (ScramCredential::dummy()?, false),
+                };
+                let nonce = format!(
+                    "{}{}",
+                    client_nonce,
+                    BASE64.encode(random_bytes(NONCE_LEN)?)
+                );
+                let server_first = format!(
+                    "r={},s={},i={}",
+                    nonce,
+                    BASE64.encode(&credential.salt),
+                    credential.iterations
+                );
+
+                self.state = State::ScramClientFinal(Box::new(ScramExchange {
+                    principal: Principal {
+                        name: username,
+                        mechanism: SaslMechanism::ScramSha256,
+                    },
+                    credential,
+                    known,
+                    gs2_header: gs2_header.to_string(),
+                    nonce,
+                    client_first_bare: client_first_bare.to_string(),
+                    server_first: server_first.clone(),
+                }));
+                Ok(Frame::response_ok(Some(server_first.into_bytes())))
+            }
+            State::ScramClientFinal(exchange) => {
+                let ScramExchange {
+                    principal,
+                    credential,
+                    known,
+                    gs2_header,
+                    nonce,
+                    client_first_bare,
+                    server_first,
+                } = *exchange;
+                let message = utf8(auth_bytes)?;
+                let (without_proof, proof) = message
+                    .rsplit_once(",p=")
+                    .ok_or_else(|| scram_error("missing proof"))?;
+                let mut channel_binding = None;
+                let mut final_nonce = None;
+                for (key, value) in attributes(without_proof)? {
+                    match key {
+                        'c' => channel_binding = Some(value),
+                        'r' => final_nonce = Some(value),
+                        _ => {}
+                    }
+                }
+                if channel_binding != Some(BASE64.encode(&gs2_header).as_str()) {
+                    return Err(scram_error("channel binding mismatch"));
+                }
+                if final_nonce != Some(nonce.as_str()) {
+                    return Err(scram_error("nonce mismatch"));
+                }
+
+                let auth_message =
+                    format!("{},{},{}", client_first_bare, server_first, without_proof);
+                let client_signature = hmac_sign(&credential.stored_key, auth_message.as_bytes());
+                let proof = BASE64
+                    .decode(proof)
+                    .map_err(|_| scram_error("invalid proof"))?;
+                if proof.len() != KEY_LEN {
+                    return Err(scram_error("invalid proof"));
+                }
+                let client_key = xor(&proof, &client_signature);
+                let stored_key = digest::digest(&digest::SHA256, &client_key);
+                if !known || !constant_time_eq(stored_key.as_ref(), &credential.stored_key) {
+                    return Err(MeierError::Authentication(
+                        "Invalid username or password".to_string(),
+                    ));
+                }
+
+                let server_signature = hmac_sign(&credential.server_key, auth_message.as_bytes());
+                self.principal = Some(principal);
+                Ok(Frame::response_ok(Some(
+                    format!("v={}", BASE64.encode(server_signature)).into_bytes(),
+                )))
+            }
+            State::Start | State::Done => Err(MeierError::Authentication(
+                "SaslHandshake is required before SaslAuthenticate".to_string(),
+            )),
+        }
+    }
+}
+
+/// PLAIN 메시지(authzid \0 authcid \0 passwd) 확인
+fn verify_plain(inner: &AuthenticatorInner, auth_bytes: &[u8]) -> Result<Principal> {
+    let message = utf8(auth_bytes)?;
+    let mut parts = message.split('\0');
+    let (Some(authzid), Some(username), Some(password), None) =
+        (parts.next(), parts.next(), parts.next(), parts.next())
+    else {
+        return Err(MeierError::Authentication(
+            "Invalid PLAIN message".to_string(),
+        ));
+    };
+    if !authzid.is_empty() && authzid != username {
+        return Err(MeierError::Authentication(
+            "Authorization identity must match the user".to_string(),
+        ));
+    }
+
+    // 없는 사용자도 같은 키 유도를 거쳐 응답 시간으로 사용자 존재 여부를 알 수 없게 한다
+    let dummy;
+    let (credential, known) = match inner.credentials.get(username) {
+        Some(credential) => (credential, true),
+        None => {
+            dummy = ScramCredential::dummy()?;
+            (&dummy, false)
+        }
+    };
+    if credential.verify_password(password) && known {
+        Ok(Principal {
+            name: username.to_string(),
+            mechanism: SaslMechanism::Plain,
+        })
+    } else {
+        Err(MeierError::Authentication(
+            "Invalid username or password".to_string(),
+        ))
+    }
+}
+
+/// 연결 직후 브로커에 SASL 인증
+pub async fn authenticate<S>(
+    framed: &mut Framed<S, MeierCodec>,
+    credentials: &SaslCredentials,
+) -> Result<()>
+where
+    S: AsyncRead + AsyncWrite + Unpin,
+{
+    request(
+        framed,
+        Frame::SaslHandshake {
+            mechanism: credentials.mechanism.name().to_string(),
+        },
+    )
+    .await?;
+
+    match credentials.mechanism {
+        SaslMechanism::Plain => {
+            let auth_bytes =
+                format!("\0{}\0{}", credentials.username, credentials.password).into_bytes();
+            request(framed, Frame::SaslAuthenticate { auth_bytes }).await?;
+            Ok(())
+        }
+        SaslMechanism::ScramSha256 => scram_authenticate(framed, credentials).await,
+    }
+}
+
+async fn scram_authenticate<S>(
+    framed: &mut Framed<S, MeierCodec>,
+    credentials: &SaslCredentials,
+) -> Result<()>
+where
+    S: AsyncRead + AsyncWrite + Unpin,
+{
+    let client_nonce = BASE64.encode(random_bytes(NONCE_LEN)?);
+    let client_first_bare = format!(
+        "n={},r={}",
+        escape_username(&credentials.username),
+        client_nonce
+    );
+    let server_first = request(
+        framed,
+        Frame::SaslAuthenticate {
+            auth_bytes: format!("n,,{}", client_first_bare).into_bytes(),
+        },
+    )
+    .await?;
+
+    let mut nonce = None;
+    let mut salt = None;
+    let mut iterations = None;
+    for (key, value) in attributes(&server_first)? {
+        match key {
+            'r' => nonce = Some(value),
+            's' => salt = BASE64.decode(value).ok(),
+            'i' => iterations = value.parse::<u32>().ok().filter(|&i| i > 0),
+            _ => {}
+        }
+    }
+    let (Some(nonce), Some(salt), Some(iterations)) = (nonce, salt, iterations) else {
+        return Err(scram_error("invalid server-first message"));
+    };
+    if !nonce.starts_with(&client_nonce) {
+        return Err(scram_error("nonce mismatch"));
+    }
+
+    let salted = salted_password(&credentials.password, &salt, iterations);
+    let client_key = hmac_sign(&salted, b"Client Key");
+    let stored_key = digest::digest(&digest::SHA256, &client_key);
+    // c=biws 는 base64("n,,")
+    let without_proof = format!("c=biws,r={}", nonce);
+    let auth_message = format!("{},{},{}", client_first_bare, server_first, without_proof);
+    let client_signature = hmac_sign(stored_key.as_ref(), auth_message.as_bytes());
+    let proof = xor(&client_key, &client_signature);
+
+    let server_final = request(
+        framed,
+        Frame::SaslAuthenticate {
+            auth_bytes: format!("{},p={}", without_proof, BASE64.encode(proof)).into_bytes(),
+        },
+    )
+    .await?;
+
+    // 브로커도 같은 키를 알고 있는지 확인
+    let server_key = hmac_sign(&salted, b"Server Key");
+    let expected = hmac_sign(&server_key, auth_message.as_bytes());
+    let signature = server_final
+        .strip_prefix("v=")
+        .and_then(|v| BASE64.decode(v).ok())
+        .ok_or_else(|| scram_error("invalid server-final message"))?;
+    if !constant_time_eq(&signature, &expected) {
+        return Err(MeierError::Authentication(
+            "Server signature mismatch".to_string(),
+        ));
+    }
+    Ok(())
+}
+
+/// 요청을 보내고 응답 data 를 문자열로 반환
+async fn request<S>(framed: &mut Framed<S, MeierCodec>, frame: Frame) -> Result<String>
+where
+    S: AsyncRead + AsyncWrite + Unpin,
+{
+    framed.send(frame).await?;
+    let response = framed
+        .next()
+        .await
+        .ok_or_else(|| MeierError::Protocol("Connection closed during authentication".into()))??
+        .into_result()?;
+
+    match response {
+        Frame::Response {
+            data: Some(data), ..
+        } => Ok(String::from_utf8_lossy(&data).into_owned()),
+        _ => Ok(String::new()),
+    }
+}
+
+/// gs2 헤더("n,," 등)와 client-first-message-bare 분리
+fn split_gs2_header(message: &str) -> Result<(&str, &str)> {
+    if message.starts_with("p=") {
+        return Err(scram_error("channel binding is not supported"));
+    }
+    if !message.starts_with("n,") && !message.starts_with("y,") {
+        return Err(scram_error("invalid gs2 header"));
+    }
+    let end = message[2..]
+        .find(',')
+        .map(|i| i + 3)
+        .ok_or_else(|| scram_error("invalid gs2 header"))?;
+    Ok(message.split_at(end))
+}
+
+/// "k=v,k=v" 형식의 SCRAM 속성
+fn attributes(message: &str) -> Result<Vec<(char, &str)>> {
+    message
+        .split(',')
+        .map(|attribute| {
+            // 키는 ASCII 한 글자(인증 전 입력이므로 바이트 위치로 자르지 않는다)
+            let (key, value) = attribute
+                .split_once('=')
+                .ok_or_else(|| scram_error("invalid attribute"))?;
+            let mut chars = key.chars();
+            match (chars.next(), chars.next()) {
+                (Some(key), None) if key.is_ascii_alphabetic() => Ok((key, value)),
+                _ => Err(scram_error("invalid attribute")),
+            }
+        })
+        .collect()
+}
+
+fn escape_username(username: &str) -> String {
+    username.replace('=', "=3D").replace(',', "=2C")
+}
+
+fn unescape_username(value: &str) -> Result<String> {
+    let mut result = String::with_capacity(value.len());
+    let mut rest = value;
+    while let Some(i) = rest.find('=') {
+        result.push_str(&rest[..i]);
+        match rest.get(i..i + 3) {
+            Some("=2C") => result.push(','),
+            Some("=3D") => result.push('='),
+            _ => return Err(scram_error("invalid username encoding")),
+        }
+        rest = &rest[i + 3..];
+    }
+    result.push_str(rest);
+    Ok(result)
+}
+
+fn salted_password(password: &str, salt: &[u8], iterations: u32) -> [u8; KEY_LEN] {
+    let mut salted = [0u8; KEY_LEN];
+    pbkdf2::derive(
+        pbkdf2::PBKDF2_HMAC_SHA256,
+        NonZeroU32::new(iterations).unwrap_or(NonZeroU32::MIN),
+        salt,
+        password.as_bytes(),
+        &mut salted,
+    );
+    salted
+}
+
+fn hmac_sign(key: &[u8], message: &[u8]) -> Vec<u8> {
+    let key = hmac::Key::new(hmac::HMAC_SHA256, key);
+    hmac::sign(&key, message).as_ref().to_vec()
+}
+
+fn xor(a: &[u8], b: &[u8]) -> Vec<u8> {
+    a.iter().zip(b).map(|(a, b)| a ^ b).collect()
+}
+
+fn constant_time_eq(a: &[u8], b: &[u8]) -> bool {
+    a.len() == b.len() && a.iter().zip(b).fold(0, |acc, (a, b)| acc | (a ^ b)) == 0
+}
+
+fn random_bytes(len: usize) -> Result<Vec<u8>> {
+    let mut bytes = vec![0u8; len];
+    SystemRandom::new()
+        .fill(&mut bytes)
+        .map_err(|_| MeierError::Authentication("Failed to generate random bytes".to_string()))?;
+    Ok(bytes)
+}
+
+fn utf8(bytes: &[u8]) -> Result<&str> {
+    std::str::from_utf8(bytes)
+        .map_err(|_| MeierError::Authentication("Message is not valid UTF-8".to_string()))
+}
+
+fn scram_error(reason: &str) -> MeierError {
+    MeierError::Authentication(format!("Invalid SCRAM message: {}", reason))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn authenticator_inner() -> AuthenticatorInner {
+        AuthenticatorInner {
+            mechanisms: vec![SaslMechanism::Plain],
+            credentials: HashMap::from([(
+                "alice".to_string(),
+                ScramCredential::new("secret", 4096).unwrap(),
+            )]),
+        }
+    }
+
+    #[test]
+    fn unescape_username_decodes_escapes_and_rejects_others() {
+        assert_eq!(unescape_username("a=2Cb=3Dc").unwrap(), "a,b=c");
+        assert_eq!(unescape_username("alice").unwrap(), "alice");
+        assert!(unescape_username("a=2").is_err());
+        assert!(unescape_username("a=41").is_err());
+
+        let name = "a=b,c";
+        assert_eq!(unescape_username(&escape_username(name)).unwrap(), name);
+    }
+
+    #[test]
+    fn gs2_header_is_split_from_the_bare_message() {
+        assert_eq!(
+            split_gs2_header("n,,n=alice,r=abc").unwrap(),
+            ("n,,", "n=alice,r=abc")
+        );
+        assert_eq!(
+            split_gs2_header("y,a=alice,n=alice,r=abc").unwrap(),
+            ("y,a=alice,", "n=alice,r=abc")
+        );
+        assert!(split_gs2_header("p=tls-unique,,n=alice,r=abc").is_err());
+        assert!(split_gs2_header("x,,n=alice,r=abc").is_err());
+        assert!(split_gs2_header("n,").is_err());
+    }
+
+    #[test]
+    fn plain_accepts_only_known_users_with_the_right_password() {
+        let inner = authenticator_inner();
+
+        let principal = verify_plain(&inner, b"\0alice\0secret").unwrap();
+        assert_eq!(principal.name, "alice");
+        assert!(verify_plain(&inner, b"alice\0alice\0secret").is_ok());
+
+        let wrong = verify_plain(&inner, b"\0alice\0wrong").unwrap_err();
+        let unknown = verify_plain(&inner, b"\0bob\0secret").unwrap_err();
+        assert_eq!(wrong.to_string(), unknown.to_string());
+
+        assert!(verify_plain(&inner, b"bob\0alice\0secret").is_err());
+        assert!(verify_plain(&inner, b"alice\0secret").is_err());
+    }
+}
@@ -0,0 +1,448 @@
+use clap::{Args, Subcommand};
+use futures::{SinkExt, StreamExt};
+use meier_core::{
+    Frame, MeierCodec, MeierError, Result,
+    auth::{CredentialsFile, DEFAULT_SCRAM_ITERATIONS, ScramCredential, UserEntry},
+    config::{BrokerId, ClientTlsConfig, SaslCredentials, SaslMechanism},
+    protocol::{
+        AclBinding, AclFilter, AclOperation, AclPermission, PartitionReassignment, PatternType,
+        ReassignmentStatus, ResourceType,
+    },
+    tls::Connector,
+};
+use std::{path::PathBuf, time::Duration};
+use tokio_util::codec::Framed;
+
+/// --wait 시 재배치 진행 상태 확인 주기
+const WAIT_INTERVAL: Duration = Duration::from_secs(1);
+
+/// 요청을 보낼 브로커와 TLS 설정
+#[derive(Args, Debug)]
+pub struct ConnectionArgs {
+    /// Broker to send the request to
+    #[arg(short, long, default_value = "127.0.0.1:2369")]
+    server: String,
+
+    /// CA certificate (PEM) to verify the broker with; enables TLS
+    #[arg(long)]
+    tls_ca: Option<PathBuf>,
+
+    /// Client certificate (PEM) for brokers that require mTLS
+    #[arg(long, requires = "tls_key")]
+    tls_cert: Option<PathBuf>,
+
+    /// Client private key (PEM) for brokers that require mTLS
+    #[arg(long, requires = "tls_cert")]
+    tls_key: Option<PathBuf>,
+
+    /// Name to verify in the broker certificate (default: host of --server)
+    #[arg(long)]
+    tls_server_name: Option<String>,
+
+    /// User to authenticate as on brokers that require SASL
+    #[arg(long, requires = "sasl_password")]
+    sasl_username: Option<String>,
+
+    #[arg(long, env = "MEIER_SASL_PASSWORD", hide_env_values = true)]
+    sasl_password: Option<String>,
+
+    #[arg(long, value_parser = parse_mechanism, default_value = "SCRAM-SHA-256")]
+    sasl_mechanism: SaslMechanism,
+}
+
+impl ConnectionArgs {
+    fn connector(&self) -> Result<Connector> {
+        let tls = self.tls_ca.clone().map(|ca_path| ClientTlsConfig {
+            ca_path,
+            cert_path: self.tls_cert.clone(),
+            key_path: self.tls_key.clone(),
+            server_name: self.tls_server_name.clone(),
+        });
+        let sasl = self.sasl_username.clone().map(|username| SaslCredentials {
+            mechanism: self.sasl_mechanism,
+            username,
+            password: self.sasl_password.clone().unwrap_or_default(),
+        });
+        Connector::new(tls.as_ref(), sasl.as_ref())
+    }
+}
+
+fn parse_mechanism(name: &str) -> std::result::Result<SaslMechanism, String> {
+    SaslMechanism::from_name(name)
+        .ok_or_else(|| format!("expected PLAIN or SCRAM-SHA-256, got {}", name))
+}
+
+/// 실행 중인 클러스터에 보내는 관리 명령
+#[derive(Subcommand, Debug)]
+pub enum Command {
+    /// Move the replicas of a partition to other brokers
+    Reassign {
+        #[command(flatten)]
+        connection: ConnectionArgs,
+
+        #[arg(long)]
+        topic: String,
+
+        #[arg(long)]
+        partition: usize,
+
+        /// New replicas, preferred leader first (e.g. 1,2,3)
+        #[arg(long, value_delimiter = ',', required = true)]
+        replicas: Vec<BrokerId>,
+
+        /// Maximum bytes per second copied to new replicas
+        #[arg(long)]
+        throttle: Option<u64>,
+
+        /// Wait until the reassignment completes
+        #[arg(long)]
+        wait: bool,
+    },
+    /// Move all partitions off a broker before removing it
+    Decommission {
+        #[command(flatten)]
+        connection: ConnectionArgs,
+
+        /// Broker to drain
+        #[arg(long)]
+        broker_id: BrokerId,
+
+        /// Maximum bytes per second copied to new replicas
+        #[arg(long)]
+        throttle: Option<u64>,
+
+        /// Wait until all partitions are moved
+        #[arg(long)]
+        wait: bool,
+    },
+    /// List partition reassignments in progress
+    Reassignments {
+        #[command(flatten)]
+        connection: ConnectionArgs,
+    },
+    /// Allow (or deny) a principal an operation on a resource
+    AddAcl {
+        #[command(flatten)]
+        connection: ConnectionArgs,
+
+        #[command(flatten)]
+        acl: AclArgs,
+
+        /// Operations: All, Read, Write, Create, Delete, Alter, Describe, ClusterAction
+        #[arg(long, value_delimiter = ',', value_parser = parse_variant::<AclOperation>, required = true)]
+        operation: Vec<AclOperation>,
+
+        /// Deny instead of allow
+        #[arg(long)]
+        deny: bool,
+    },
+    /// List ACLs (all if no filter is given)
+    Acls {
+        #[command(flatten)]
+        connection: ConnectionArgs,
+
+        #[command(flatten)]
+        filter: AclFilterArgs,
+    },
+    /// Remove the ACLs matching the filter
+    RemoveAcls {
+        #[command(flatten)]
+        connection: ConnectionArgs,
+
+        #[command(flatten)]
+        filter: AclFilterArgs,
+    },
+    /// Print a credentials file entry with SCRAM keys instead of the password
+    ScramCredential {
+        #[arg(long)]
+        username: String,
+
+        #[arg(long, env = "MEIER_SASL_PASSWORD", hide_env_values = true)]
+        password: String,
+
+        #[arg(long, default_value_t = DEFAULT_SCRAM_ITERATIONS)]
+        iterations: u32,
+    },
+}
+
+/// 추가할 ACL 의 principal 과 리소스
+#[derive(Args, Debug)]
+pub struct AclArgs {
+    /// User:<name>, or User:* for every user
+    #[arg(long)]
+    principal: String,
+
+    /// Topic, Group, TransactionalId or Cluster
+    #[arg(long, value_parser = parse_variant::<ResourceType>)]
+    resource_type: ResourceType,
+
+    /// Resource name (* for every resource; meier-cluster for Cluster)
+    #[arg(long)]
+    resource: String,
+
+    /// Literal or Prefixed
+    #[arg(long, value_parser = parse_variant::<PatternType>, default_value = "Literal")]
+    pattern: PatternType,
+}
+
+/// ACL 조회/삭제 조건(지정하지 않은 항목은 모두 일치)
+#[derive(Args, Debug)]
+pub struct AclFilterArgs {
+    #[arg(long)]
+    principal: Option<String>,
+
+    #[arg(long, value_parser = parse_variant::<ResourceType>)]
+    resource_type: Option<ResourceType>,
+
+    #[arg(long)]
+    resource: Option<String>,
+
+    #[arg(long, value_parser = parse_variant::<PatternType>)]
+    pattern: Option<PatternType>,
+
+    #[arg(long, value_parser = parse_variant::<AclOperation>)]
+    operation: Option<AclOperation>,
+
+    #[arg(long, value_parser = parse_variant::<AclPermission>)]
+    permission: Option<AclPermission>,
+}
+
+impl From<AclFilterArgs> for AclFilter {
+    fn from(args: AclFilterArgs) -> Self {
+        AclFilter {
+            principal: args.principal,
+            resource_type: args.resource_type,
+            resource_name: args.resource,
+            pattern_type: args.pattern,
+            operation: args.operation,
+            permission: args.permission,
+        }
+    }
+}
+
+/// 프로토콜의 enum 이름 그대로 파싱(예: Topic, Prefixed)
+fn parse_variant<T: serde::de::DeserializeOwned>(name: &str) -> std::result::Result<T, String> {
+    serde_json::from_value(serde_json::Value::String(name.to_string())).map_err(|e| e.to_string())
+}
+
+pub async fn run(command: Command) -> Result<()> {
+    match command {
+        Command::Reassign {
+            connection,
+            topic,
+            partition,
+            replicas,
+            throttle,
+            wait,
+        } => {
+            let frame = Frame::ReassignPartitions {
+                reassignments: vec![PartitionReassignment {
+                    topic: topic.clone(),
+                    partition_id: partition,
+                    replicas,
+                }],
+                throttle_bytes_per_sec: throttle,
+            };
+            print_message(request(&connection, frame).await?);
+
+            if wait {
+                wait_until_done(&connection, |status| {
+                    status.topic == topic && status.partition_id == partition
+                })
+                .await?;
+            }
+        }
+        Command::Decommission {
+            connection,
+            broker_id,
+            throttle,
+            wait,
+        } => {
+            let frame = Frame::DecommissionBroker {
+                broker_id,
+                throttle_bytes_per_sec: throttle,
+            };
+            let response = request(&connection, frame).await?;
+            let started: Vec<PartitionReassignment> = response.clone().into_json()?;
+            print_message(response);
+
+            if wait {
+                wait_until_done(&connection, |status| {
+                    started.iter().any(|reassignment| {
+                        reassignment.topic == status.topic
+                            && reassignment.partition_id == status.partition_id
+                    })
+                })
+                .await?;
+            }
+        }
+        Command::Reassignments { connection } => {
+            let statuses = list_reassignments(&connection).await?;
+            if statuses.is_empty() {
+                println!("No reassignments in progress");
+            }
+            for status in &statuses {
+                print_status(status);
+            }
+        }
+        Command::AddAcl {
+            connection,
+            acl,
+            operation,
+            deny,
+        } => {
+            let permission = match deny {
+                true => AclPermission::Deny,
+                false => AclPermission::Allow,
+            };
+            let acls = operation
+                .into_iter()
+                .map(|operation| AclBinding {
+                    principal: acl.principal.clone(),
+                    resource_type: acl.resource_type,
+                    resource_name: acl.resource.clone(),
+                    pattern_type: acl.pattern,
+                    operation,
+                    permission,
+                })
+                .collect();
+            print_message(request(&connection, Frame::CreateAcls { acls }).await?);
+        }
+        Command::Acls { connection, filter } => {
+            let acls: Vec<AclBinding> = request(
+                &connection,
+                Frame::DescribeAcls {
+                    filter: filter.into(),
+                },
+            )
+            .await?
+            .into_json()?;
+            if acls.is_empty() {
+                println!("No ACLs");
+            }
+            for acl in &acls {
+                print_acl(acl);
+            }
+        }
+        Command::RemoveAcls { connection, filter } => {
+            let acls: Vec<AclBinding> = request(
+                &connection,
+                Frame::DeleteAcls {
+                    filters: vec![filter.into()],
+                },
+            )
+            .await?
+            .into_json()?;
+            println!("Removed {} ACL(s)", acls.len());
+            for acl in &acls {
+                print_acl(acl);
+            }
+        }
+        Command::ScramCredential {
+            username,
+            password,
+            iterations,
+        } => {
+            if iterations == 0 {
+                return Err(MeierError::Config(
+                    "iterations must be greater than 0".to_string(),
+                ));
+            }
+            let mut file = CredentialsFile::default();
+            file.users.insert(
+                username,
+                UserEntry {
+                    password: None,
+                    scram_sha_256: Some(ScramCredential::new(&password, iterations)?.to_entry()),
+                },
+            );
+            let entry = toml::to_string(&file)
+                .map_err(|e| MeierError::Config(format!("Serialization error: {}", e)))?;
+            print!("{}", entry);
+        }
+    }
+    Ok(())
+}
+
+async fn request(connection: &ConnectionArgs, frame: Frame) -> Result<Frame> {
+    let server = &connection.server;
+    let stream = connection.connector()?.connect(server).await?;
+    let mut framed = Framed::new(stream, MeierCodec::default());
+    framed.send(frame).await?;
+
+    framed
+        .next()
+        .await
+        .ok_or_else(|| MeierError::Protocol(format!("Connection to {} closed", server)))??
+        .into_result()
+}
+
+async fn list_reassignments(connection: &ConnectionArgs) -> Result<Vec<ReassignmentStatus>> {
+    request(connection, Frame::ListReassignments)
+        .await?
+        .into_json()
+}
+
+/// filter 에 맞는 재배치가 모두 끝날 때까지 진행 상태 출력
+async fn wait_until_done<F>(connection: &ConnectionArgs, filter: F) -> Result<()>
+where
+    F: Fn(&ReassignmentStatus) -> bool,
+{
+    loop {
+        let statuses: Vec<_> = list_reassignments(connection)
+            .await?
+            .into_iter()
+            .filter(|status| filter(status))
+            .collect();
+        if statuses.is_empty() {
+            println!("Reassignment completed");
+            return Ok(());
+        }
+
+        for status in &statuses {
+            print_status(status);
+        }
+        tokio::time::sleep(WAIT_INTERVAL).await;
+    }
+}
+
+fn print_message(response: Frame) {
+    if let Frame::Response {
+        message: Some(message),
+        ..
+    } = response
+    {
+        println!("{}", message);
+    }
+}
+
+fn print_status(status: &ReassignmentStatus) {
+    let caught_up = status
+        .adding
+        .iter()
+        .filter(|id| status.isr.contains(id))
+        .count();
+    println!(
+        "{}-{}: {:?} -> {:?} (leader: {}, isr: {:?}, new replicas caught up: {}/{})",
+        status.topic,
+        status.partition_id,
+        status.replicas,
+        status.target,
+        status.leader,
+        status.isr,
+        caught_up,
+        status.adding.len()
+    );
+}
+
+fn print_acl(acl: &AclBinding) {
+    println!(
+        "{} {:?} {:?} on {:?} {} ({:?})",
+        acl.principal,
+        acl.permission,
+        acl.operation,
+        acl.resource_type,
+        acl.resource_name,
+        acl.pattern_type
+    );
+}
@@ -1,30 +1,416 @@
 use serde::{Deserialize, Serialize};
 use std::{
+    collections::BTreeMap,
     env, fs,
+    net::ToSocketAddrs,
     path::{Path, PathBuf},
 };
 
 use crate::{MeierError, Result};
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+/// 환경변수 접두어
+///
+/// `MEIER_<섹션>__<필드>` 형식으로 설정 파일 값을 덮어쓴다.
+/// 예: `MEIER_SERVER__BIND_ADDR=0.0.0.0:2369`, `MEIER_STORAGE__MAX_TOPICS=10`
+pub const ENV_PREFIX: &str = "MEIER";
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
 pub struct Config {
+    #[serde(default)]
     pub server: ServerConfig,
+    #[serde(default)]
     pub storage: StorageConfig,
+    #[serde(default)]
     pub logging: LoggingConfig,
+    #[serde(default)]
+    pub metrics: MetricsConfig,
+    #[serde(default)]
+    pub cluster: ClusterConfig,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct ServerConfig {
+    #[serde(default = "default_bind_addr")]
     pub bind_addr: String,
     #[serde(default = "default_max_connections")]
     pub max_connections: usize,
+    /// 프레임 최대 크기(바이트)
+    #[serde(default = "default_max_frame_bytes")]
+    pub max_frame_bytes: usize,
+    #[serde(default = "default_max_connections_per_ip")]
+    pub max_connections_per_ip: usize,
+    /// 요청이 없는 연결을 닫기까지의 시간(초), 0 이면 제한 없음
+    #[serde(default = "default_idle_timeout_secs")]
+    pub idle_timeout_secs: u64,
+    /// 요청 응답을 기다리는 최대 시간(초), 지나도 처리 중인 요청은 취소하지 않는다
+    #[serde(default = "default_request_timeout_secs")]
+    pub request_timeout_secs: u64,
+    /// 종료 시 처리 중인 요청을 기다리는 최대 시간(초)
+    #[serde(default = "default_shutdown_timeout_secs")]
+    pub shutdown_timeout_secs: u64,
+    /// 브로커가 속한 랙(가용 영역), 토픽 생성 시 파티션 복제본을 서로 다른 랙에 나눠 배치
+    #[serde(default)]
+    pub rack: Option<String>,
+    /// 설정하면 클라이언트와 다른 브로커의 연결을 TLS 로 받는다
+    #[serde(default)]
+    pub tls: Option<TlsConfig>,
+    /// 설정하면 SASL 인증을 마친 연결의 요청만 처리한다
+    #[serde(default)]
+    pub sasl: Option<SaslConfig>,
+    /// 설정하면 인증된 principal 의 요청을 ACL 로 인가한다
+    #[serde(default)]
+    pub acl: Option<AclConfig>,
+    /// principal / client id 별 produce, fetch, 요청 수 제한
+    #[serde(default)]
+    pub quotas: QuotaConfig,
+}
+
+/// 리스너 TLS 설정(PEM 파일)
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TlsConfig {
+    /// 서버 인증서 체인
+    pub cert_path: PathBuf,
+    pub key_path: PathBuf,
+    /// 설정하면 이 CA 가 서명한 클라이언트 인증서를 요구(mTLS)
+    #[serde(default)]
+    pub client_ca_path: Option<PathBuf>,
+    /// 다른 브로커의 인증서를 검증할 CA(없으면 client_ca_path 사용)
+    #[serde(default)]
+    pub peer_ca_path: Option<PathBuf>,
+}
+
+impl TlsConfig {
+    /// 다른 브로커에 연결할 때 사용할 설정(mTLS 면 서버 인증서를 클라이언트 인증서로 제시)
+    pub fn peer_client_config(&self) -> Option<ClientTlsConfig> {
+        let ca_path = self
+            .peer_ca_path
+            .clone()
+            .or_else(|| self.client_ca_path.clone())?;
+        let mtls = self.client_ca_path.is_some();
+
+        Some(ClientTlsConfig {
+            ca_path,
+            cert_path: mtls.then(|| self.cert_path.clone()),
+            key_path: mtls.then(|| self.key_path.clone()),
+            server_name: None,
+        })
+    }
+}
+
+/// TLS 로 브로커에 연결할 때의 설정(PEM 파일)
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ClientTlsConfig {
+    /// 브로커 인증서를 검증할 CA
+    pub ca_path: PathBuf,
+    /// 브로커가 mTLS 를 요구할 때 제시할 인증서 체인과 키
+    #[serde(default)]
+    pub cert_path: Option<PathBuf>,
+    #[serde(default)]
+    pub key_path: Option<PathBuf>,
+    /// 인증서에서 확인할 이름(없으면 접속 주소의 호스트)
+    #[serde(default)]
+    pub server_name: Option<String>,
+}
+
+/// 리스너 SASL 인증 설정
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SaslConfig {
+    /// 사용자 자격 증명 파일(TOML)
+    pub credentials_path: PathBuf,
+    /// 허용할 메커니즘
+    #[serde(default = "default_sasl_mechanisms")]
+    pub mechanisms: Vec<SaslMechanism>,
+    /// 다른 브로커에 연결할 때 사용할 자격 증명(클러스터 모드에서 필수)
+    #[serde(default)]
+    pub inter_broker: Option<SaslCredentials>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum SaslMechanism {
+    #[serde(rename = "PLAIN")]
+    Plain,
+    #[serde(rename = "SCRAM-SHA-256")]
+    ScramSha256,
+}
+
+impl SaslMechanism {
+    pub fn name(&self) -> &'static str {
+        match self {
+            SaslMechanism::Plain => "PLAIN",
+            SaslMechanism::ScramSha256 => "SCRAM-SHA-256",
+        }
+    }
+
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "PLAIN" => Some(SaslMechanism::Plain),
+            "SCRAM-SHA-256" => Some(SaslMechanism::ScramSha256),
+            _ => None,
+        }
+    }
+}
+
+/// ACL 인가 설정
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct AclConfig {
+    /// ACL 과 관계없이 모든 요청이 허용되는 principal(예: "User:admin")
+    #[serde(default)]
+    pub super_users: Vec<String>,
+    /// 리소스에 일치하는 ACL 이 하나도 없으면 허용
+    #[serde(default)]
+    pub allow_if_no_acls: bool,
+}
+
+/// 클라이언트별 쿼터 설정
+///
+/// principal 설정, client id 설정, 기본값 순으로 먼저 지정된 값을 사용한다.
+/// principal 설정이 있으면 그 principal 의 모든 연결이, client id 설정이 있으면 같은
+/// client id 의 모든 연결이 한도를 나눠 쓰고, 기본값만 적용되면 principal 과 client id
+/// 조합마다 따로 계산한다.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct QuotaConfig {
+    #[serde(default)]
+    pub default: QuotaLimits,
+    /// principal 별 설정(예: "User:orders", 인증하지 않은 연결은 "User:ANONYMOUS")
+    #[serde(default)]
+    pub users: BTreeMap<String, QuotaLimits>,
+    /// SetClientId 로 지정한 client id 별 설정
+    #[serde(default)]
+    pub clients: BTreeMap<String, QuotaLimits>,
+}
+
+/// 쿼터 한도(None 이면 제한 없음)
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct QuotaLimits {
+    /// 초당 produce 메시지 바이트
+    #[serde(default)]
+    pub produce_bytes_per_sec: Option<u64>,
+    /// 초당 consume 응답 메시지 바이트
+    #[serde(default)]
+    pub fetch_bytes_per_sec: Option<u64>,
+    /// 초당 요청 수
+    #[serde(default)]
+    pub requests_per_sec: Option<u64>,
+}
+
+impl QuotaLimits {
+    /// 지정하지 않은 항목을 fallback 값으로 채운다
+    pub fn or(self, fallback: QuotaLimits) -> Self {
+        Self {
+            produce_bytes_per_sec: self
+                .produce_bytes_per_sec
+                .or(fallback.produce_bytes_per_sec),
+            fetch_bytes_per_sec: self.fetch_bytes_per_sec.or(fallback.fetch_bytes_per_sec),
+            requests_per_sec: self.requests_per_sec.or(fallback.requests_per_sec),
+        }
+    }
+
+    fn validate(&self, name: &str, errors: &mut Vec<String>) {
+        let fields = [
+            ("produce_bytes_per_sec", self.produce_bytes_per_sec),
+            ("fetch_bytes_per_sec", self.fetch_bytes_per_sec),
+            ("requests_per_sec", self.requests_per_sec),
+        ];
+        for (field, value) in fields {
+            if value == Some(0) {
+                errors.push(format!("{}.{}: must be greater than 0", name, field));
+            }
+        }
+    }
+}
+
+/// SASL 로 브로커에 인증할 때의 자격 증명
+#[derive(Clone, PartialEq, Serialize, Deserialize)]
+pub struct SaslCredentials {
+    #[serde(default = "default_client_sasl_mechanism")]
+    pub mechanism: SaslMechanism,
+    pub username: String,
+    pub password: String,
+}
+
+// 로그에 비밀번호가 남지 않도록 가린다
+impl std::fmt::Debug for SaslCredentials {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("SaslCredentials")
+            .field("mechanism", &self.mechanism)
+            .field("username", &self.username)
+            .field("password", &"***")
+            .finish()
+    }
+}
+
+fn default_sasl_mechanisms() -> Vec<SaslMechanism> {
+    vec![SaslMechanism::ScramSha256, SaslMechanism::Plain]
+}
+
+fn default_client_sasl_mechanism() -> SaslMechanism {
+    SaslMechanism::ScramSha256
+}
+
+fn default_bind_addr() -> String {
+    "127.0.0.1:2369".to_string()
 }
 
 fn default_max_connections() -> usize {
     1000
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+fn default_max_frame_bytes() -> usize {
+    10 * 1024 * 1024 // 10MB
+}
+
+fn default_max_connections_per_ip() -> usize {
+    100
+}
+
+fn default_idle_timeout_secs() -> u64 {
+    600
+}
+
+fn default_request_timeout_secs() -> u64 {
+    30
+}
+
+fn default_shutdown_timeout_secs() -> u64 {
+    30
+}
+
+/// 브로커 식별자
+pub type BrokerId = u32;
+
+/// 클러스터 구성과 복제 설정
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ClusterConfig {
+    #[serde(default)]
+    pub broker_id: BrokerId,
+    /// 다른 브로커 목록(비어 있으면 단일 브로커, 있으면 모든 브로커가 메타데이터 컨트롤러로 참여)
+    #[serde(default)]
+    pub peers: Vec<PeerConfig>,
+    /// 새 토픽의 파티션별 복제본 수
+    #[serde(default = "default_replication_factor")]
+    pub replication_factor: usize,
+    /// acks=all 쓰기에 필요한 최소 ISR 크기
+    #[serde(default = "default_min_insync_replicas")]
+    pub min_insync_replicas: usize,
+    /// 팔로워가 이 시간(ms) 동안 리더를 따라잡지 못하면 ISR 에서 제외
+    #[serde(default = "default_replica_lag_time_max_ms")]
+    pub replica_lag_time_max_ms: u64,
+    /// 새 메시지가 없을 때 팔로워가 다시 가져오기까지 기다리는 시간(ms)
+    #[serde(default = "default_replica_fetch_interval_ms")]
+    pub replica_fetch_interval_ms: u64,
+    /// acks=all produce 가 ISR 복제를 기다리는 최대 시간(ms)
+    #[serde(default = "default_ack_timeout_ms")]
+    pub ack_timeout_ms: u64,
+    /// 컨트롤러 리더의 heartbeat 가 이 시간(ms)~2배 사이 동안 없으면 새 선거 시작
+    #[serde(default = "default_election_timeout_ms")]
+    pub election_timeout_ms: u64,
+    /// 컨트롤러 리더가 메타데이터 로그와 heartbeat 를 보내는 주기(ms)
+    #[serde(default = "default_heartbeat_interval_ms")]
+    pub heartbeat_interval_ms: u64,
+    /// 컨트롤러가 이 시간(ms) 동안 응답이 없는 브로커를 차단하고 파티션 리더를 옮긴다
+    #[serde(default = "default_broker_session_timeout_ms")]
+    pub broker_session_timeout_ms: u64,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PeerConfig {
+    pub id: BrokerId,
+    pub addr: String,
+}
+
+fn default_replication_factor() -> usize {
+    1
+}
+
+fn default_min_insync_replicas() -> usize {
+    1
+}
+
+fn default_replica_lag_time_max_ms() -> u64 {
+    10_000
+}
+
+fn default_replica_fetch_interval_ms() -> u64 {
+    50
+}
+
+fn default_ack_timeout_ms() -> u64 {
+    10_000
+}
+
+fn default_election_timeout_ms() -> u64 {
+    1_000
+}
+
+fn default_heartbeat_interval_ms() -> u64 {
+    200
+}
+
+fn default_broker_session_timeout_ms() -> u64 {
+    5_000
+}
+
+impl Default for ClusterConfig {
+    fn default() -> Self {
+        Self {
+            broker_id: 0,
+            peers: Vec::new(),
+            replication_factor: default_replication_factor(),
+            min_insync_replicas: default_min_insync_replicas(),
+            replica_lag_time_max_ms: default_replica_lag_time_max_ms(),
+            replica_fetch_interval_ms: default_replica_fetch_interval_ms(),
+            ack_timeout_ms: default_ack_timeout_ms(),
+            election_timeout_ms: default_election_timeout_ms(),
+            heartbeat_interval_ms: default_heartbeat_interval_ms(),
+            broker_session_timeout_ms: default_broker_session_timeout_ms(),
+        }
+    }
+}
+
+impl ClusterConfig {
+    /// 자신을 포함한 전체 브로커 ID(오름차순)
+    pub fn broker_ids(&self) -> Vec<BrokerId> {
+        let mut ids: Vec<_> = self.peers.iter().map(|peer| peer.id).collect();
+        ids.push(self.broker_id);
+        ids.sort();
+        ids
+    }
+
+    pub fn peer(&self, id: BrokerId) -> Option<&PeerConfig> {
+        self.peers.iter().find(|peer| peer.id == id)
+    }
+}
+
+/// Prometheus 메트릭 HTTP 엔드포인트 설정
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct MetricsConfig {
+    #[serde(default)]
+    pub enabled: bool,
+    #[serde(default = "default_metrics_bind_addr")]
+    pub bind_addr: String,
+    #[serde(default = "default_metrics_path")]
+    pub path: String,
+}
+
+fn default_metrics_bind_addr() -> String {
+    "127.0.0.1:9369".to_string()
+}
+
+fn default_metrics_path() -> String {
+    "/metrics".to_string()
+}
+
+impl Default for MetricsConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            bind_addr: default_metrics_bind_addr(),
+            path: default_metrics_path(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct StorageConfig {
     #[serde(default = "default_max_messages")]
     pub max_messages_per_partition: usize,
@@ -32,6 +418,22 @@ pub struct StorageConfig {
     pub max_message_size_bytes: usize,
     #[serde(default = "default_max_topics")]
     pub max_topics: usize,
+    /// 토픽 기본 파티션 수
+    #[serde(default = "default_partitions")]
+    pub default_partitions: usize,
+    /// 메시지 보존 기간(ms), None 이면 무제한
+    #[serde(default)]
+    pub retention_ms: Option<u64>,
+    #[serde(default)]
+    pub cleanup_policy: CleanupPolicy,
+    #[serde(default)]
+    pub compression: CompressionType,
+    /// 시작 후 이 시간(ms) 안에 커밋/중단되지 않은 트랜잭션은 중단
+    #[serde(default = "default_transaction_timeout_ms")]
+    pub transaction_timeout_ms: u64,
+    /// 브로커 데이터 저장 디렉토리
+    #[serde(default = "default_data_dir")]
+    pub data_dir: PathBuf,
 }
 
 fn default_max_messages() -> usize {
@@ -46,15 +448,187 @@ fn default_max_topics() -> usize {
     100
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+fn default_partitions() -> usize {
+    3
+}
+
+fn default_transaction_timeout_ms() -> u64 {
+    60_000
+}
+
+fn default_data_dir() -> PathBuf {
+    directories::ProjectDirs::from("", "", "meier")
+        .map(|dirs| dirs.data_dir().to_path_buf())
+        .unwrap_or_else(|| PathBuf::from("data"))
+}
+
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum CleanupPolicy {
+    /// 보존 기간이 지난 메시지 삭제
+    #[default]
+    Delete,
+}
+
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum CompressionType {
+    #[default]
+    None,
+    Gzip,
+}
+
+/// 토픽별 설정(브로커 기본값 위에 토픽 설정을 덮어쓴 결과)
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TopicConfig {
+    pub partitions: usize,
+    pub max_message_size_bytes: usize,
+    pub retention_ms: Option<u64>,
+    pub cleanup_policy: CleanupPolicy,
+    pub compression: CompressionType,
+}
+
+impl TopicConfig {
+    pub const PARTITIONS: &str = "partitions";
+    pub const MAX_MESSAGE_BYTES: &str = "max.message.bytes";
+    pub const RETENTION_MS: &str = "retention.ms";
+    pub const CLEANUP_POLICY: &str = "cleanup.policy";
+    pub const COMPRESSION_TYPE: &str = "compression.type";
+
+    pub const KEYS: [&str; 5] = [
+        Self::PARTITIONS,
+        Self::MAX_MESSAGE_BYTES,
+        Self::RETENTION_MS,
+        Self::CLEANUP_POLICY,
+        Self::COMPRESSION_TYPE,
+    ];
+
+    /// 기본값에 토픽별 설정 적용
+    pub fn with_overrides(&self, overrides: &BTreeMap<String, String>) -> Result<Self> {
+        let mut config = self.clone();
+        for (key, value) in overrides {
+            config.set(key, value)?;
+        }
+        Ok(config)
+    }
+
+    pub fn get(&self, key: &str) -> Option<String> {
+        let value = match key {
+            Self::PARTITIONS => self.partitions.to_string(),
+            Self::MAX_MESSAGE_BYTES => self.max_message_size_bytes.to_string(),
+            Self::RETENTION_MS => self
+                .retention_ms
+                .map(|ms| ms.to_string())
+                .unwrap_or_else(|| "-1".to_string()),
+            Self::CLEANUP_POLICY => match self.cleanup_policy {
+                CleanupPolicy::Delete => "delete".to_string(),
+            },
+            Self::COMPRESSION_TYPE => match self.compression {
+                CompressionType::None => "none".to_string(),
+                CompressionType::Gzip => "gzip".to_string(),
+            },
+            _ => return None,
+        };
+        Some(value)
+    }
+
+    fn set(&mut self, key: &str, value: &str) -> Result<()> {
+        let invalid = || MeierError::Config(format!("Invalid value for {}: {}", key, value));
+
+        match key {
+            Self::PARTITIONS => {
+                self.partitions = value.parse().map_err(|_| invalid())?;
+                if self.partitions == 0 {
+                    return Err(invalid());
+                }
+            }
+            Self::MAX_MESSAGE_BYTES => {
+                self.max_message_size_bytes = value.parse().map_err(|_| invalid())?
+            }
+            // -1 은 무제한
+            Self::RETENTION_MS => {
+                self.retention_ms = match value {
+                    "-1" => None,
+                    _ => Some(value.parse().map_err(|_| invalid())?),
+                }
+            }
+            Self::CLEANUP_POLICY => {
+                self.cleanup_policy = match value {
+                    "delete" => CleanupPolicy::Delete,
+                    "compact" => {
+                        return Err(MeierError::Config(
+                            "cleanup.policy=compact is not supported: messages have no key"
+                                .to_string(),
+                        ));
+                    }
+                    _ => return Err(invalid()),
+                }
+            }
+            Self::COMPRESSION_TYPE => {
+                self.compression = match value {
+                    "none" => CompressionType::None,
+                    "gzip" => CompressionType::Gzip,
+                    _ => return Err(invalid()),
+                }
+            }
+            _ => {
+                return Err(MeierError::Config(format!("Unknown topic config: {}", key)));
+            }
+        }
+        Ok(())
+    }
+}
+
+impl StorageConfig {
+    /// 토픽 설정 기본값
+    pub fn topic_defaults(&self) -> TopicConfig {
+        TopicConfig {
+            partitions: self.default_partitions,
+            max_message_size_bytes: self.max_message_size_bytes,
+            retention_ms: self.retention_ms,
+            cleanup_policy: self.cleanup_policy,
+            compression: self.compression,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
 pub struct LoggingConfig {
     #[serde(default)]
     pub level: LogLevel,
+    /// 모듈별 필터(예: `meier_core::storage=debug,meier_core::server=warn`)
+    #[serde(default)]
+    pub filter: Option<String>,
+    #[serde(default)]
+    pub format: LogFormat,
+    /// 로그 파일 경로(지정 시 콘솔과 함께 파일에도 기록)
+    #[serde(default)]
     pub file: Option<String>,
+    #[serde(default)]
+    pub rotation: LogRotation,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum LogFormat {
+    #[default]
+    Text,
+    Json,
+}
+
+/// 로그 파일 교체 주기
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum LogRotation {
+    Never,
+    Hourly,
+    #[default]
+    Daily,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize, clap::ValueEnum)]
 pub enum LogLevel {
+    #[default]
     INFO,
     DEBUG,
     ERROR,
@@ -62,30 +636,217 @@ pub enum LogLevel {
     FATAL,
 }
 
-impl Default for LogLevel {
+impl LogLevel {
+    /// tracing 에는 FATAL 이 없으므로 ERROR 로 취급
+    pub fn as_tracing_level(&self) -> tracing::Level {
+        match self {
+            LogLevel::INFO => tracing::Level::INFO,
+            LogLevel::DEBUG => tracing::Level::DEBUG,
+            LogLevel::ERROR | LogLevel::FATAL => tracing::Level::ERROR,
+            LogLevel::WARN => tracing::Level::WARN,
+        }
+    }
+}
+
+impl Default for ServerConfig {
     fn default() -> Self {
-        LogLevel::INFO
+        Self {
+            bind_addr: default_bind_addr(),
+            max_connections: default_max_connections(),
+            max_frame_bytes: default_max_frame_bytes(),
+            max_connections_per_ip: default_max_connections_per_ip(),
+            idle_timeout_secs: default_idle_timeout_secs(),
+            request_timeout_secs: default_request_timeout_secs(),
+            shutdown_timeout_secs: default_shutdown_timeout_secs(),
+            rack: None,
+            tls: None,
+            sasl: None,
+            acl: None,
+            quotas: QuotaConfig::default(),
+        }
     }
 }
 
-impl Config {
-    pub fn default() -> Self {
+impl Default for StorageConfig {
+    fn default() -> Self {
         Self {
-            server: ServerConfig {
-                bind_addr: "127.0.0.1:2369".to_string(),
-                max_connections: 1000,
-            },
-            storage: StorageConfig {
-                max_messages_per_partition: 10000,
-                max_message_size_bytes: 1024 * 1024,
-                max_topics: 100,
-            },
-            logging: LoggingConfig {
-                level: LogLevel::INFO,
-                file: None,
-            },
+            max_messages_per_partition: default_max_messages(),
+            max_message_size_bytes: default_max_size(),
+            max_topics: default_max_topics(),
+            default_partitions: default_partitions(),
+            retention_ms: None,
+            cleanup_policy: CleanupPolicy::default(),
+            compression: CompressionType::default(),
+            transaction_timeout_ms: default_transaction_timeout_ms(),
+            data_dir: default_data_dir(),
         }
     }
+}
+
+/// 설정 변경 내역
+#[derive(Debug, Default)]
+pub struct ConfigChanges {
+    /// 실행 중에 적용된 항목
+    pub applied: Vec<&'static str>,
+    /// 재시작해야 적용되는 항목
+    pub restart_required: Vec<&'static str>,
+}
+
+impl ConfigChanges {
+    pub fn is_empty(&self) -> bool {
+        self.applied.is_empty() && self.restart_required.is_empty()
+    }
+}
+
+impl Config {
+    /// new 와 비교해 바뀐 항목을 실행 중 적용 가능 여부로 나눠 반환
+    pub fn changes(&self, new: &Config) -> ConfigChanges {
+        let mut changes = ConfigChanges::default();
+        let mut check = |name: &'static str, changed: bool, live: bool| {
+            if !changed {
+                return;
+            }
+            if live {
+                changes.applied.push(name);
+            } else {
+                changes.restart_required.push(name);
+            }
+        };
+
+        let (old_server, new_server) = (&self.server, &new.server);
+        check(
+            "server.bind_addr",
+            old_server.bind_addr != new_server.bind_addr,
+            false,
+        );
+        check(
+            "server.max_connections",
+            old_server.max_connections != new_server.max_connections,
+            true,
+        );
+        check(
+            "server.max_frame_bytes",
+            old_server.max_frame_bytes != new_server.max_frame_bytes,
+            true,
+        );
+        check(
+            "server.max_connections_per_ip",
+            old_server.max_connections_per_ip != new_server.max_connections_per_ip,
+            true,
+        );
+        check(
+            "server.idle_timeout_secs",
+            old_server.idle_timeout_secs != new_server.idle_timeout_secs,
+            true,
+        );
+        check(
+            "server.request_timeout_secs",
+            old_server.request_timeout_secs != new_server.request_timeout_secs,
+            true,
+        );
+        check(
+            "server.shutdown_timeout_secs",
+            old_server.shutdown_timeout_secs != new_server.shutdown_timeout_secs,
+            true,
+        );
+        check("server.rack", old_server.rack != new_server.rack, false);
+        check("server.tls", old_server.tls != new_server.tls, false);
+        check("server.sasl", old_server.sasl != new_server.sasl, false);
+        check("server.acl", old_server.acl != new_server.acl, false);
+        check(
+            "server.quotas",
+            old_server.quotas != new_server.quotas,
+            true,
+        );
+
+        let (old_storage, new_storage) = (&self.storage, &new.storage);
+        check(
+            "storage.max_messages_per_partition",
+            old_storage.max_messages_per_partition != new_storage.max_messages_per_partition,
+            true,
+        );
+        check(
+            "storage.max_message_size_bytes",
+            old_storage.max_message_size_bytes != new_storage.max_message_size_bytes,
+            true,
+        );
+        check(
+            "storage.max_topics",
+            old_storage.max_topics != new_storage.max_topics,
+            true,
+        );
+        check(
+            "storage.default_partitions",
+            old_storage.default_partitions != new_storage.default_partitions,
+            true,
+        );
+        check(
+            "storage.retention_ms",
+            old_storage.retention_ms != new_storage.retention_ms,
+            true,
+        );
+        check(
+            "storage.cleanup_policy",
+            old_storage.cleanup_policy != new_storage.cleanup_policy,
+            true,
+        );
+        check(
+            "storage.compression",
+            old_storage.compression != new_storage.compression,
+            true,
+        );
+        check(
+            "storage.transaction_timeout_ms",
+            old_storage.transaction_timeout_ms != new_storage.transaction_timeout_ms,
+            true,
+        );
+        check(
+            "storage.data_dir",
+            old_storage.data_dir != new_storage.data_dir,
+            false,
+        );
+
+        let (old_logging, new_logging) = (&self.logging, &new.logging);
+        check(
+            "logging.level",
+            old_logging.level != new_logging.level,
+            true,
+        );
+        check(
+            "logging.filter",
+            old_logging.filter != new_logging.filter,
+            true,
+        );
+        check(
+            "logging.format",
+            old_logging.format != new_logging.format,
+            false,
+        );
+        check("logging.file", old_logging.file != new_logging.file, false);
+        check(
+            "logging.rotation",
+            old_logging.rotation != new_logging.rotation,
+            false,
+        );
+
+        let (old_metrics, new_metrics) = (&self.metrics, &new.metrics);
+        check(
+            "metrics.enabled",
+            old_metrics.enabled != new_metrics.enabled,
+            false,
+        );
+        check(
+            "metrics.bind_addr",
+            old_metrics.bind_addr != new_metrics.bind_addr,
+            false,
+        );
+        check("metrics.path", old_metrics.path != new_metrics.path, false);
+
+        // 파티션 배치가 바뀌므로 클러스터 설정은 재시작해야 적용된다
+        check("cluster", self.cluster != new.cluster, false);
+
+        changes
+    }
 
     pub fn user_config_file() -> Result<PathBuf> {
         let home = env::var("HOME")
@@ -139,7 +900,7 @@ impl Config {
     pub fn from_file<P: AsRef<Path>>(path: P) -> crate::error::Result<Self> {
         let settings = config::Config::builder()
             .add_source(config::File::with_name(path.as_ref().to_str().unwrap()))
-            .add_source(config::Environment::with_prefix("TESSERACT"))
+            .add_source(Self::environment())
             .build()
             .map_err(|e| crate::error::MeierError::Config(e.to_string()))?;
 
@@ -151,7 +912,7 @@ impl Config {
     // 환경변수 내 추출
     pub fn from_env() -> crate::error::Result<Self> {
         let settings = config::Config::builder()
-            .add_source(config::Environment::with_prefix("TESSERACT"))
+            .add_source(Self::environment())
             .build()
             .map_err(|e| crate::error::MeierError::Config(e.to_string()))?;
 
@@ -159,4 +920,292 @@ impl Config {
             .try_deserialize()
             .map_err(|e| crate::error::MeierError::Config(e.to_string()))
     }
+
+    fn environment() -> config::Environment {
+        config::Environment::with_prefix(ENV_PREFIX)
+            .prefix_separator("_")
+            .separator("__")
+    }
+
+    /// 설정 값 검사, 모든 오류를 모아 하나의 에러로 반환
+    pub fn validate(&self) -> Result<()> {
+        let mut errors = Vec::new();
+
+        if self.server.bind_addr.to_socket_addrs().is_err() {
+            errors.push(format!(
+                "server.bind_addr: invalid address '{}'",
+                self.server.bind_addr
+            ));
+        }
+        if self.server.max_connections == 0 {
+            errors.push("server.max_connections: must be greater than 0".to_string());
+        }
+        if self.server.max_connections_per_ip == 0 {
+            errors.push("server.max_connections_per_ip: must be greater than 0".to_string());
+        }
+        if self.server.max_frame_bytes == 0 || self.server.max_frame_bytes > u32::MAX as usize {
+            errors.push(format!(
+                "server.max_frame_bytes: must be between 1 and {}",
+                u32::MAX
+            ));
+        }
+        if self.server.request_timeout_secs == 0 {
+            errors.push("server.request_timeout_secs: must be greater than 0".to_string());
+        }
+        if self
+            .server
+            .rack
+            .as_deref()
+            .is_some_and(|rack| rack.trim().is_empty())
+        {
+            errors.push("server.rack: must not be empty".to_string());
+        }
+        if let Some(tls) = &self.server.tls
+            && !self.cluster.peers.is_empty()
+            && tls.peer_client_config().is_none()
+        {
+            errors.push(
+                "server.tls: peer_ca_path or client_ca_path is required to verify other brokers"
+                    .to_string(),
+            );
+        }
+        if let Some(sasl) = &self.server.sasl {
+            if sasl.mechanisms.is_empty() {
+                errors.push("server.sasl.mechanisms: must not be empty".to_string());
+            }
+            if !self.cluster.peers.is_empty() && sasl.inter_broker.is_none() {
+                errors.push(
+                    "server.sasl.inter_broker: credentials are required in cluster mode"
+                        .to_string(),
+                );
+            }
+        }
+        if let Some(acl) = &self.server.acl {
+            match &self.server.sasl {
+                None => errors
+                    .push("server.acl: server.sasl is required to identify principals".to_string()),
+                // 다른 브로커의 복제, 컨트롤러 요청은 ACL 없이 허용되어야 한다
+                Some(sasl) => {
+                    if let Some(credentials) = &sasl.inter_broker
+                        && !acl
+                            .super_users
+                            .contains(&format!("User:{}", credentials.username))
+                    {
+                        errors.push(format!(
+                            "server.acl.super_users: must include User:{} (server.sasl.inter_broker)",
+                            credentials.username
+                        ));
+                    }
+                }
+            }
+        }
+        let quotas = &self.server.quotas;
+        quotas
+            .default
+            .validate("server.quotas.default", &mut errors);
+        for (user, limits) in &quotas.users {
+            if !user.starts_with("User:") {
+                errors.push(format!(
+                    "server.quotas.users: '{}' must be User:<name>",
+                    user
+                ));
+            }
+            limits.validate(&format!("server.quotas.users.\"{}\"", user), &mut errors);
+        }
+        for (client_id, limits) in &quotas.clients {
+            limits.validate(
+                &format!("server.quotas.clients.\"{}\"", client_id),
+                &mut errors,
+            );
+        }
+
+        if self.storage.max_topics == 0 {
+            errors.push("storage.max_topics: must be greater than 0".to_string());
+        }
+        if self.storage.max_messages_per_partition == 0 {
+            errors.push("storage.max_messages_per_partition: must be greater than 0".to_string());
+        }
+        if self.storage.default_partitions == 0 {
+            errors.push("storage.default_partitions: must be greater than 0".to_string());
+        }
+        if self.storage.max_message_size_bytes == 0 {
+            errors.push("storage.max_message_size_bytes: must be greater than 0".to_string());
+        }
+        if self.storage.transaction_timeout_ms == 0 {
+            errors.push("storage.transaction_timeout_ms: must be greater than 0".to_string());
+        }
+        if self.storage.max_message_size_bytes > self.server.max_frame_bytes {
+            errors.push(format!(
+                "storage.max_message_size_bytes: {} exceeds server.max_frame_bytes ({})",
+                self.storage.max_message_size_bytes, self.server.max_frame_bytes
+            ));
+        }
+
+        let cluster = &self.cluster;
+        let broker_ids = cluster.broker_ids();
+        if broker_ids.windows(2).any(|w| w[0] == w[1]) {
+            errors.push(
+                "cluster.peers: broker ids must be unique and differ from broker_id".to_string(),
+            );
+        }
+        for peer in &cluster.peers {
+            if peer.addr.to_socket_addrs().is_err() {
+                errors.push(format!(
+                    "cluster.peers: invalid address '{}' for broker {}",
+                    peer.addr, peer.id
+                ));
+            }
+        }
+        if cluster.replication_factor == 0 || cluster.replication_factor > broker_ids.len() {
+            errors.push(format!(
+                "cluster.replication_factor: must be between 1 and the number of brokers ({})",
+                broker_ids.len()
+            ));
+        }
+        if cluster.min_insync_replicas == 0
+            || cluster.min_insync_replicas > cluster.replication_factor
+        {
+            errors.push(
+                "cluster.min_insync_replicas: must be between 1 and cluster.replication_factor"
+                    .to_string(),
+            );
+        }
+        if cluster.heartbeat_interval_ms == 0
+            || cluster.heartbeat_interval_ms * 2 > cluster.election_timeout_ms
+        {
+            errors.push(
+                "cluster.heartbeat_interval_ms: must be positive and at most half of cluster.election_timeout_ms"
+                    .to_string(),
+            );
+        }
+        // 복제 대기가 끝나기 전에 클라이언트 응답 대기 시간이 지나지 않도록 한다
+        if cluster.ack_timeout_ms == 0
+            || cluster.ack_timeout_ms >= self.server.request_timeout_secs * 1000
+        {
+            errors.push(
+                "cluster.ack_timeout_ms: must be positive and less than server.request_timeout_secs"
+                    .to_string(),
+            );
+        }
+        if cluster.broker_session_timeout_ms < cluster.election_timeout_ms {
+            errors.push(
+                "cluster.broker_session_timeout_ms: must be at least cluster.election_timeout_ms"
+                    .to_string(),
+            );
+        }
+
+        if self.metrics.enabled {
+            if self.metrics.bind_addr.to_socket_addrs().is_err() {
+                errors.push(format!(
+                    "metrics.bind_addr: invalid address '{}'",
+                    self.metrics.bind_addr
+                ));
+            }
+            if !self.metrics.path.starts_with('/') {
+                errors.push("metrics.path: must start with '/'".to_string());
+            }
+        }
+
+        if let Err(e) = crate::logging::env_filter(&self.logging) {
+            errors.push(format!("logging.filter: {}", e));
+        }
+
+        if errors.is_empty() {
+            Ok(())
+        } else {
+            Err(MeierError::Config(format!(
+                "Invalid configuration:\n  - {}",
+                errors.join("\n  - ")
+            )))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn load(toml: &str, env: &[(&str, &str)]) -> Config {
+        let env = env
+            .iter()
+            .map(|(key, value)| (key.to_string(), value.to_string()))
+            .collect();
+        config::Config::builder()
+            .add_source(config::File::from_str(toml, config::FileFormat::Toml))
+            .add_source(Config::environment().source(Some(env)))
+            .build()
+            .unwrap()
+            .try_deserialize()
+            .unwrap()
+    }
+
+    #[test]
+    fn environment_overrides_file() {
+        let config = load(
+            "[storage]\nmax_topics = 7\ndefault_partitions = 2\n",
+            &[
+                ("MEIER_STORAGE__MAX_TOPICS", "9"),
+                ("MEIER_SERVER__BIND_ADDR", "0.0.0.0:2400"),
+            ],
+        );
+
+        assert_eq!(config.storage.max_topics, 9);
+        assert_eq!(config.storage.default_partitions, 2);
+        assert_eq!(config.server.bind_addr, "0.0.0.0:2400");
+        assert_eq!(
+            config.storage.max_messages_per_partition,
+            default_max_messages()
+        );
+        assert!(config.validate().is_ok());
+    }
+
+    #[test]
+    fn validate_reports_every_error() {
+        let mut config = Config::default();
+        config.server.max_connections = 0;
+        config.storage.max_topics = 0;
+        config.storage.max_message_size_bytes = config.server.max_frame_bytes + 1;
+        config.cluster.replication_factor = 2;
+
+        let Err(MeierError::Config(message)) = config.validate() else {
+            panic!("expected a config error");
+        };
+        assert!(message.contains("server.max_connections"));
+        assert!(message.contains("storage.max_topics"));
+        assert!(message.contains("storage.max_message_size_bytes"));
+        assert!(message.contains("cluster.replication_factor"));
+        assert!(!message.contains("server.bind_addr"));
+    }
+
+    #[test]
+    fn validate_rejects_invalid_addresses_and_timeouts() {
+        let mut config = Config::default();
+        config.server.bind_addr = "not an address".to_string();
+        config.cluster.ack_timeout_ms = config.server.request_timeout_secs * 1000;
+
+        let Err(MeierError::Config(message)) = config.validate() else {
+            panic!("expected a config error");
+        };
+        assert!(message.contains("server.bind_addr: invalid address 'not an address'"));
+        assert!(message.contains("cluster.ack_timeout_ms"));
+    }
+
+    #[test]
+    fn changes_split_live_and_restart_required_settings() {
+        let old = Config::default();
+        let mut new = old.clone();
+        assert!(old.changes(&new).is_empty());
+
+        new.storage.max_messages_per_partition = 10;
+        new.logging.level = LogLevel::DEBUG;
+        new.server.bind_addr = "0.0.0.0:2400".to_string();
+        new.cluster.broker_id = 1;
+
+        let changes = old.changes(&new);
+        assert_eq!(
+            changes.applied,
+            ["storage.max_messages_per_partition", "logging.level"]
+        );
+        assert_eq!(changes.restart_required, ["server.bind_addr", "cluster"]);
+    }
 }
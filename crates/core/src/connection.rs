@@ -0,0 +1,98 @@
+use std::{
+    collections::HashMap,
+    net::IpAddr,
+    sync::{
+        Arc, Mutex,
+        atomic::{AtomicUsize, Ordering},
+    },
+};
+
+use crate::{MeierError, Result};
+
+/// 전체 및 클라이언트 IP 별 연결 수 관리
+pub struct ConnectionTracker {
+    max_connections: AtomicUsize,
+    max_connections_per_ip: AtomicUsize,
+    state: Mutex<TrackerState>,
+}
+
+#[derive(Default)]
+struct TrackerState {
+    total: usize,
+    per_ip: HashMap<IpAddr, usize>,
+}
+
+impl ConnectionTracker {
+    pub fn new(max_connections: usize, max_connections_per_ip: usize) -> Self {
+        Self {
+            max_connections: AtomicUsize::new(max_connections),
+            max_connections_per_ip: AtomicUsize::new(max_connections_per_ip),
+            state: Mutex::new(TrackerState::default()),
+        }
+    }
+
+    /// 연결 등록, 한도를 넘으면 에러. 반환된 guard 가 drop 되면 해제된다.
+    pub fn acquire(self: &Arc<Self>, ip: IpAddr) -> Result<ConnectionGuard> {
+        let max_connections = self.max_connections.load(Ordering::Relaxed);
+        let max_connections_per_ip = self.max_connections_per_ip.load(Ordering::Relaxed);
+        let mut state = self.state.lock().unwrap();
+
+        if state.total >= max_connections {
+            return Err(MeierError::Protocol(format!(
+                "Too many connections (max: {})",
+                max_connections
+            )));
+        }
+
+        let count = state.per_ip.entry(ip).or_insert(0);
+        if *count >= max_connections_per_ip {
+            return Err(MeierError::Protocol(format!(
+                "Too many connections from {} (max: {})",
+                ip, max_connections_per_ip
+            )));
+        }
+
+        *count += 1;
+        state.total += 1;
+
+        Ok(ConnectionGuard {
+            tracker: self.clone(),
+            ip,
+        })
+    }
+
+    /// 한도 변경(이미 맺어진 연결은 유지)
+    pub fn set_limits(&self, max_connections: usize, max_connections_per_ip: usize) {
+        self.max_connections
+            .store(max_connections, Ordering::Relaxed);
+        self.max_connections_per_ip
+            .store(max_connections_per_ip, Ordering::Relaxed);
+    }
+
+    pub fn total(&self) -> usize {
+        self.state.lock().unwrap().total
+    }
+
+    fn release(&self, ip: IpAddr) {
+        let mut state = self.state.lock().unwrap();
+        state.total = state.total.saturating_sub(1);
+
+        if let Some(count) = state.per_ip.get_mut(&ip) {
+            *count -= 1;
+            if *count == 0 {
+                state.per_ip.remove(&ip);
+            }
+        }
+    }
+}
+
+pub struct ConnectionGuard {
+    tracker: Arc<ConnectionTracker>,
+    ip: IpAddr,
+}
+
+impl Drop for ConnectionGuard {
+    fn drop(&mut self) {
+        self.tracker.release(self.ip);
+    }
+}
@@ -0,0 +1,1349 @@
+pub mod raft;
+pub mod store;
+
+use futures::{SinkExt, StreamExt};
+use std::{
+    collections::{BTreeMap, HashMap, HashSet},
+    sync::Arc,
+    time::{Duration, Instant},
+};
+use tokio::sync::{Mutex, RwLock, oneshot, watch};
+use tokio_util::{codec::Framed, sync::CancellationToken};
+use tracing::{debug, info, warn};
+
+use crate::{
+    Config, Frame, MeierCodec, MeierError, Result,
+    acl::Authorizer,
+    config::{BrokerId, ClusterConfig, PeerConfig},
+    controller::{
+        raft::{AppendRequest, RaftState, Role, VoteRequest},
+        store::MetadataStore,
+    },
+    protocol::{
+        AclBinding, AclFilter, BrokerInfo, ClusterResult, MetadataEntry, MetadataRecord,
+        MetadataResult, PartitionReassignment, RaftAppendResult, RaftVoteResult,
+        ReassignmentStatus, TopicAssignment,
+    },
+    storage::{Topic, TopicManager},
+    tls::{Connector, Stream},
+};
+
+/// 선거 시각 확인 주기
+const ELECTION_TICK: Duration = Duration::from_millis(50);
+
+type Connection = Framed<Stream, MeierCodec>;
+
+/// 제안한 항목이 적용되면 결과를 받을 요청(항목을 추가한 term 과 함께 보관)
+type PendingProposal = (u64, oneshot::Sender<Result<()>>);
+
+enum Rpc {
+    Vote(VoteRequest),
+    Append(AppendRequest),
+}
+
+/// 리더가 본 브로커 상태
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Liveness {
+    Alive,
+    Dead,
+    /// 리더가 된 직후라 아직 판단할 수 없음
+    Unknown,
+}
+
+/// Raft 로 복제되는 클러스터 메타데이터 컨트롤러
+///
+/// 클러스터의 모든 브로커가 컨트롤러 투표자로 참여한다. 토픽 생성/삭제, 설정 변경,
+/// 브로커 등록/차단, 파티션 리더 변경은 리더의 메타데이터 로그에 기록되고,
+/// 과반에 복제되어 커밋된 항목을 모든 브로커가 같은 순서로 `TopicManager` 에 적용한다.
+pub struct Controller {
+    raft: Mutex<RaftState>,
+    topic_manager: Arc<TopicManager>,
+    /// 메타데이터 로그의 ACL 을 적용할 대상
+    authorizer: Arc<Authorizer>,
+    cluster: ClusterConfig,
+    /// 이 브로커를 등록할 주소
+    local_addr: String,
+    /// 이 브로커의 랙
+    rack: Option<String>,
+    max_frame_bytes: usize,
+    connector: Connector,
+    /// 커밋된 마지막 인덱스(적용 태스크를 깨운다)
+    committed: watch::Sender<u64>,
+    /// 적용된 마지막 인덱스
+    applied: watch::Sender<u64>,
+    /// 새 항목 추가나 선거 시작 시 peer 태스크를 깨운다
+    wake: watch::Sender<u64>,
+    pending: Mutex<HashMap<u64, PendingProposal>>,
+    /// 메타데이터 로그에 등록된 브로커
+    brokers: RwLock<BTreeMap<BrokerId, BrokerInfo>>,
+    /// 리더: 팔로워가 RaftAppend 응답으로 알려 준 랙
+    peer_racks: Mutex<HashMap<BrokerId, Option<String>>>,
+}
+
+impl Controller {
+    /// storage.data_dir 아래 브로커별 디렉토리의 메타데이터 로그를 연다
+    ///
+    /// 로그는 재시작 후 리더에게서 커밋 위치를 받으면 처음부터 다시 적용된다.
+    pub fn open(
+        config: &Config,
+        topic_manager: Arc<TopicManager>,
+        authorizer: Arc<Authorizer>,
+        connector: Connector,
+    ) -> Result<Self> {
+        let cluster = config.cluster.clone();
+        let dir = config
+            .storage
+            .data_dir
+            .join("metadata")
+            .join(format!("broker-{}", cluster.broker_id));
+        let store = MetadataStore::open(&dir)?;
+        info!(
+            "Opened metadata log at {} ({} entries, term {})",
+            dir.display(),
+            store.last_index(),
+            store.term()
+        );
+
+        let raft = RaftState::new(
+            cluster.broker_id,
+            cluster.broker_ids(),
+            store,
+            Duration::from_millis(cluster.election_timeout_ms),
+        );
+
+        Ok(Self {
+            raft: Mutex::new(raft),
+            topic_manager,
+            authorizer,
+            cluster,
+            local_addr: config.server.bind_addr.clone(),
+            rack: config.server.rack.clone(),
+            max_frame_bytes: config.server.max_frame_bytes,
+            connector,
+            committed: watch::Sender::new(0),
+            applied: watch::Sender::new(0),
+            wake: watch::Sender::new(0),
+            pending: Mutex::new(HashMap::new()),
+            brokers: RwLock::new(BTreeMap::new()),
+            peer_racks: Mutex::new(HashMap::new()),
+        })
+    }
+
+    /// 선거, 로그 복제, 적용, 브로커 감시 태스크 시작
+    pub fn spawn(self: &Arc<Self>, shutdown: CancellationToken) {
+        tokio::spawn(self.clone().run_election_timer(shutdown.clone()));
+        tokio::spawn(self.clone().run_applier(shutdown.clone()));
+        tokio::spawn(self.clone().run_broker_monitor(shutdown.clone()));
+        for peer in self.cluster.peers.clone() {
+            tokio::spawn(self.clone().run_peer(peer, shutdown.clone()));
+        }
+    }
+
+    pub async fn is_leader(&self) -> bool {
+        self.raft.lock().await.is_leader()
+    }
+
+    pub async fn describe(&self) -> ClusterResult {
+        let (controller_id, term, commit_index) = {
+            let raft = self.raft.lock().await;
+            (raft.leader_id(), raft.term(), raft.commit_index())
+        };
+
+        ClusterResult {
+            controller_id,
+            term,
+            commit_index,
+            brokers: self.brokers.read().await.values().cloned().collect(),
+        }
+    }
+
+    pub async fn handle_vote(
+        &self,
+        term: u64,
+        candidate_id: BrokerId,
+        last_log_index: u64,
+        last_log_term: u64,
+    ) -> Result<RaftVoteResult> {
+        self.check_voter(candidate_id)?;
+        self.raft
+            .lock()
+            .await
+            .handle_vote(term, candidate_id, last_log_index, last_log_term)
+    }
+
+    pub async fn handle_append(
+        &self,
+        term: u64,
+        leader_id: BrokerId,
+        prev_log_index: u64,
+        prev_log_term: u64,
+        entries: Vec<MetadataEntry>,
+        leader_commit: u64,
+    ) -> Result<RaftAppendResult> {
+        self.check_voter(leader_id)?;
+        let mut raft = self.raft.lock().await;
+        let mut result = raft.handle_append(
+            term,
+            leader_id,
+            prev_log_index,
+            prev_log_term,
+            entries,
+            leader_commit,
+        )?;
+        self.notify_commit(&raft);
+        result.rack = self.rack.clone();
+        Ok(result)
+    }
+
+    fn check_voter(&self, id: BrokerId) -> Result<()> {
+        match self.cluster.peer(id) {
+            Some(_) => Ok(()),
+            None => Err(MeierError::Protocol(format!(
+                "Unknown controller: broker {}",
+                id
+            ))),
+        }
+    }
+
+    /// 토픽 생성 후 파티션 수 반환(리더가 아니면 리더에게 전달)
+    pub async fn create_topic(
+        &self,
+        name: String,
+        configs: BTreeMap<String, String>,
+    ) -> Result<usize> {
+        // 설정은 리더의 기본값으로 정해 모든 브로커가 같은 설정으로 생성한다
+        if !self.is_leader().await {
+            self.forward(
+                Frame::CreateTopic {
+                    topic: name.clone(),
+                    configs,
+                },
+                1,
+            )
+            .await?;
+            self.wait_until(|| async { self.topic_manager.get_topic(&name).await.is_some() })
+                .await;
+            return match self.topic_manager.get_topic(&name).await {
+                Some(topic) => Ok(topic.partitions().len()),
+                None => Err(MeierError::Controller(format!(
+                    "Topic {} was created but is not applied on this broker yet",
+                    name
+                ))),
+            };
+        }
+
+        let config = self
+            .topic_manager
+            .topic_defaults()
+            .await
+            .with_overrides(&configs)?;
+
+        // 적용 시 실패할 항목이 로그에 남지 않도록 미리 확인
+        if self.topic_manager.get_topic(&name).await.is_some() {
+            return Err(MeierError::Storage(format!(
+                "Topic already exists: {}",
+                name
+            )));
+        }
+
+        // 응답이 없거나 제거 중인 브로커에는 새 복제본을 배치하지 않는다
+        let (brokers, racks) = {
+            let registered = self.brokers.read().await;
+            let brokers: Vec<BrokerId> = self
+                .cluster
+                .broker_ids()
+                .into_iter()
+                .filter(|id| {
+                    !registered
+                        .get(id)
+                        .is_some_and(|b| b.fenced || b.decommissioned)
+                })
+                .collect();
+            let racks: HashMap<BrokerId, String> = registered
+                .values()
+                .filter_map(|broker| Some((broker.id, broker.rack.clone()?)))
+                .collect();
+            (brokers, racks)
+        };
+        if brokers.is_empty() {
+            return Err(MeierError::Controller(
+                "No broker available for new replicas".to_string(),
+            ));
+        }
+
+        let assignment = TopicAssignment {
+            replicas: self
+                .topic_manager
+                .assign_replicas(config.partitions, &brokers, &racks)
+                .await,
+            name,
+            configs,
+            config: Some(config.clone()),
+        };
+        self.propose(MetadataRecord::CreateTopic { assignment })
+            .await?;
+        Ok(config.partitions)
+    }
+
+    /// 토픽이 없으면 브로커 기본 설정으로 생성
+    pub async fn get_or_create_topic(&self, name: String) -> Result<Arc<Topic>> {
+        if let Some(topic) = self.topic_manager.get_topic(&name).await {
+            return Ok(topic);
+        }
+
+        // 다른 요청이 먼저 생성했으면 그 토픽을 사용
+        if let Err(e) = self.create_topic(name.clone(), BTreeMap::new()).await {
+            return self.topic_manager.get_topic(&name).await.ok_or(e);
+        }
+        self.topic_manager
+            .get_topic(&name)
+            .await
+            .ok_or(MeierError::TopicNotFound(name))
+    }
+
+    pub async fn delete_topic(&self, name: String) -> Result<()> {
+        if !self.is_leader().await {
+            self.forward(
+                Frame::DeleteTopic {
+                    topic: name.clone(),
+                },
+                1,
+            )
+            .await?;
+            self.wait_until(|| async { self.topic_manager.get_topic(&name).await.is_none() })
+                .await;
+            return Ok(());
+        }
+
+        self.propose(MetadataRecord::DeleteTopic { name }).await
+    }
+
+    pub async fn alter_configs(
+        &self,
+        topic: String,
+        set: BTreeMap<String, String>,
+        delete: Vec<String>,
+    ) -> Result<()> {
+        if !self.is_leader().await {
+            self.forward(Frame::AlterConfigs { topic, set, delete }, 1)
+                .await?;
+            return Ok(());
+        }
+
+        self.propose(MetadataRecord::AlterConfigs { topic, set, delete })
+            .await
+    }
+
+    pub async fn create_acls(&self, acls: Vec<AclBinding>) -> Result<()> {
+        if !self.is_leader().await {
+            self.forward(Frame::CreateAcls { acls: acls.clone() }, 1)
+                .await?;
+            self.wait_until(|| async { self.authorizer.contains_all(&acls).await })
+                .await;
+            return Ok(());
+        }
+
+        self.propose(MetadataRecord::CreateAcls { acls }).await
+    }
+
+    /// 조건에 맞는 ACL 삭제(리더가 조건을 다시 적용하므로 acls 는 대기에만 사용)
+    pub async fn delete_acls(&self, filters: Vec<AclFilter>, acls: Vec<AclBinding>) -> Result<()> {
+        if !self.is_leader().await {
+            self.forward(Frame::DeleteAcls { filters }, 1).await?;
+            self.wait_until(|| async { !self.authorizer.contains_any(&acls).await })
+                .await;
+            return Ok(());
+        }
+
+        self.propose(MetadataRecord::DeleteAcls { acls }).await
+    }
+
+    /// 파티션 복제본 재배치 시작
+    ///
+    /// 새 복제본이 리더를 따라잡으면 브로커 감시 태스크가 복제본 목록을 교체한다.
+    pub async fn reassign_partitions(
+        &self,
+        reassignments: Vec<PartitionReassignment>,
+        throttle_bytes_per_sec: Option<u64>,
+    ) -> Result<()> {
+        if throttle_bytes_per_sec == Some(0) {
+            return Err(MeierError::Controller(
+                "throttle_bytes_per_sec must be greater than 0".to_string(),
+            ));
+        }
+
+        if !self.is_leader().await {
+            self.forward(
+                Frame::ReassignPartitions {
+                    reassignments: reassignments.clone(),
+                    throttle_bytes_per_sec,
+                },
+                reassignments.len(),
+            )
+            .await?;
+            self.wait_until(|| self.reassignments_started(&reassignments))
+                .await;
+            return Ok(());
+        }
+
+        let brokers = self.brokers.read().await.clone();
+        for (i, reassignment) in reassignments.iter().enumerate() {
+            if reassignments[..i].iter().any(|other| {
+                other.topic == reassignment.topic && other.partition_id == reassignment.partition_id
+            }) {
+                return Err(MeierError::Controller(format!(
+                    "Partition {} of topic {} is listed more than once",
+                    reassignment.partition_id, reassignment.topic
+                )));
+            }
+            self.check_reassignment(reassignment, &brokers).await?;
+        }
+
+        for reassignment in reassignments {
+            self.propose(MetadataRecord::ReassignPartition {
+                topic: reassignment.topic,
+                partition_id: reassignment.partition_id,
+                replicas: reassignment.replicas,
+                throttle_bytes_per_sec,
+            })
+            .await?;
+        }
+        Ok(())
+    }
+
+    async fn check_reassignment(
+        &self,
+        reassignment: &PartitionReassignment,
+        brokers: &BTreeMap<BrokerId, BrokerInfo>,
+    ) -> Result<()> {
+        let PartitionReassignment {
+            topic,
+            partition_id,
+            replicas,
+        } = reassignment;
+
+        let partition = self
+            .topic_manager
+            .get_partition(topic, *partition_id)
+            .await?;
+        if partition.replica_state().await.reassignment().is_some() {
+            return Err(MeierError::Controller(format!(
+                "Partition {} of topic {} is already being reassigned",
+                partition_id, topic
+            )));
+        }
+
+        if replicas.len() < self.cluster.min_insync_replicas {
+            return Err(MeierError::Controller(format!(
+                "Partition {} of topic {} needs at least {} replica(s) (cluster.min_insync_replicas)",
+                partition_id, topic, self.cluster.min_insync_replicas
+            )));
+        }
+        for (i, id) in replicas.iter().enumerate() {
+            if replicas[..i].contains(id) {
+                return Err(MeierError::Controller(format!(
+                    "Broker {} is listed more than once for partition {} of topic {}",
+                    id, partition_id, topic
+                )));
+            }
+            match brokers.get(id) {
+                Some(broker) if !broker.fenced && !broker.decommissioned => {}
+                _ => {
+                    return Err(MeierError::Controller(format!(
+                        "Broker {} is not available for new replicas",
+                        id
+                    )));
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// 전달한 재배치가 이 브로커에 적용되었는지(이미 완료된 경우 포함)
+    async fn reassignments_started(&self, reassignments: &[PartitionReassignment]) -> bool {
+        for reassignment in reassignments {
+            let Ok(partition) = self
+                .topic_manager
+                .get_partition(&reassignment.topic, reassignment.partition_id)
+                .await
+            else {
+                continue;
+            };
+            let replica = partition.replica_state().await;
+            if !reassignment
+                .replicas
+                .iter()
+                .all(|id| replica.replicas().contains(id))
+            {
+                return false;
+            }
+        }
+        true
+    }
+
+    /// 브로커의 모든 파티션 복제본을 다른 브로커로 옮기고 시작한 재배치 반환
+    ///
+    /// 대신할 브로커는 남은 복제본과 랙이 겹치지 않고 복제본이 적은 브로커를 고른다.
+    pub async fn decommission_broker(
+        &self,
+        broker_id: BrokerId,
+        throttle_bytes_per_sec: Option<u64>,
+    ) -> Result<Vec<PartitionReassignment>> {
+        if throttle_bytes_per_sec == Some(0) {
+            return Err(MeierError::Controller(
+                "throttle_bytes_per_sec must be greater than 0".to_string(),
+            ));
+        }
+
+        if !self.is_leader().await {
+            // 브로커 제거 항목과 옮길 파티션마다 재배치 항목이 하나씩 기록된다
+            let records = 1 + self.partitions_on(broker_id).await;
+            let started: Vec<PartitionReassignment> = self
+                .forward(
+                    Frame::DecommissionBroker {
+                        broker_id,
+                        throttle_bytes_per_sec,
+                    },
+                    records,
+                )
+                .await?
+                .into_json()?;
+            self.wait_until(|| async {
+                let decommissioned = self
+                    .brokers
+                    .read()
+                    .await
+                    .get(&broker_id)
+                    .is_some_and(|broker| broker.decommissioned);
+                decommissioned && self.reassignments_started(&started).await
+            })
+            .await;
+            return Ok(started);
+        }
+
+        let brokers = self.brokers.read().await.clone();
+        let decommissioned = brokers
+            .get(&broker_id)
+            .ok_or_else(|| {
+                MeierError::Controller(format!("Broker {} is not registered", broker_id))
+            })?
+            .decommissioned;
+
+        // 브로커별 복제본 수(진행 중인 재배치는 완료 후 기준)
+        let mut partitions = Vec::new();
+        let mut load: HashMap<BrokerId, usize> = HashMap::new();
+        let mut names = self.topic_manager.list_topics().await;
+        names.sort();
+        for name in names {
+            let Some(topic) = self.topic_manager.get_topic(&name).await else {
+                continue;
+            };
+            for (partition_id, partition) in topic.partitions() {
+                let replica = partition.replica_state().await;
+                let replicas = match replica.reassignment() {
+                    Some(reassignment) => &reassignment.target,
+                    None => replica.replicas(),
+                };
+                for &id in replicas {
+                    *load.entry(id).or_default() += 1;
+                }
+                partitions.push((name.clone(), partition_id, replica));
+            }
+        }
+
+        let mut plan = Vec::new();
+        for (topic, partition_id, replica) in partitions {
+            if let Some(reassignment) = replica.reassignment() {
+                if reassignment.target.contains(&broker_id) {
+                    return Err(MeierError::Controller(format!(
+                        "Partition {} of topic {} is being reassigned to broker {}",
+                        partition_id, topic, broker_id
+                    )));
+                }
+                continue;
+            }
+            if !replica.replicas().contains(&broker_id) {
+                continue;
+            }
+
+            let used_racks: Vec<&String> = replica
+                .replicas()
+                .iter()
+                .filter(|&&id| id != broker_id)
+                .filter_map(|id| brokers.get(id)?.rack.as_ref())
+                .collect();
+            let replacement = brokers
+                .values()
+                .filter(|broker| {
+                    broker.id != broker_id
+                        && !broker.fenced
+                        && !broker.decommissioned
+                        && !replica.replicas().contains(&broker.id)
+                })
+                .min_by_key(|broker| {
+                    let same_rack = broker
+                        .rack
+                        .as_ref()
+                        .is_some_and(|rack| used_racks.contains(&rack));
+                    (
+                        same_rack,
+                        load.get(&broker.id).copied().unwrap_or(0),
+                        broker.id,
+                    )
+                })
+                .map(|broker| broker.id)
+                .ok_or_else(|| {
+                    MeierError::Controller(format!(
+                        "No broker available to replace broker {} in partition {} of topic {}",
+                        broker_id, partition_id, topic
+                    ))
+                })?;
+            *load.entry(replacement).or_default() += 1;
+
+            plan.push(PartitionReassignment {
+                topic,
+                partition_id,
+                replicas: replica
+                    .replicas()
+                    .iter()
+                    .map(|&id| if id == broker_id { replacement } else { id })
+                    .collect(),
+            });
+        }
+
+        if !decommissioned {
+            self.propose(MetadataRecord::DecommissionBroker { broker_id })
+                .await?;
+        }
+        for reassignment in &plan {
+            self.propose(MetadataRecord::ReassignPartition {
+                topic: reassignment.topic.clone(),
+                partition_id: reassignment.partition_id,
+                replicas: reassignment.replicas.clone(),
+                throttle_bytes_per_sec,
+            })
+            .await?;
+        }
+        Ok(plan)
+    }
+
+    /// 진행 중인 재배치와 파티션 리더가 알려 준 ISR
+    pub async fn list_reassignments(&self) -> Vec<ReassignmentStatus> {
+        let mut statuses = Vec::new();
+        let mut names = self.topic_manager.list_topics().await;
+        names.sort();
+        for name in names {
+            let Some(topic) = self.topic_manager.get_topic(&name).await else {
+                continue;
+            };
+            for (partition_id, partition) in topic.partitions() {
+                let replica = partition.replica_state().await;
+                let Some(reassignment) = replica.reassignment() else {
+                    continue;
+                };
+                statuses.push(ReassignmentStatus {
+                    topic: name.clone(),
+                    partition_id,
+                    leader: replica.leader(),
+                    replicas: replica.replicas().to_vec(),
+                    target: reassignment.target.clone(),
+                    adding: reassignment.adding.clone(),
+                    removing: reassignment.removing.clone(),
+                    isr: Vec::new(),
+                    throttle_bytes_per_sec: reassignment.throttle_bytes_per_sec,
+                });
+            }
+        }
+
+        // 팔로워가 아닌 브로커는 ISR 을 모르므로 리더에게 묻는다
+        let leaders: HashSet<BrokerId> = statuses.iter().map(|status| status.leader).collect();
+        for leader in leaders {
+            let topics: Vec<String> = statuses
+                .iter()
+                .filter(|status| status.leader == leader)
+                .map(|status| status.topic.clone())
+                .collect();
+            let isr = match self.partition_isr(leader, topics).await {
+                Ok(isr) => isr,
+                Err(e) => {
+                    debug!("Failed to fetch ISR from broker {}: {}", leader, e);
+                    continue;
+                }
+            };
+            for status in statuses.iter_mut().filter(|status| status.leader == leader) {
+                if let Some(isr) = isr.get(&(status.topic.clone(), status.partition_id)) {
+                    status.isr = isr.clone();
+                }
+            }
+        }
+        statuses
+    }
+
+    /// leader 브로커가 본 topics 의 파티션별 ISR
+    async fn partition_isr(
+        &self,
+        leader: BrokerId,
+        mut topics: Vec<String>,
+    ) -> Result<HashMap<(String, usize), Vec<BrokerId>>> {
+        topics.dedup();
+
+        let mut isr = HashMap::new();
+        if leader == self.cluster.broker_id {
+            for name in topics {
+                let Some(topic) = self.topic_manager.get_topic(&name).await else {
+                    continue;
+                };
+                for (partition_id, partition) in topic.partitions() {
+                    isr.insert(
+                        (name.clone(), partition_id),
+                        partition.in_sync_replicas().await,
+                    );
+                }
+            }
+            return Ok(isr);
+        }
+
+        let peer = self
+            .cluster
+            .peer(leader)
+            .ok_or_else(|| MeierError::Controller(format!("Unknown broker: {}", leader)))?;
+        let result: MetadataResult = self
+            .call(
+                &mut None,
+                peer,
+                Frame::Metadata {
+                    topics: Some(topics),
+                },
+                self.rpc_timeout(),
+            )
+            .await?
+            .into_json()?;
+        for topic in result.topics {
+            for partition in topic.partitions {
+                isr.insert((topic.name.clone(), partition.partition_id), partition.isr);
+            }
+        }
+        Ok(isr)
+    }
+
+    /// 리더: 모든 새 복제본이 ISR 에 들어온 재배치 완료
+    async fn complete_reassignments(&self) -> Result<()> {
+        if !self.is_leader().await {
+            return Ok(());
+        }
+
+        for status in self.list_reassignments().await {
+            if !status.target.iter().all(|id| status.isr.contains(id)) {
+                continue;
+            }
+
+            // 기존 리더가 남으면 유지하고, 빠지면 선호 리더로 넘긴다
+            let leader = match status.target.contains(&status.leader) {
+                true => status.leader,
+                false => status.target[0],
+            };
+            self.propose(MetadataRecord::CompleteReassignment {
+                topic: status.topic,
+                partition_id: status.partition_id,
+                replicas: status.target,
+                leader,
+            })
+            .await?;
+        }
+        Ok(())
+    }
+
+    /// 리더: 로그에 항목을 추가하고 커밋되어 적용될 때까지 대기
+    ///
+    /// 과반과 연결이 끊기면 커밋되지 않으므로 브로커 세션 시간까지만 기다린다.
+    async fn propose(&self, record: MetadataRecord) -> Result<()> {
+        let (index, rx) = {
+            let mut raft = self.raft.lock().await;
+            let index = raft.propose(record)?;
+
+            // 적용 태스크가 결과를 보내기 전에 등록되도록 raft 락을 잡은 채로 추가
+            let (tx, rx) = oneshot::channel();
+            self.pending.lock().await.insert(index, (raft.term(), tx));
+            self.notify_commit(&raft);
+            (index, rx)
+        };
+        self.wake.send_modify(|n| *n += 1);
+
+        let timeout = Duration::from_millis(self.cluster.broker_session_timeout_ms);
+        match tokio::time::timeout(timeout, rx).await {
+            Ok(result) => {
+                result.map_err(|_| MeierError::Controller("Controller stopped".to_string()))?
+            }
+            Err(_) => {
+                self.pending.lock().await.remove(&index);
+                Err(MeierError::Controller(format!(
+                    "Metadata change was not committed within {:?} (no controller quorum)",
+                    timeout
+                )))
+            }
+        }
+    }
+
+    /// 복제본이 broker_id 에 있는 파티션 수
+    async fn partitions_on(&self, broker_id: BrokerId) -> usize {
+        let mut count = 0;
+        for name in self.topic_manager.list_topics().await {
+            let Some(topic) = self.topic_manager.get_topic(&name).await else {
+                continue;
+            };
+            for (_, partition) in topic.partitions() {
+                if partition
+                    .replica_state()
+                    .await
+                    .replicas()
+                    .contains(&broker_id)
+                {
+                    count += 1;
+                }
+            }
+        }
+        count
+    }
+
+    /// 현재 리더 컨트롤러에게 요청을 전달하고 결과 반환
+    ///
+    /// 리더는 records 개의 항목을 하나씩 커밋하므로 그만큼 propose 대기 시간을 더해 기다린다.
+    async fn forward(&self, frame: Frame, records: usize) -> Result<Frame> {
+        let leader_id =
+            self.raft.lock().await.leader_id().ok_or_else(|| {
+                MeierError::Controller("No controller leader elected".to_string())
+            })?;
+        let peer = self.cluster.peer(leader_id).ok_or_else(|| {
+            MeierError::Controller(format!("Unknown controller leader: broker {}", leader_id))
+        })?;
+
+        let timeout = Duration::from_millis(
+            self.cluster.broker_session_timeout_ms * records.max(1) as u64
+                + self.cluster.election_timeout_ms,
+        );
+        let mut connection = None;
+        match self
+            .call(&mut connection, peer, frame, timeout)
+            .await?
+            .into_result()
+        {
+            Ok(response) => Ok(response),
+            Err(MeierError::Protocol(e)) => Err(MeierError::Controller(e)),
+            Err(e) => Err(e),
+        }
+    }
+
+    /// 전달한 변경이 이 브로커에 적용될 때까지 대기(선거 시간 안에 적용되지 않으면 그냥 반환)
+    async fn wait_until<F, Fut>(&self, condition: F)
+    where
+        F: Fn() -> Fut,
+        Fut: Future<Output = bool>,
+    {
+        let timeout = Duration::from_millis(self.cluster.election_timeout_ms);
+        let mut applied = self.applied.subscribe();
+        let wait = async {
+            while !condition().await {
+                if applied.changed().await.is_err() {
+                    break;
+                }
+            }
+        };
+        let _ = tokio::time::timeout(timeout, wait).await;
+    }
+
+    fn notify_commit(&self, raft: &RaftState) {
+        self.committed.send_if_modified(|index| {
+            let modified = raft.commit_index() > *index;
+            *index = (*index).max(raft.commit_index());
+            modified
+        });
+    }
+
+    async fn run_election_timer(self: Arc<Self>, shutdown: CancellationToken) {
+        let mut interval = tokio::time::interval(ELECTION_TICK);
+        loop {
+            tokio::select! {
+                _ = interval.tick() => {}
+                _ = shutdown.cancelled() => break,
+            }
+
+            let mut raft = self.raft.lock().await;
+            match raft.tick(Instant::now()) {
+                Ok(true) => {
+                    self.notify_commit(&raft);
+                    self.wake.send_modify(|n| *n += 1);
+                }
+                Ok(false) => {}
+                Err(e) => warn!("Controller election failed: {}", e),
+            }
+        }
+    }
+
+    /// peer 에게 투표 요청(후보) 또는 로그 복제/heartbeat(리더) 전송
+    async fn run_peer(self: Arc<Self>, peer: PeerConfig, shutdown: CancellationToken) {
+        let heartbeat = Duration::from_millis(self.cluster.heartbeat_interval_ms);
+        let mut wake = self.wake.subscribe();
+        let mut connection = None;
+        // 이미 투표를 요청한 term
+        let mut vote_requested = 0;
+
+        loop {
+            let rpc = {
+                let raft = self.raft.lock().await;
+                match raft.role() {
+                    Role::Leader => raft.append_request(peer.id).map(Rpc::Append),
+                    Role::Candidate => raft
+                        .vote_request()
+                        .filter(|request| request.term > vote_requested)
+                        .map(Rpc::Vote),
+                    Role::Follower => None,
+                }
+            };
+
+            let mut more = false;
+            if let Some(rpc) = rpc {
+                match self.send_rpc(&mut connection, &peer, rpc).await {
+                    Ok(Some(term)) => vote_requested = term,
+                    Ok(None) => {
+                        // 보낼 항목이 남아 있으면 기다리지 않고 이어서 전송
+                        let raft = self.raft.lock().await;
+                        more = raft
+                            .append_request(peer.id)
+                            .is_some_and(|request| !request.entries.is_empty());
+                    }
+                    Err(e) => {
+                        debug!("Controller request to broker {} failed: {}", peer.id, e);
+                        connection = None;
+                    }
+                }
+            }
+
+            if more {
+                continue;
+            }
+            tokio::select! {
+                _ = tokio::time::sleep(heartbeat) => {}
+                _ = wake.changed() => {}
+                _ = shutdown.cancelled() => break,
+            }
+        }
+    }
+
+    /// 요청을 보내고 응답을 반영(투표 요청이었으면 그 term 반환)
+    async fn send_rpc(
+        &self,
+        connection: &mut Option<Connection>,
+        peer: &PeerConfig,
+        rpc: Rpc,
+    ) -> Result<Option<u64>> {
+        let local_id = self.cluster.broker_id;
+        match rpc {
+            Rpc::Vote(request) => {
+                let response: RaftVoteResult = self
+                    .call(
+                        connection,
+                        peer,
+                        Frame::RaftVote {
+                            term: request.term,
+                            candidate_id: local_id,
+                            last_log_index: request.last_log_index,
+                            last_log_term: request.last_log_term,
+                        },
+                        self.rpc_timeout(),
+                    )
+                    .await?
+                    .into_json()?;
+
+                let mut raft = self.raft.lock().await;
+                raft.handle_vote_response(peer.id, request.term, response)?;
+                self.notify_commit(&raft);
+                if raft.is_leader() {
+                    self.wake.send_modify(|n| *n += 1);
+                }
+                Ok(Some(request.term))
+            }
+            Rpc::Append(request) => {
+                let response: RaftAppendResult = self
+                    .call(
+                        connection,
+                        peer,
+                        Frame::RaftAppend {
+                            term: request.term,
+                            leader_id: local_id,
+                            prev_log_index: request.prev_log_index,
+                            prev_log_term: request.prev_log_term,
+                            entries: request.entries.clone(),
+                            leader_commit: request.leader_commit,
+                        },
+                        self.rpc_timeout(),
+                    )
+                    .await?
+                    .into_json()?;
+                self.peer_racks
+                    .lock()
+                    .await
+                    .insert(peer.id, response.rack.clone());
+
+                let mut raft = self.raft.lock().await;
+                raft.handle_append_response(peer.id, &request, response)?;
+                self.notify_commit(&raft);
+                Ok(None)
+            }
+        }
+    }
+
+    /// 컨트롤러 사이 RPC 응답 대기 시간
+    fn rpc_timeout(&self) -> Duration {
+        Duration::from_millis(self.cluster.election_timeout_ms)
+    }
+
+    /// peer 와의 연결로 요청 하나를 보내고 timeout 안에 받은 응답 반환(끊겼으면 다시 연결)
+    async fn call(
+        &self,
+        connection: &mut Option<Connection>,
+        peer: &PeerConfig,
+        frame: Frame,
+        timeout: Duration,
+    ) -> Result<Frame> {
+        let request = async {
+            if connection.is_none() {
+                let stream = self.connector.connect(&peer.addr).await?;
+                *connection = Some(Framed::new(
+                    stream,
+                    MeierCodec::with_max_length(self.max_frame_bytes),
+                ));
+            }
+            let Some(framed) = connection.as_mut() else {
+                unreachable!("connection was just established");
+            };
+
+            framed.send(frame).await?;
+            framed.next().await.ok_or_else(|| {
+                MeierError::Protocol(format!("Connection to broker {} closed", peer.id))
+            })?
+        };
+
+        match tokio::time::timeout(timeout, request).await {
+            Ok(Ok(frame)) => Ok(frame),
+            Ok(Err(e)) => {
+                *connection = None;
+                Err(e)
+            }
+            Err(_) => {
+                *connection = None;
+                Err(MeierError::Controller(format!(
+                    "Request to broker {} timed out",
+                    peer.id
+                )))
+            }
+        }
+    }
+
+    /// 커밋된 항목을 순서대로 TopicManager 에 적용
+    async fn run_applier(self: Arc<Self>, shutdown: CancellationToken) {
+        let mut committed = self.committed.subscribe();
+        loop {
+            let applied = *self.applied.borrow();
+            let entries: Vec<(u64, MetadataEntry)> = {
+                let raft = self.raft.lock().await;
+                (applied + 1..=raft.commit_index())
+                    .filter_map(|index| raft.entry(index).map(|entry| (index, entry.clone())))
+                    .collect()
+            };
+
+            if entries.is_empty() {
+                tokio::select! {
+                    result = committed.changed() => if result.is_err() { break },
+                    _ = shutdown.cancelled() => break,
+                }
+                continue;
+            }
+
+            for (index, entry) in entries {
+                let result = self.apply(entry.record).await;
+                if let Err(e) = &result {
+                    debug!("Metadata entry {} not applied: {}", index, e);
+                }
+
+                if let Some((term, tx)) = self.pending.lock().await.remove(&index) {
+                    let result = match term == entry.term {
+                        true => result,
+                        false => Err(MeierError::Controller(
+                            "Controller leadership changed before the request was committed"
+                                .to_string(),
+                        )),
+                    };
+                    let _ = tx.send(result);
+                }
+                self.applied.send_replace(index);
+            }
+        }
+    }
+
+    async fn apply(&self, record: MetadataRecord) -> Result<()> {
+        let topic_manager = &self.topic_manager;
+        match record {
+            MetadataRecord::LeaderChange { leader_id } => {
+                debug!("Controller leader changed to broker {}", leader_id);
+            }
+            MetadataRecord::RegisterBroker {
+                broker_id,
+                addr,
+                rack,
+            } => {
+                info!(
+                    "Broker {} registered at {} (rack: {})",
+                    broker_id,
+                    addr,
+                    rack.as_deref().unwrap_or("none")
+                );
+                let mut brokers = self.brokers.write().await;
+                let (fenced, decommissioned) =
+                    brokers.get(&broker_id).map_or((false, false), |broker| {
+                        (broker.fenced, broker.decommissioned)
+                    });
+                brokers.insert(
+                    broker_id,
+                    BrokerInfo {
+                        id: broker_id,
+                        addr,
+                        rack,
+                        fenced,
+                        decommissioned,
+                    },
+                );
+            }
+            MetadataRecord::FenceBroker { broker_id } => {
+                warn!("Broker {} fenced", broker_id);
+                if let Some(broker) = self.brokers.write().await.get_mut(&broker_id) {
+                    broker.fenced = true;
+                }
+            }
+            MetadataRecord::UnfenceBroker { broker_id } => {
+                info!("Broker {} unfenced", broker_id);
+                if let Some(broker) = self.brokers.write().await.get_mut(&broker_id) {
+                    broker.fenced = false;
+                }
+            }
+            MetadataRecord::CreateTopic { assignment } => {
+                topic_manager.create_assigned_topic(assignment).await?;
+            }
+            MetadataRecord::DeleteTopic { name } => {
+                topic_manager.delete_topic(&name).await?;
+            }
+            MetadataRecord::AlterConfigs { topic, set, delete } => {
+                topic_manager
+                    .alter_topic_config(&topic, set, delete)
+                    .await?;
+            }
+            MetadataRecord::PartitionLeader {
+                topic,
+                partition_id,
+                leader,
+            } => {
+                info!(
+                    "Leader of partition {} of topic {} changed to broker {}",
+                    partition_id, topic, leader
+                );
+                let partition = topic_manager.get_partition(&topic, partition_id).await?;
+                partition.set_leader(leader).await;
+            }
+            MetadataRecord::ReassignPartition {
+                topic,
+                partition_id,
+                replicas,
+                throttle_bytes_per_sec,
+            } => {
+                info!(
+                    "Reassigning partition {} of topic {} to brokers {:?}",
+                    partition_id, topic, replicas
+                );
+                let partition = topic_manager.get_partition(&topic, partition_id).await?;
+                partition
+                    .start_reassignment(replicas, throttle_bytes_per_sec)
+                    .await;
+            }
+            MetadataRecord::CompleteReassignment {
+                topic,
+                partition_id,
+                replicas,
+                leader,
+            } => {
+                info!(
+                    "Reassignment of partition {} of topic {} completed (replicas: {:?}, leader: broker {})",
+                    partition_id, topic, replicas, leader
+                );
+                let partition = topic_manager.get_partition(&topic, partition_id).await?;
+                partition.complete_reassignment(replicas, leader).await?;
+            }
+            MetadataRecord::DecommissionBroker { broker_id } => {
+                info!("Broker {} decommissioned", broker_id);
+                if let Some(broker) = self.brokers.write().await.get_mut(&broker_id) {
+                    broker.decommissioned = true;
+                }
+            }
+            MetadataRecord::CreateAcls { acls } => {
+                info!("Created {} ACL(s)", acls.len());
+                self.authorizer.add(acls).await?;
+            }
+            MetadataRecord::DeleteAcls { acls } => {
+                info!("Deleted {} ACL(s)", acls.len());
+                self.authorizer.remove(&acls).await?;
+            }
+        }
+        Ok(())
+    }
+
+    /// 리더: 브로커 등록/차단 상태를 갱신하고 차단된 브로커가 리더인 파티션의 리더 교체,
+    /// 새 복제본이 따라잡은 재배치 완료
+    async fn run_broker_monitor(self: Arc<Self>, shutdown: CancellationToken) {
+        let heartbeat = Duration::from_millis(self.cluster.heartbeat_interval_ms);
+        let session_timeout = Duration::from_millis(self.cluster.broker_session_timeout_ms);
+        loop {
+            tokio::select! {
+                _ = tokio::time::sleep(heartbeat) => {}
+                _ = shutdown.cancelled() => break,
+            }
+
+            // 과반을 잃어 커밋되지 않는 제안에 묶이지 않도록 세션 시간으로 제한
+            let check = async {
+                self.check_brokers().await?;
+                self.complete_reassignments().await
+            };
+            match tokio::time::timeout(session_timeout, check).await {
+                Ok(Ok(())) => {}
+                Ok(Err(e)) => debug!("Broker check failed: {}", e),
+                Err(_) => debug!("Broker check timed out"),
+            }
+        }
+    }
+
+    async fn check_brokers(&self) -> Result<()> {
+        let liveness = {
+            let raft = self.raft.lock().await;
+            let Some(leader_since) = raft.leader_since() else {
+                return Ok(());
+            };
+
+            let now = Instant::now();
+            let session_timeout = Duration::from_millis(self.cluster.broker_session_timeout_ms);
+            let liveness: HashMap<BrokerId, Liveness> = self
+                .cluster
+                .broker_ids()
+                .into_iter()
+                .map(|id| {
+                    let state = if id == self.cluster.broker_id {
+                        Liveness::Alive
+                    } else {
+                        match raft.last_contact(id) {
+                            Some(at) if now.duration_since(at) < session_timeout => Liveness::Alive,
+                            _ if now.duration_since(leader_since) < session_timeout => {
+                                Liveness::Unknown
+                            }
+                            _ => Liveness::Dead,
+                        }
+                    };
+                    (id, state)
+                })
+                .collect();
+
+            // 이전 리더가 적용하지 않은 항목이 남아 있으면 모두 적용된 후 판단
+            if *self.applied.borrow() < raft.commit_index()
+                || raft.commit_index() < raft.last_log_index()
+            {
+                return Ok(());
+            }
+            liveness
+        };
+
+        let brokers = self.brokers.read().await.clone();
+        let peer_racks = self.peer_racks.lock().await.clone();
+        for (&id, &state) in &liveness {
+            let rack = match id == self.cluster.broker_id {
+                true => self.rack.clone(),
+                false => peer_racks.get(&id).cloned().flatten(),
+            };
+            let record = match (brokers.get(&id), state) {
+                // 처음 보는 브로커이거나 랙을 바꿔 재시작한 브로커
+                (None, Liveness::Alive) => Some(MetadataRecord::RegisterBroker {
+                    broker_id: id,
+                    addr: self.broker_addr(id),
+                    rack,
+                }),
+                (Some(broker), Liveness::Alive) if broker.rack != rack => {
+                    Some(MetadataRecord::RegisterBroker {
+                        broker_id: id,
+                        addr: self.broker_addr(id),
+                        rack,
+                    })
+                }
+                (Some(broker), Liveness::Alive) if broker.fenced => {
+                    Some(MetadataRecord::UnfenceBroker { broker_id: id })
+                }
+                (Some(broker), Liveness::Dead) if !broker.fenced => {
+                    Some(MetadataRecord::FenceBroker { broker_id: id })
+                }
+                _ => None,
+            };
+            if let Some(record) = record {
+                self.propose(record).await?;
+            }
+        }
+
+        self.elect_partition_leaders(&liveness).await
+    }
+
+    /// 리더가 응답하지 않는 파티션에 ISR 중 살아 있는 복제본을 새 리더로 선출
+    async fn elect_partition_leaders(&self, liveness: &HashMap<BrokerId, Liveness>) -> Result<()> {
+        let alive: HashSet<BrokerId> = liveness
+            .iter()
+            .filter(|(_, state)| **state == Liveness::Alive)
+            .map(|(id, _)| *id)
+            .collect();
+
+        let mut names = self.topic_manager.list_topics().await;
+        names.sort();
+        for name in names {
+            let Some(topic) = self.topic_manager.get_topic(&name).await else {
+                continue;
+            };
+
+            for (partition_id, partition) in topic.partitions() {
+                let replica = partition.replica_state().await;
+                if liveness.get(&replica.leader()) != Some(&Liveness::Dead) {
+                    continue;
+                }
+
+                // ISR 밖의 복제본은 커밋된 메시지가 없을 수 있으므로 선택하지 않는다
+                let isr = partition.in_sync_replicas().await;
+                let Some(leader) = replica
+                    .replicas()
+                    .iter()
+                    .copied()
+                    .find(|id| isr.contains(id) && alive.contains(id))
+                else {
+                    debug!(
+                        "No in-sync replica available for partition {} of topic {}",
+                        partition_id, name
+                    );
+                    continue;
+                };
+
+                self.propose(MetadataRecord::PartitionLeader {
+                    topic: name.clone(),
+                    partition_id,
+                    leader,
+                })
+                .await?;
+            }
+        }
+        Ok(())
+    }
+
+    fn broker_addr(&self, id: BrokerId) -> String {
+        match self.cluster.peer(id) {
+            Some(peer) => peer.addr.clone(),
+            None => self.local_addr.clone(),
+        }
+    }
+}
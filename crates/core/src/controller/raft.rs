@@ -0,0 +1,585 @@
+use std::{
+    collections::{HashMap, HashSet},
+    time::{Duration, Instant},
+};
+use tracing::info;
+
+use crate::{
+    MeierError, Result,
+    config::BrokerId,
+    controller::store::MetadataStore,
+    protocol::{MetadataEntry, MetadataRecord, RaftAppendResult, RaftVoteResult},
+};
+
+/// RaftAppend 하나에 담는 최대 항목 수
+const MAX_APPEND_ENTRIES: usize = 100;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Role {
+    Follower,
+    Candidate,
+    Leader,
+}
+
+/// 리더가 팔로워에게 보낼 RaftAppend 내용
+#[derive(Debug, Clone)]
+pub struct AppendRequest {
+    pub term: u64,
+    pub prev_log_index: u64,
+    pub prev_log_term: u64,
+    pub entries: Vec<MetadataEntry>,
+    pub leader_commit: u64,
+}
+
+/// 후보가 다른 컨트롤러에게 보낼 RaftVote 내용
+#[derive(Debug, Clone, Copy)]
+pub struct VoteRequest {
+    pub term: u64,
+    pub last_log_index: u64,
+    pub last_log_term: u64,
+}
+
+/// 메타데이터 로그를 복제하는 Raft 상태
+///
+/// 네트워크 I/O 없이 요청/응답을 받아 상태만 바꾸며, 전송은 `Controller` 가 맡는다.
+pub struct RaftState {
+    id: BrokerId,
+    /// 자신을 포함한 전체 투표자
+    voters: Vec<BrokerId>,
+    store: MetadataStore,
+    role: Role,
+    leader_id: Option<BrokerId>,
+    commit_index: u64,
+    election_timeout: Duration,
+    election_deadline: Instant,
+    votes: HashSet<BrokerId>,
+    /// 리더: 팔로워별 다음에 보낼 인덱스와 복제가 확인된 인덱스
+    next_index: HashMap<BrokerId, u64>,
+    match_index: HashMap<BrokerId, u64>,
+    /// 리더: 팔로워가 마지막으로 응답한 시각
+    last_contact: HashMap<BrokerId, Instant>,
+    /// 리더가 된 시각
+    leader_since: Option<Instant>,
+    rng: u64,
+}
+
+impl RaftState {
+    pub fn new(
+        id: BrokerId,
+        voters: Vec<BrokerId>,
+        store: MetadataStore,
+        election_timeout: Duration,
+    ) -> Self {
+        // 브로커마다 선거 시각이 달라지도록 시드에 ID 를 섞는다
+        let seed = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .map(|d| d.as_nanos() as u64)
+            .unwrap_or(0)
+            ^ (u64::from(id) + 1).wrapping_mul(0x9E37_79B9_7F4A_7C15);
+
+        let mut state = Self {
+            id,
+            voters,
+            store,
+            role: Role::Follower,
+            leader_id: None,
+            commit_index: 0,
+            election_timeout,
+            election_deadline: Instant::now(),
+            votes: HashSet::new(),
+            next_index: HashMap::new(),
+            match_index: HashMap::new(),
+            last_contact: HashMap::new(),
+            leader_since: None,
+            rng: seed | 1,
+        };
+        state.reset_election_deadline();
+        state
+    }
+
+    pub fn role(&self) -> Role {
+        self.role
+    }
+
+    pub fn is_leader(&self) -> bool {
+        self.role == Role::Leader
+    }
+
+    pub fn leader_id(&self) -> Option<BrokerId> {
+        self.leader_id
+    }
+
+    pub fn term(&self) -> u64 {
+        self.store.term()
+    }
+
+    pub fn commit_index(&self) -> u64 {
+        self.commit_index
+    }
+
+    pub fn last_log_index(&self) -> u64 {
+        self.store.last_index()
+    }
+
+    pub fn entry(&self, index: u64) -> Option<&MetadataEntry> {
+        self.store.entry(index)
+    }
+
+    /// 리더: 팔로워가 마지막으로 응답한 시각(리더가 된 후 응답이 없었으면 None)
+    pub fn last_contact(&self, id: BrokerId) -> Option<Instant> {
+        self.last_contact.get(&id).copied()
+    }
+
+    pub fn leader_since(&self) -> Option<Instant> {
+        self.leader_since
+    }
+
+    fn quorum(&self) -> usize {
+        self.voters.len() / 2 + 1
+    }
+
+    fn last_log_term(&self) -> u64 {
+        self.store
+            .term_at(self.store.last_index())
+            .unwrap_or_default()
+    }
+
+    // election_timeout ~ 2배 사이의 임의 시간 후로 선거 시각 설정
+    fn reset_election_deadline(&mut self) {
+        // xorshift
+        self.rng ^= self.rng << 13;
+        self.rng ^= self.rng >> 7;
+        self.rng ^= self.rng << 17;
+
+        let base = self.election_timeout.as_millis() as u64;
+        let jitter = self.rng % base.max(1);
+        self.election_deadline = Instant::now() + Duration::from_millis(base + jitter);
+    }
+
+    /// 리더가 아니고 선거 시각이 지났으면 후보가 되어 선거 시작(시작했으면 true)
+    pub fn tick(&mut self, now: Instant) -> Result<bool> {
+        if self.role == Role::Leader || now < self.election_deadline {
+            return Ok(false);
+        }
+
+        let term = self.store.term() + 1;
+        self.store.set_term(term, Some(self.id))?;
+        self.role = Role::Candidate;
+        self.leader_id = None;
+        self.votes = HashSet::from([self.id]);
+        self.reset_election_deadline();
+        info!("Starting controller election for term {}", term);
+
+        if self.votes.len() >= self.quorum() {
+            self.become_leader()?;
+        }
+        Ok(true)
+    }
+
+    pub fn vote_request(&self) -> Option<VoteRequest> {
+        (self.role == Role::Candidate).then(|| VoteRequest {
+            term: self.store.term(),
+            last_log_index: self.store.last_index(),
+            last_log_term: self.last_log_term(),
+        })
+    }
+
+    // 더 높은 term 을 보면 팔로워로 돌아간다
+    fn observe_term(&mut self, term: u64) -> Result<()> {
+        if term > self.store.term() {
+            self.store.set_term(term, None)?;
+            if self.role != Role::Follower {
+                info!("Controller stepping down: observed term {}", term);
+            }
+            self.role = Role::Follower;
+            self.leader_id = None;
+            self.leader_since = None;
+        }
+        Ok(())
+    }
+
+    pub fn handle_vote(
+        &mut self,
+        term: u64,
+        candidate_id: BrokerId,
+        last_log_index: u64,
+        last_log_term: u64,
+    ) -> Result<RaftVoteResult> {
+        self.observe_term(term)?;
+
+        // 후보의 로그가 자신의 로그보다 뒤처지지 않을 때만 투표
+        let log_ok =
+            (last_log_term, last_log_index) >= (self.last_log_term(), self.store.last_index());
+        let can_vote = self.store.voted_for().is_none_or(|id| id == candidate_id);
+        let vote_granted = term == self.store.term() && log_ok && can_vote;
+
+        if vote_granted {
+            self.store.set_term(term, Some(candidate_id))?;
+            self.reset_election_deadline();
+        }
+
+        Ok(RaftVoteResult {
+            term: self.store.term(),
+            vote_granted,
+        })
+    }
+
+    /// 후보: 투표 응답 반영(과반을 얻으면 리더가 된다)
+    pub fn handle_vote_response(
+        &mut self,
+        from: BrokerId,
+        request_term: u64,
+        response: RaftVoteResult,
+    ) -> Result<()> {
+        self.observe_term(response.term)?;
+        if self.role != Role::Candidate
+            || request_term != self.store.term()
+            || !response.vote_granted
+        {
+            return Ok(());
+        }
+
+        self.votes.insert(from);
+        if self.votes.len() >= self.quorum() {
+            self.become_leader()?;
+        }
+        Ok(())
+    }
+
+    fn become_leader(&mut self) -> Result<()> {
+        info!(
+            "Elected as controller leader for term {}",
+            self.store.term()
+        );
+        self.role = Role::Leader;
+        self.leader_id = Some(self.id);
+        self.leader_since = Some(Instant::now());
+        self.last_contact.clear();
+
+        let next = self.store.last_index() + 1;
+        for &id in &self.voters {
+            if id != self.id {
+                self.next_index.insert(id, next);
+                self.match_index.insert(id, 0);
+            }
+        }
+
+        // 현재 term 항목이 커밋되어야 이전 term 의 항목도 커밋된다
+        self.propose(MetadataRecord::LeaderChange { leader_id: self.id })?;
+        Ok(())
+    }
+
+    /// 리더: 로그에 항목을 추가하고 그 인덱스 반환
+    pub fn propose(&mut self, record: MetadataRecord) -> Result<u64> {
+        if self.role != Role::Leader {
+            return Err(MeierError::Controller(format!(
+                "Not the controller leader (leader: {})",
+                self.leader_id
+                    .map(|id| format!("broker {}", id))
+                    .unwrap_or_else(|| "unknown".to_string())
+            )));
+        }
+
+        let term = self.store.term();
+        self.store.append(vec![MetadataEntry { term, record }])?;
+        self.advance_commit_index();
+        Ok(self.store.last_index())
+    }
+
+    /// 리더: peer 에게 보낼 RaftAppend(리더가 아니면 None)
+    pub fn append_request(&self, peer: BrokerId) -> Option<AppendRequest> {
+        if self.role != Role::Leader {
+            return None;
+        }
+
+        let next = self.next_index.get(&peer).copied().unwrap_or(1);
+        let prev_log_index = next - 1;
+        Some(AppendRequest {
+            term: self.store.term(),
+            prev_log_index,
+            prev_log_term: self.store.term_at(prev_log_index).unwrap_or_default(),
+            entries: self.store.entries_from(next, MAX_APPEND_ENTRIES),
+            leader_commit: self.commit_index,
+        })
+    }
+
+    /// 팔로워: 리더의 로그를 받아 자신의 로그에 반영
+    pub fn handle_append(
+        &mut self,
+        term: u64,
+        leader_id: BrokerId,
+        prev_log_index: u64,
+        prev_log_term: u64,
+        entries: Vec<MetadataEntry>,
+        leader_commit: u64,
+    ) -> Result<RaftAppendResult> {
+        self.observe_term(term)?;
+
+        let current_term = self.store.term();
+        if term < current_term {
+            return Ok(RaftAppendResult {
+                term: current_term,
+                success: false,
+                last_log_index: self.store.last_index(),
+                rack: None,
+            });
+        }
+
+        // 같은 term 의 리더가 있으면 선거를 멈춘다
+        if self.role != Role::Follower {
+            self.role = Role::Follower;
+        }
+        if self.leader_id != Some(leader_id) {
+            info!(
+                "Following controller leader broker {} (term {})",
+                leader_id, term
+            );
+            self.leader_id = Some(leader_id);
+        }
+        self.reset_election_deadline();
+
+        if self.store.term_at(prev_log_index) != Some(prev_log_term) {
+            return Ok(RaftAppendResult {
+                term: current_term,
+                success: false,
+                last_log_index: self
+                    .store
+                    .last_index()
+                    .min(prev_log_index.saturating_sub(1)),
+                rack: None,
+            });
+        }
+
+        // 같은 인덱스의 term 이 다른 항목부터 리더의 로그로 바꾼다
+        let mut index = prev_log_index;
+        let mut new_entries = Vec::new();
+        for entry in entries {
+            index += 1;
+            if !new_entries.is_empty() {
+                new_entries.push(entry);
+                continue;
+            }
+            match self.store.term_at(index) {
+                Some(term) if term == entry.term => {}
+                Some(_) => {
+                    self.store.truncate_after(index - 1)?;
+                    new_entries.push(entry);
+                }
+                None => new_entries.push(entry),
+            }
+        }
+        self.store.append(new_entries)?;
+
+        if leader_commit > self.commit_index {
+            self.commit_index = leader_commit.min(index);
+        }
+
+        Ok(RaftAppendResult {
+            term: current_term,
+            success: true,
+            last_log_index: index,
+            rack: None,
+        })
+    }
+
+    /// 리더: RaftAppend 응답 반영
+    pub fn handle_append_response(
+        &mut self,
+        from: BrokerId,
+        request: &AppendRequest,
+        response: RaftAppendResult,
+    ) -> Result<()> {
+        self.observe_term(response.term)?;
+        if self.role != Role::Leader || request.term != self.store.term() {
+            return Ok(());
+        }
+
+        self.last_contact.insert(from, Instant::now());
+        if response.success {
+            let matched = request.prev_log_index + request.entries.len() as u64;
+            let match_index = self.match_index.entry(from).or_default();
+            *match_index = (*match_index).max(matched);
+            self.next_index.insert(from, *match_index + 1);
+            self.advance_commit_index();
+        } else {
+            // 팔로워 로그 끝 바로 다음부터 다시 맞춰본다
+            let next = self.next_index.entry(from).or_insert(1);
+            *next = (response.last_log_index + 1)
+                .min(next.saturating_sub(1))
+                .max(1);
+        }
+        Ok(())
+    }
+
+    /// 리더: 과반에 복제된 현재 term 의 항목까지 커밋
+    fn advance_commit_index(&mut self) {
+        let mut matched: Vec<u64> = self
+            .voters
+            .iter()
+            .map(|id| match *id == self.id {
+                true => self.store.last_index(),
+                false => self.match_index.get(id).copied().unwrap_or(0),
+            })
+            .collect();
+        matched.sort_unstable_by(|a, b| b.cmp(a));
+
+        let replicated = matched[self.quorum() - 1];
+        if replicated > self.commit_index
+            && self.store.term_at(replicated) == Some(self.store.term())
+        {
+            self.commit_index = replicated;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const ELECTION_TIMEOUT: Duration = Duration::from_millis(100);
+
+    /// 테스트마다 빈 디렉토리에 로그를 두는 상태
+    fn raft(name: &str, id: BrokerId, voters: Vec<BrokerId>) -> RaftState {
+        let dir =
+            std::env::temp_dir().join(format!("meier-raft-{}-{}-{}", name, id, std::process::id()));
+        let _ = std::fs::remove_dir_all(&dir);
+        let store = MetadataStore::open(&dir).unwrap();
+        RaftState::new(id, voters, store, ELECTION_TIMEOUT)
+    }
+
+    fn entry(term: u64) -> MetadataEntry {
+        MetadataEntry {
+            term,
+            record: MetadataRecord::LeaderChange { leader_id: 0 },
+        }
+    }
+
+    /// 선거 시각이 지난 뒤의 시각
+    fn after_election_timeout() -> Instant {
+        Instant::now() + ELECTION_TIMEOUT * 10
+    }
+
+    #[test]
+    fn single_voter_elects_itself() {
+        let mut raft = raft("single", 1, vec![1]);
+
+        assert!(raft.tick(after_election_timeout()).unwrap());
+        assert!(raft.is_leader());
+        assert_eq!(raft.leader_id(), Some(1));
+        assert_eq!(raft.term(), 1);
+        // 리더가 되며 추가한 항목은 자신만으로 과반이므로 바로 커밋된다
+        assert_eq!(raft.commit_index(), 1);
+    }
+
+    #[test]
+    fn refuses_vote_to_candidate_with_stale_log() {
+        let mut raft = raft("stale-log", 1, vec![1, 2, 3]);
+        raft.handle_append(2, 2, 0, 0, vec![entry(1), entry(2)], 0)
+            .unwrap();
+
+        // 로그가 짧은 후보
+        let result = raft.handle_vote(3, 3, 1, 1).unwrap();
+        assert!(!result.vote_granted);
+        assert_eq!(result.term, 3);
+
+        // 로그는 길지만 마지막 term 이 낮은 후보
+        let result = raft.handle_vote(3, 3, 5, 1).unwrap();
+        assert!(!result.vote_granted);
+
+        let result = raft.handle_vote(3, 3, 2, 2).unwrap();
+        assert!(result.vote_granted);
+    }
+
+    #[test]
+    fn votes_once_per_term() {
+        let mut raft = raft("vote-once", 1, vec![1, 2, 3]);
+
+        assert!(raft.handle_vote(1, 2, 0, 0).unwrap().vote_granted);
+        assert!(!raft.handle_vote(1, 3, 0, 0).unwrap().vote_granted);
+        // 같은 후보의 재요청은 다시 허용
+        assert!(raft.handle_vote(1, 2, 0, 0).unwrap().vote_granted);
+        // 다음 term 에서는 다른 후보에게 투표할 수 있다
+        assert!(raft.handle_vote(2, 3, 0, 0).unwrap().vote_granted);
+    }
+
+    #[test]
+    fn append_truncates_conflicting_suffix() {
+        let mut raft = raft("truncate", 1, vec![1, 2, 3]);
+        raft.handle_append(1, 2, 0, 0, vec![entry(1), entry(1), entry(1)], 0)
+            .unwrap();
+        assert_eq!(raft.last_log_index(), 3);
+
+        // 새 리더는 2 번 항목부터 다른 term 의 로그를 가지고 있다
+        let result = raft.handle_append(2, 3, 1, 1, vec![entry(2)], 0).unwrap();
+        assert!(result.success);
+        assert_eq!(result.last_log_index, 2);
+        assert_eq!(raft.last_log_index(), 2);
+        assert_eq!(raft.entry(2).map(|e| e.term), Some(2));
+        assert!(raft.entry(3).is_none());
+
+        // prev 항목이 일치하지 않으면 거부
+        let result = raft.handle_append(2, 3, 2, 1, vec![entry(2)], 0).unwrap();
+        assert!(!result.success);
+    }
+
+    #[test]
+    fn commits_previous_term_entry_only_with_current_term_entry() {
+        let mut raft = raft("commit", 1, vec![1, 2, 3]);
+        // term 1 리더에게 받았지만 커밋되지 않은 항목
+        raft.handle_append(1, 2, 0, 0, vec![entry(1)], 0).unwrap();
+
+        assert!(raft.tick(after_election_timeout()).unwrap());
+        let vote = raft.vote_request().unwrap();
+        raft.handle_vote_response(
+            3,
+            vote.term,
+            RaftVoteResult {
+                term: vote.term,
+                vote_granted: true,
+            },
+        )
+        .unwrap();
+        assert!(raft.is_leader());
+        assert_eq!(raft.term(), 2);
+        // 리더가 되며 현재 term 항목(2 번)을 추가했다
+        assert_eq!(raft.last_log_index(), 2);
+
+        // 이전 term 항목만 과반에 복제되어서는 커밋되지 않는다
+        let request = AppendRequest {
+            term: 2,
+            prev_log_index: 0,
+            prev_log_term: 0,
+            entries: vec![entry(1)],
+            leader_commit: 0,
+        };
+        raft.handle_append_response(
+            3,
+            &request,
+            RaftAppendResult {
+                term: 2,
+                success: true,
+                last_log_index: 1,
+                rack: None,
+            },
+        )
+        .unwrap();
+        assert_eq!(raft.commit_index(), 0);
+
+        // 현재 term 항목이 과반에 복제되면 이전 항목도 함께 커밋된다
+        let request = raft.append_request(3).unwrap();
+        assert_eq!(request.prev_log_index, 1);
+        assert_eq!(request.entries.len(), 1);
+        raft.handle_append_response(
+            3,
+            &request,
+            RaftAppendResult {
+                term: 2,
+                success: true,
+                last_log_index: 2,
+                rack: None,
+            },
+        )
+        .unwrap();
+        assert_eq!(raft.commit_index(), 2);
+    }
+}
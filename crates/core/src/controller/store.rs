@@ -0,0 +1,167 @@
+use serde::{Deserialize, Serialize};
+use std::{
+    fs::{self, File, OpenOptions},
+    io::{BufRead, BufReader, Write},
+    path::{Path, PathBuf},
+};
+
+use crate::{MeierError, Result, config::BrokerId, protocol::MetadataEntry};
+
+const STATE_FILE: &str = "state.json";
+const LOG_FILE: &str = "log.jsonl";
+
+/// 재시작 후에도 유지해야 하는 Raft 상태
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+struct HardState {
+    term: u64,
+    voted_for: Option<BrokerId>,
+}
+
+/// 컨트롤러의 term, 투표 기록, 메타데이터 로그를 디렉토리에 저장
+///
+/// 로그는 한 줄에 항목 하나씩 JSON 으로 기록하며 인덱스는 1 부터 시작한다.
+/// 메타데이터 로그는 작으므로 충돌한 항목을 지울 때는 파일 전체를 다시 쓴다.
+pub struct MetadataStore {
+    dir: PathBuf,
+    state: HardState,
+    entries: Vec<MetadataEntry>,
+}
+
+impl MetadataStore {
+    pub fn open(dir: &Path) -> Result<Self> {
+        fs::create_dir_all(dir).map_err(|e| store_error(dir, e))?;
+
+        let state_path = dir.join(STATE_FILE);
+        let state = match fs::read(&state_path) {
+            Ok(bytes) => serde_json::from_slice(&bytes).map_err(|e| {
+                MeierError::Controller(format!("Corrupt {}: {}", state_path.display(), e))
+            })?,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => HardState::default(),
+            Err(e) => return Err(store_error(&state_path, e)),
+        };
+
+        let log_path = dir.join(LOG_FILE);
+        let mut entries = Vec::new();
+        match File::open(&log_path) {
+            Ok(file) => {
+                for line in BufReader::new(file).lines() {
+                    let line = line.map_err(|e| store_error(&log_path, e))?;
+                    // 기록 도중 종료되어 잘린 마지막 줄은 버린다
+                    match serde_json::from_str(&line) {
+                        Ok(entry) => entries.push(entry),
+                        Err(_) => break,
+                    }
+                }
+            }
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => {}
+            Err(e) => return Err(store_error(&log_path, e)),
+        }
+
+        let store = Self {
+            dir: dir.to_path_buf(),
+            state,
+            entries,
+        };
+        // 잘린 줄이 있었다면 정상 항목만 남긴다
+        store.rewrite_log()?;
+        Ok(store)
+    }
+
+    pub fn term(&self) -> u64 {
+        self.state.term
+    }
+
+    pub fn voted_for(&self) -> Option<BrokerId> {
+        self.state.voted_for
+    }
+
+    pub fn set_term(&mut self, term: u64, voted_for: Option<BrokerId>) -> Result<()> {
+        self.state = HardState { term, voted_for };
+
+        let bytes = serde_json::to_vec(&self.state)
+            .map_err(|e| MeierError::Controller(format!("Serialization error: {}", e)))?;
+        write_atomic(&self.dir.join(STATE_FILE), &bytes)
+    }
+
+    pub fn last_index(&self) -> u64 {
+        self.entries.len() as u64
+    }
+
+    /// index 위치 항목의 term(0 은 빈 로그 앞 위치로 term 0)
+    pub fn term_at(&self, index: u64) -> Option<u64> {
+        match index {
+            0 => Some(0),
+            _ => self.entry(index).map(|entry| entry.term),
+        }
+    }
+
+    pub fn entry(&self, index: u64) -> Option<&MetadataEntry> {
+        let position = usize::try_from(index.checked_sub(1)?).ok()?;
+        self.entries.get(position)
+    }
+
+    /// from 부터 최대 max 개 항목
+    pub fn entries_from(&self, from: u64, max: usize) -> Vec<MetadataEntry> {
+        let start = (from.max(1) - 1) as usize;
+        self.entries.iter().skip(start).take(max).cloned().collect()
+    }
+
+    pub fn append(&mut self, entries: Vec<MetadataEntry>) -> Result<()> {
+        if entries.is_empty() {
+            return Ok(());
+        }
+
+        let path = self.dir.join(LOG_FILE);
+        let mut file = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&path)
+            .map_err(|e| store_error(&path, e))?;
+
+        let mut buf = Vec::new();
+        for entry in &entries {
+            serde_json::to_writer(&mut buf, entry)
+                .map_err(|e| MeierError::Controller(format!("Serialization error: {}", e)))?;
+            buf.push(b'\n');
+        }
+        file.write_all(&buf)
+            .and_then(|_| file.sync_data())
+            .map_err(|e| store_error(&path, e))?;
+
+        self.entries.extend(entries);
+        Ok(())
+    }
+
+    /// index 이후 항목 삭제
+    pub fn truncate_after(&mut self, index: u64) -> Result<()> {
+        if index >= self.last_index() {
+            return Ok(());
+        }
+        self.entries.truncate(index as usize);
+        self.rewrite_log()
+    }
+
+    fn rewrite_log(&self) -> Result<()> {
+        let mut buf = Vec::new();
+        for entry in &self.entries {
+            serde_json::to_writer(&mut buf, entry)
+                .map_err(|e| MeierError::Controller(format!("Serialization error: {}", e)))?;
+            buf.push(b'\n');
+        }
+        write_atomic(&self.dir.join(LOG_FILE), &buf)
+    }
+}
+
+/// 임시 파일에 기록한 후 이름을 바꿔 중간에 종료되어도 이전 내용이 남도록 한다
+pub(crate) fn write_atomic(path: &Path, bytes: &[u8]) -> Result<()> {
+    let tmp = path.with_extension("tmp");
+    let mut file = File::create(&tmp).map_err(|e| store_error(&tmp, e))?;
+    file.write_all(bytes)
+        .and_then(|_| file.sync_all())
+        .map_err(|e| store_error(&tmp, e))?;
+    fs::rename(&tmp, path).map_err(|e| store_error(path, e))
+}
+
+fn store_error(path: &Path, e: std::io::Error) -> MeierError {
+    MeierError::Controller(format!("Metadata store error at {}: {}", path.display(), e))
+}
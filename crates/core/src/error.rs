@@ -1,5 +1,7 @@
 use thiserror::Error;
 
+use crate::config::BrokerId;
+
 #[derive(Error, Debug)]
 pub enum MeierError {
     #[error("IO error: {0}")]
@@ -22,6 +24,36 @@ pub enum MeierError {
 
     #[error("Partition not found: {0}")]
     PartitionNotFound(String),
+
+    #[error("Transaction error: {0}")]
+    Transaction(String),
+
+    #[error("Controller error: {0}")]
+    Controller(String),
+
+    #[error("TLS error: {0}")]
+    Tls(String),
+
+    #[error("Authentication failed: {0}")]
+    Authentication(String),
+
+    /// ACL 로 허용되지 않은 요청
+    #[error("Not authorized: {0}")]
+    NotAuthorized(String),
+
+    /// acks=all 메시지가 리더에 기록되었지만 시간 안에 ISR 에 복제되지 않음
+    #[error("Not enough replicas after append: {0}")]
+    NotEnoughReplicasAfterAppend(String),
+
+    /// 요청한 파티션의 리더가 아닌 브로커로 보낸 요청
+    #[error(
+        "Not the leader for partition {partition_id} of topic {topic} (leader: broker {leader})"
+    )]
+    NotLeaderForPartition {
+        topic: String,
+        partition_id: usize,
+        leader: BrokerId,
+    },
 }
 
 pub type Result<T> = std::result::Result<T, MeierError>;
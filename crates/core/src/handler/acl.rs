@@ -0,0 +1,51 @@
+use crate::{
+    Frame, Result,
+    acl::{self, Authorizer},
+    controller::Controller,
+    protocol::{self, AclBinding, AclFilter},
+};
+
+pub async fn handle_create_acls(
+    authorizer: &Authorizer,
+    controller: Option<&Controller>,
+    acls: Vec<AclBinding>,
+) -> Result<Frame> {
+    for acl in &acls {
+        acl::validate(acl)?;
+    }
+
+    let count = acls.len();
+    match controller {
+        Some(controller) => controller.create_acls(acls).await?,
+        None => authorizer.add(acls).await?,
+    }
+
+    Ok(Frame::Response {
+        status: protocol::Status::ok(),
+        data: None,
+        message: Some(format!("Created {} ACL(s)", count)),
+        record: None,
+        throttle_time_ms: None,
+    })
+}
+
+pub async fn handle_describe_acls(authorizer: &Authorizer, filter: AclFilter) -> Result<Frame> {
+    Frame::response_ok_json(&authorizer.list(&filter).await)
+}
+
+/// 삭제한 ACL 목록 반환
+pub async fn handle_delete_acls(
+    authorizer: &Authorizer,
+    controller: Option<&Controller>,
+    filters: Vec<AclFilter>,
+) -> Result<Frame> {
+    let acls = authorizer.matching(&filters).await;
+    if !acls.is_empty() {
+        match controller {
+            Some(controller) => controller.delete_acls(filters, acls.clone()).await?,
+            None => authorizer.remove(&acls).await?,
+        }
+    }
+
+    Frame::response_ok_json(&acls)
+}
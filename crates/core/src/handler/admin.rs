@@ -0,0 +1,455 @@
+use std::collections::BTreeMap;
+
+use crate::{
+    Frame, MeierError, Result,
+    config::TopicConfig,
+    controller::Controller,
+    protocol::{
+        self, ConfigEntry, ConfigSource, DeleteRecordsResult, MetadataResult, PartitionInfo,
+        PartitionMetadata, TopicMetadata,
+    },
+    storage::{Partition, TopicManager},
+};
+
+async fn partition_info(partition_id: usize, partition: &Partition) -> PartitionInfo {
+    let replica = partition.replica_state().await;
+    PartitionInfo {
+        partition_id,
+        log_start_offset: partition.current_offset().await,
+        log_end_offset: partition.log_end_offset().await,
+        high_watermark: partition.high_watermark(),
+        last_stable_offset: partition.last_stable_offset().await,
+        message_count: partition.message_count().await,
+        size_bytes: partition.size_bytes().await,
+        leader: replica.leader(),
+        replicas: replica.replicas().to_vec(),
+        isr: partition.in_sync_replicas().await,
+    }
+}
+
+pub async fn handle_describe_partitions(
+    topic_manager: &TopicManager,
+    topic: String,
+) -> Result<Frame> {
+    let topic = topic_manager
+        .get_topic(&topic)
+        .await
+        .ok_or_else(|| MeierError::TopicNotFound(topic.clone()))?;
+
+    let mut partitions = Vec::new();
+    for (partition_id, partition) in topic.partitions() {
+        partitions.push(partition_info(partition_id, &partition).await);
+    }
+
+    Frame::response_ok_json(&partitions)
+}
+
+/// 토픽 구성과 파티션 리더 조회(클러스터 모드에서는 브로커 주소와 컨트롤러 포함)
+pub async fn handle_metadata(
+    topic_manager: &TopicManager,
+    controller: Option<&Controller>,
+    topics: Option<Vec<String>>,
+) -> Result<Frame> {
+    let mut names = match topics {
+        Some(names) => names,
+        None => topic_manager.list_topics().await,
+    };
+    names.sort();
+
+    let mut result = MetadataResult {
+        topics: Vec::new(),
+        brokers: Vec::new(),
+        controller_id: None,
+    };
+    if let Some(controller) = controller {
+        let cluster = controller.describe().await;
+        result.brokers = cluster.brokers;
+        result.controller_id = cluster.controller_id;
+    }
+
+    for name in names {
+        let topic = topic_manager
+            .get_topic(&name)
+            .await
+            .ok_or_else(|| MeierError::TopicNotFound(name.clone()))?;
+
+        let mut partitions = Vec::new();
+        for (partition_id, partition) in topic.partitions() {
+            let replica = partition.replica_state().await;
+            partitions.push(PartitionMetadata {
+                partition_id,
+                leader: replica.leader(),
+                replicas: replica.replicas().to_vec(),
+                isr: partition.in_sync_replicas().await,
+            });
+        }
+
+        let config = topic.config().await;
+        result.topics.push(TopicMetadata {
+            name,
+            config: TopicConfig::KEYS
+                .iter()
+                .filter_map(|key| config.get(key).map(|value| (key.to_string(), value)))
+                .collect(),
+            partitions,
+        });
+    }
+
+    Frame::response_ok_json(&result)
+}
+
+/// 클러스터 모드에서는 컨트롤러를 통해 생성
+pub async fn handle_create_topic(
+    topic_manager: &TopicManager,
+    controller: Option<&Controller>,
+    topic: String,
+    configs: BTreeMap<String, String>,
+) -> Result<Frame> {
+    let partitions = match controller {
+        Some(controller) => controller.create_topic(topic.clone(), configs).await?,
+        None => {
+            let created = topic_manager.create_topic(topic.clone(), configs).await?;
+            created.config().await.partitions
+        }
+    };
+
+    Ok(Frame::Response {
+        status: protocol::Status::ok(),
+        data: None,
+        message: Some(format!(
+            "Topic {} created with {} partition(s)",
+            topic, partitions
+        )),
+        record: None,
+        throttle_time_ms: None,
+    })
+}
+
+pub async fn handle_delete_topic(
+    topic_manager: &TopicManager,
+    controller: Option<&Controller>,
+    topic: String,
+) -> Result<Frame> {
+    match controller {
+        Some(controller) => controller.delete_topic(topic.clone()).await?,
+        None => topic_manager.delete_topic(&topic).await?,
+    }
+
+    Ok(Frame::Response {
+        status: protocol::Status::ok(),
+        data: None,
+        message: Some(format!("Topic {} deleted", topic)),
+        record: None,
+        throttle_time_ms: None,
+    })
+}
+
+pub async fn handle_delete_records(
+    topic_manager: &TopicManager,
+    topic: String,
+    partition_id: usize,
+    before_offset: usize,
+) -> Result<Frame> {
+    let partition = topic_manager
+        .get_leader_partition(&topic, partition_id)
+        .await?;
+    let log_start_offset = partition.delete_records(before_offset).await?;
+
+    Frame::response_ok_json(&DeleteRecordsResult {
+        topic,
+        partition_id,
+        log_start_offset,
+    })
+}
+
+pub async fn handle_describe_configs(topic_manager: &TopicManager, topic: String) -> Result<Frame> {
+    let topic = topic_manager
+        .get_topic(&topic)
+        .await
+        .ok_or_else(|| MeierError::TopicNotFound(topic.clone()))?;
+
+    let config = topic.config().await;
+    let overrides = topic.overrides().await;
+
+    let entries: Vec<ConfigEntry> = TopicConfig::KEYS
+        .iter()
+        .filter_map(|key| {
+            config.get(key).map(|value| ConfigEntry {
+                name: key.to_string(),
+                value,
+                source: if overrides.contains_key(*key) {
+                    ConfigSource::Topic
+                } else {
+                    ConfigSource::Default
+                },
+            })
+        })
+        .collect();
+
+    Frame::response_ok_json(&entries)
+}
+
+pub async fn handle_alter_configs(
+    topic_manager: &TopicManager,
+    controller: Option<&Controller>,
+    topic: String,
+    set: BTreeMap<String, String>,
+    delete: Vec<String>,
+) -> Result<Frame> {
+    match controller {
+        Some(controller) => controller.alter_configs(topic.clone(), set, delete).await?,
+        None => {
+            topic_manager
+                .alter_topic_config(&topic, set, delete)
+                .await?
+        }
+    }
+
+    Ok(Frame::Response {
+        status: protocol::Status::ok(),
+        data: None,
+        message: Some(format!("Config of topic {} updated", topic)),
+        record: None,
+        throttle_time_ms: None,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::storage::{Message, topic::tests::topic_manager};
+
+    async fn produce(topic_manager: &TopicManager, topic: &str, partition_id: usize, count: usize) {
+        let topic = topic_manager
+            .get_or_create_topic(topic.to_string())
+            .await
+            .unwrap();
+        for _ in 0..count {
+            topic
+                .add_message_to(partition_id, Message::new(b"created".to_vec()))
+                .await
+                .unwrap();
+        }
+    }
+
+    #[tokio::test]
+    async fn describe_partitions_reports_offsets_and_sizes() {
+        let topics = topic_manager();
+        produce(&topics, "orders", 1, 3).await;
+        topics
+            .get_partition("orders", 1)
+            .await
+            .unwrap()
+            .delete_records(1)
+            .await
+            .unwrap();
+
+        let partitions: Vec<PartitionInfo> =
+            handle_describe_partitions(&topics, "orders".to_string())
+                .await
+                .unwrap()
+                .into_json()
+                .unwrap();
+        assert_eq!(partitions.len(), 3);
+        assert_eq!(partitions[0].log_end_offset, 0);
+
+        let partition = &partitions[1];
+        assert_eq!(partition.partition_id, 1);
+        assert_eq!(partition.log_start_offset, 1);
+        assert_eq!(partition.log_end_offset, 3);
+        assert_eq!(partition.high_watermark, 3);
+        assert_eq!(partition.last_stable_offset, 3);
+        assert_eq!(partition.message_count, 2);
+        assert_eq!(partition.size_bytes, 2 * "created".len());
+        assert_eq!(partition.isr, [0]);
+
+        assert!(matches!(
+            handle_describe_partitions(&topics, "payments".to_string()).await,
+            Err(MeierError::TopicNotFound(_))
+        ));
+    }
+
+    #[tokio::test]
+    async fn metadata_lists_topics_with_partition_leaders() {
+        let topics = topic_manager();
+        topics
+            .create_topic(
+                "payments".to_string(),
+                BTreeMap::from([(TopicConfig::PARTITIONS.to_string(), "2".to_string())]),
+            )
+            .await
+            .unwrap();
+        produce(&topics, "orders", 0, 1).await;
+
+        let metadata: MetadataResult = handle_metadata(&topics, None, None)
+            .await
+            .unwrap()
+            .into_json()
+            .unwrap();
+        let names: Vec<_> = metadata.topics.iter().map(|t| t.name.as_str()).collect();
+        assert_eq!(names, ["orders", "payments"]);
+        assert!(metadata.brokers.is_empty());
+        assert_eq!(metadata.controller_id, None);
+
+        let payments = &metadata.topics[1];
+        assert_eq!(payments.partitions.len(), 2);
+        assert!(payments.partitions.iter().all(|p| p.leader == 0));
+        assert_eq!(
+            payments
+                .config
+                .get(TopicConfig::PARTITIONS)
+                .map(String::as_str),
+            Some("2")
+        );
+
+        let metadata: MetadataResult =
+            handle_metadata(&topics, None, Some(vec!["orders".to_string()]))
+                .await
+                .unwrap()
+                .into_json()
+                .unwrap();
+        assert_eq!(metadata.topics.len(), 1);
+        assert!(
+            handle_metadata(&topics, None, Some(vec!["missing".to_string()]))
+                .await
+                .is_err()
+        );
+    }
+
+    #[tokio::test]
+    async fn delete_records_moves_log_start_and_releases_buffer() {
+        let topics = topic_manager();
+        produce(&topics, "orders", 0, 3).await;
+
+        let result: DeleteRecordsResult =
+            handle_delete_records(&topics, "orders".to_string(), 0, 2)
+                .await
+                .unwrap()
+                .into_json()
+                .unwrap();
+        assert_eq!(result.log_start_offset, 2);
+        assert_eq!(topics.buffer_usage().await.message_count, 1);
+        assert!(
+            handle_delete_records(&topics, "orders".to_string(), 0, 4)
+                .await
+                .is_err()
+        );
+    }
+
+    #[tokio::test(flavor = "multi_thread", worker_threads = 4)]
+    async fn delete_topic_releases_messages_of_concurrent_produces() {
+        let topics = std::sync::Arc::new(topic_manager());
+        let topic = topics
+            .get_or_create_topic("orders".to_string())
+            .await
+            .unwrap();
+
+        let producers: Vec<_> = (0..4)
+            .map(|_| {
+                let topic = topic.clone();
+                tokio::spawn(async move {
+                    for _ in 0..200 {
+                        if topic
+                            .add_message_to(0, Message::new(b"created".to_vec()))
+                            .await
+                            .is_err()
+                        {
+                            break;
+                        }
+                        tokio::task::yield_now().await;
+                    }
+                })
+            })
+            .collect();
+        tokio::task::yield_now().await;
+        handle_delete_topic(&topics, None, "orders".to_string())
+            .await
+            .unwrap();
+        for producer in producers {
+            producer.await.unwrap();
+        }
+
+        assert_eq!(topics.buffer_usage().await.message_count, 0);
+        assert_eq!(topics.buffer_usage().await.size_bytes, 0);
+        assert!(topics.get_topic("orders").await.is_none());
+        assert!(
+            topic
+                .add_message_to(0, Message::new(b"late".to_vec()))
+                .await
+                .is_err()
+        );
+    }
+
+    #[tokio::test]
+    async fn alter_configs_overrides_and_restores_defaults() {
+        let topics = topic_manager();
+        handle_create_topic(
+            &topics,
+            None,
+            "orders".to_string(),
+            BTreeMap::from([(TopicConfig::RETENTION_MS.to_string(), "60000".to_string())]),
+        )
+        .await
+        .unwrap();
+
+        let describe = || async {
+            let entries: Vec<ConfigEntry> = handle_describe_configs(&topics, "orders".to_string())
+                .await
+                .unwrap()
+                .into_json()
+                .unwrap();
+            entries
+                .into_iter()
+                .map(|entry| (entry.name, (entry.value, entry.source)))
+                .collect::<BTreeMap<_, _>>()
+        };
+        let entries = describe().await;
+        assert_eq!(
+            entries[TopicConfig::RETENTION_MS],
+            ("60000".to_string(), ConfigSource::Topic)
+        );
+        assert_eq!(
+            entries[TopicConfig::COMPRESSION_TYPE],
+            ("none".to_string(), ConfigSource::Default)
+        );
+
+        handle_alter_configs(
+            &topics,
+            None,
+            "orders".to_string(),
+            BTreeMap::from([(
+                TopicConfig::COMPRESSION_TYPE.to_string(),
+                "gzip".to_string(),
+            )]),
+            vec![TopicConfig::RETENTION_MS.to_string()],
+        )
+        .await
+        .unwrap();
+        let entries = describe().await;
+        assert_eq!(
+            entries[TopicConfig::RETENTION_MS],
+            ("-1".to_string(), ConfigSource::Default)
+        );
+        assert_eq!(
+            entries[TopicConfig::COMPRESSION_TYPE],
+            ("gzip".to_string(), ConfigSource::Topic)
+        );
+
+        for (key, value) in [
+            (TopicConfig::PARTITIONS, "5"),
+            (TopicConfig::CLEANUP_POLICY, "compact"),
+            ("segment.bytes", "1024"),
+        ] {
+            let result = handle_alter_configs(
+                &topics,
+                None,
+                "orders".to_string(),
+                BTreeMap::from([(key.to_string(), value.to_string())]),
+                Vec::new(),
+            )
+            .await;
+            assert!(matches!(result, Err(MeierError::Config(_))), "{}", key);
+        }
+        assert_eq!(describe().await, entries);
+    }
+}
@@ -1,34 +1,47 @@
-use crate::{Frame, MeierError, Result, protocol, storage::TopicManager};
+use crate::{
+    Frame, MeierError, Result,
+    protocol::{
+        self, EARLIEST_TIMESTAMP, IsolationLevel, LATEST_TIMESTAMP, ListOffsetsResult,
+        RecordMetadata,
+    },
+    storage::{Message, TopicManager},
+};
+
+fn record_metadata(offset: usize, msg: &Message) -> RecordMetadata {
+    RecordMetadata {
+        offset,
+        timestamp: msg.timestamp,
+        event_timestamp: msg.event_timestamp,
+        headers: msg.headers.clone(),
+    }
+}
 
 pub async fn handle_consume(
     topic_manager: &TopicManager,
     topic: String,
     partition_id: usize,
     offset: usize,
+    isolation_level: IsolationLevel,
 ) -> Result<Frame> {
-    let topic = topic_manager
-        .get_topic(&topic)
-        .await
-        .ok_or_else(|| MeierError::TopicNotFound(topic.clone()))?;
+    let partition = topic_manager
+        .get_leader_partition(&topic, partition_id)
+        .await?;
 
-    let partition = topic
-        .get_partition(&partition_id.to_string())
-        .ok_or_else(|| {
-            MeierError::PartitionNotFound(format!(
-                "Partition {} not found in topic {}",
-                partition_id,
-                topic.name()
-            ))
-        })?;
-
-    match partition.get_message(offset).await {
-        Some(msg) => {
+    match partition.get_message(offset, isolation_level).await {
+        Some((msg_offset, msg)) => {
+            let msg = msg.decompress()?;
+            topic_manager
+                .metrics()
+                .record_consume(&topic, msg.size())
+                .await;
             let message_str = msg
                 .to_string()
                 .unwrap_or_else(|_| format!("[Binary data: {} bytes]", msg.size()));
             Ok(Frame::Response {
                 status: protocol::Status::ok(),
-                data: Some(msg.data.clone()),
+                record: Some(record_metadata(msg_offset, &msg)),
+                throttle_time_ms: None,
+                data: Some(msg.data),
                 message: Some(message_str),
             })
         }
@@ -40,6 +53,8 @@ pub async fn handle_consume(
                 partition.current_offset().await,
                 offset
             )),
+            record: None,
+            throttle_time_ms: None,
         }),
     }
 }
@@ -48,33 +63,28 @@ pub async fn handle_consume_next(
     topic_manager: &TopicManager,
     topic: String,
     partition_id: usize,
+    isolation_level: IsolationLevel,
 ) -> Result<Frame> {
-    let topic = topic_manager
-        .get_topic(&topic)
-        .await
-        .ok_or_else(|| MeierError::TopicNotFound(topic.clone()))?;
-
-    let partition = topic
-        .get_partition(&partition_id.to_string())
-        .ok_or_else(|| {
-            MeierError::PartitionNotFound(format!(
-                "Partition {} not found in topic {}",
-                partition_id,
-                topic.name()
-            ))
-        })?;
+    let partition = topic_manager
+        .get_leader_partition(&topic, partition_id)
+        .await?;
 
-    let current_offset = partition.current_offset().await;
-
-    match partition.consume_message().await {
-        Some(msg) => {
+    match partition.consume_message(isolation_level).await {
+        Some((msg_offset, msg)) => {
+            let msg = msg.decompress()?;
+            topic_manager
+                .metrics()
+                .record_consume(&topic, msg.size())
+                .await;
             let message_str = msg
                 .to_string()
                 .unwrap_or_else(|_| format!("[Binary data: {} bytes]", msg.size()));
             Ok(Frame::Response {
                 status: protocol::Status::ok(),
-                data: Some(msg.data.clone()),
-                message: Some(format!("offset={}:{}", current_offset, message_str)),
+                record: Some(record_metadata(msg_offset, &msg)),
+                throttle_time_ms: None,
+                data: Some(msg.data),
+                message: Some(format!("offset={}:{}", msg_offset, message_str)),
             })
         }
         None => Ok(Frame::Response {
@@ -82,8 +92,126 @@ pub async fn handle_consume_next(
             data: None,
             message: Some(format!(
                 "No new messages. Current offset: {}",
-                current_offset
+                partition.current_offset().await
             )),
+            record: None,
+            throttle_time_ms: None,
         }),
     }
 }
+
+pub async fn handle_list_offsets(
+    topic_manager: &TopicManager,
+    topic: String,
+    partition_id: usize,
+    timestamp: i64,
+) -> Result<Frame> {
+    let partition = topic_manager
+        .get_leader_partition(&topic, partition_id)
+        .await?;
+
+    let (offset, timestamp) = match timestamp {
+        LATEST_TIMESTAMP => (partition.high_watermark(), None),
+        EARLIEST_TIMESTAMP => partition.offset_for_timestamp(0).await,
+        ts if ts >= 0 => partition.offset_for_timestamp(ts as u64).await,
+        ts => {
+            return Err(MeierError::Protocol(format!("Invalid timestamp: {}", ts)));
+        }
+    };
+
+    Frame::response_ok_json(&ListOffsetsResult {
+        topic,
+        partition_id,
+        offset,
+        timestamp,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        handler::handle_produce,
+        protocol::Acks,
+        storage::{message, topic::tests::topic_manager},
+    };
+    use std::collections::BTreeMap;
+
+    #[tokio::test]
+    async fn consume_returns_headers_and_timestamps() {
+        let topics = topic_manager();
+        let headers = BTreeMap::from([("trace-id".to_string(), "abc".to_string())]);
+        let before = message::now_millis();
+        handle_produce(
+            &topics,
+            None,
+            "orders".to_string(),
+            Message::new(b"created".to_vec())
+                .with_headers(headers.clone())
+                .with_event_timestamp(Some(1_700_000_000_000)),
+            Some(0),
+            None,
+            Acks::Leader,
+        )
+        .await
+        .unwrap();
+
+        let response = handle_consume(
+            &topics,
+            "orders".to_string(),
+            0,
+            0,
+            IsolationLevel::ReadUncommitted,
+        )
+        .await
+        .unwrap();
+        let Frame::Response {
+            record: Some(record),
+            data,
+            ..
+        } = response
+        else {
+            panic!("unexpected response: {:?}", response);
+        };
+        assert_eq!(data.as_deref(), Some(&b"created"[..]));
+        assert_eq!(record.offset, 0);
+        assert_eq!(record.headers, headers);
+        assert_eq!(record.event_timestamp, Some(1_700_000_000_000));
+        assert!((before..=message::now_millis()).contains(&record.timestamp));
+    }
+
+    #[tokio::test]
+    async fn list_offsets_finds_first_offset_at_timestamp() {
+        let topics = topic_manager();
+        let topic = topics
+            .get_or_create_topic("orders".to_string())
+            .await
+            .unwrap();
+        for timestamp in [1_000, 2_000, 3_000] {
+            let msg = Message {
+                timestamp,
+                ..Message::new(b"created".to_vec())
+            };
+            topic.add_message_to(0, msg).await.unwrap();
+        }
+
+        let list_offsets = |timestamp| {
+            let topics = &topics;
+            async move {
+                handle_list_offsets(topics, "orders".to_string(), 0, timestamp)
+                    .await
+                    .and_then(Frame::into_json::<ListOffsetsResult>)
+                    .map(|result| (result.offset, result.timestamp))
+            }
+        };
+        assert_eq!(list_offsets(1_500).await.unwrap(), (1, Some(2_000)));
+        assert_eq!(list_offsets(2_000).await.unwrap(), (1, Some(2_000)));
+        assert_eq!(list_offsets(3_001).await.unwrap(), (3, None));
+        assert_eq!(
+            list_offsets(EARLIEST_TIMESTAMP).await.unwrap(),
+            (0, Some(1_000))
+        );
+        assert_eq!(list_offsets(LATEST_TIMESTAMP).await.unwrap(), (3, None));
+        assert!(list_offsets(-3).await.is_err());
+    }
+}
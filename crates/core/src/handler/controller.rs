@@ -0,0 +1,107 @@
+use crate::{
+    Frame, MeierError, Result,
+    config::BrokerId,
+    controller::Controller,
+    protocol::{self, MetadataEntry, PartitionReassignment},
+};
+
+fn require(controller: Option<&Controller>) -> Result<&Controller> {
+    controller.ok_or_else(|| {
+        MeierError::Controller(
+            "Cluster mode is not enabled (no cluster.peers configured)".to_string(),
+        )
+    })
+}
+
+pub async fn handle_raft_vote(
+    controller: Option<&Controller>,
+    term: u64,
+    candidate_id: BrokerId,
+    last_log_index: u64,
+    last_log_term: u64,
+) -> Result<Frame> {
+    let result = require(controller)?
+        .handle_vote(term, candidate_id, last_log_index, last_log_term)
+        .await?;
+
+    Frame::response_ok_json(&result)
+}
+
+pub async fn handle_raft_append(
+    controller: Option<&Controller>,
+    term: u64,
+    leader_id: BrokerId,
+    prev_log_index: u64,
+    prev_log_term: u64,
+    entries: Vec<MetadataEntry>,
+    leader_commit: u64,
+) -> Result<Frame> {
+    let result = require(controller)?
+        .handle_append(
+            term,
+            leader_id,
+            prev_log_index,
+            prev_log_term,
+            entries,
+            leader_commit,
+        )
+        .await?;
+
+    Frame::response_ok_json(&result)
+}
+
+pub async fn handle_describe_cluster(controller: Option<&Controller>) -> Result<Frame> {
+    let result = require(controller)?.describe().await;
+
+    Frame::response_ok_json(&result)
+}
+
+pub async fn handle_reassign_partitions(
+    controller: Option<&Controller>,
+    reassignments: Vec<PartitionReassignment>,
+    throttle_bytes_per_sec: Option<u64>,
+) -> Result<Frame> {
+    let count = reassignments.len();
+    require(controller)?
+        .reassign_partitions(reassignments, throttle_bytes_per_sec)
+        .await?;
+
+    Ok(Frame::Response {
+        status: protocol::Status::ok(),
+        data: None,
+        message: Some(format!("Started reassignment of {} partition(s)", count)),
+        record: None,
+        throttle_time_ms: None,
+    })
+}
+
+pub async fn handle_decommission_broker(
+    controller: Option<&Controller>,
+    broker_id: BrokerId,
+    throttle_bytes_per_sec: Option<u64>,
+) -> Result<Frame> {
+    let started = require(controller)?
+        .decommission_broker(broker_id, throttle_bytes_per_sec)
+        .await?;
+
+    // 시작한 재배치를 함께 보내 클라이언트가 완료를 기다릴 수 있게 한다
+    let data = serde_json::to_vec(&started)
+        .map_err(|e| MeierError::Protocol(format!("Serialization error: {}", e)))?;
+    Ok(Frame::Response {
+        status: protocol::Status::ok(),
+        data: Some(data),
+        message: Some(format!(
+            "Broker {} is being decommissioned ({} partition(s) to move)",
+            broker_id,
+            started.len()
+        )),
+        record: None,
+        throttle_time_ms: None,
+    })
+}
+
+pub async fn handle_list_reassignments(controller: Option<&Controller>) -> Result<Frame> {
+    let result = require(controller)?.list_reassignments().await;
+
+    Frame::response_ok_json(&result)
+}
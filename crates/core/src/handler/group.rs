@@ -0,0 +1,59 @@
+use crate::{
+    Frame, Result,
+    protocol::{self, OffsetFetchResult, TopicPartition},
+    storage::TopicManager,
+};
+
+pub async fn handle_offset_commit(
+    topic_manager: &TopicManager,
+    group: String,
+    topic: String,
+    partition_id: usize,
+    offset: usize,
+) -> Result<Frame> {
+    topic_manager
+        .commit_offset(group.clone(), &topic, partition_id, offset)
+        .await?;
+
+    Ok(Frame::Response {
+        status: protocol::Status::ok(),
+        data: None,
+        message: Some(format!(
+            "Group {} committed offset {} for {}-{}",
+            group, offset, topic, partition_id
+        )),
+        record: None,
+        throttle_time_ms: None,
+    })
+}
+
+pub async fn handle_offset_fetch(
+    topic_manager: &TopicManager,
+    group: String,
+    topic: String,
+    partition_id: usize,
+) -> Result<Frame> {
+    // 존재하지 않는 파티션 요청은 에러
+    topic_manager.get_partition(&topic, partition_id).await?;
+
+    let tp = TopicPartition {
+        topic,
+        partition_id,
+    };
+    let committed = topic_manager.offsets().fetch(&group, &tp).await;
+
+    Frame::response_ok_json(&OffsetFetchResult {
+        group,
+        topic: tp.topic,
+        partition_id,
+        offset: committed.map(|c| c.offset),
+    })
+}
+
+pub async fn handle_describe_consumer_lag(
+    topic_manager: &TopicManager,
+    group: Option<String>,
+) -> Result<Frame> {
+    let lag = topic_manager.consumer_lag(group.as_deref()).await;
+    Frame::response_ok_json(&lag)
+}
@@ -1,5 +1,23 @@
+pub mod acl;
+pub mod admin;
 pub mod consumer;
+pub mod controller;
+pub mod group;
 pub mod producer;
+pub mod replication;
+pub mod transaction;
 
-pub use consumer::{handle_consume, handle_consume_next};
+pub use acl::{handle_create_acls, handle_delete_acls, handle_describe_acls};
+pub use admin::{
+    handle_alter_configs, handle_create_topic, handle_delete_records, handle_delete_topic,
+    handle_describe_configs, handle_describe_partitions, handle_metadata,
+};
+pub use consumer::{handle_consume, handle_consume_next, handle_list_offsets};
+pub use controller::{
+    handle_decommission_broker, handle_describe_cluster, handle_list_reassignments,
+    handle_raft_append, handle_raft_vote, handle_reassign_partitions,
+};
+pub use group::{handle_describe_consumer_lag, handle_offset_commit, handle_offset_fetch};
 pub use producer::handle_produce;
+pub use replication::handle_replica_fetch;
+pub use transaction::{handle_add_partitions_to_txn, handle_begin_txn, handle_end_txn};
@@ -1,20 +1,175 @@
+use std::time::Duration;
+
 use crate::{
-    Frame, Result, protocol,
+    Frame, MeierError, Result,
+    controller::Controller,
+    protocol,
+    protocol::{Acks, TopicPartition},
     storage::{Message, TopicManager},
 };
 
 pub async fn handle_produce(
     topic_manager: &TopicManager,
+    controller: Option<&Controller>,
     topic: String,
-    message: Vec<u8>,
+    msg: Message,
+    partition_id: Option<usize>,
+    transactional_id: Option<String>,
+    acks: Acks,
 ) -> Result<Frame> {
-    let msg = Message::new(message);
-    let topic = topic_manager.get_or_create_topic(topic).await?;
-    topic.add_message(msg).await?;
+    // 클러스터 모드에서는 없는 토픽도 컨트롤러를 통해 생성
+    let topic = match controller {
+        Some(controller) => controller.get_or_create_topic(topic).await?,
+        None => topic_manager.get_or_create_topic(topic).await?,
+    };
+    let size = msg.size();
+
+    let (partition, offset) = match (partition_id, transactional_id) {
+        (Some(partition_id), Some(transactional_id)) => {
+            let tp = TopicPartition {
+                topic: topic.name().to_string(),
+                partition_id,
+            };
+            let txn_id = topic_manager
+                .transactions()
+                .txn_id_for(&transactional_id, &tp)
+                .await?;
+            topic
+                .add_message_to(partition_id, msg.with_txn_id(txn_id))
+                .await?
+        }
+        (None, Some(transactional_id)) => {
+            return Err(MeierError::Transaction(format!(
+                "partition_id is required for transactional produce: {}",
+                transactional_id
+            )));
+        }
+        (Some(partition_id), None) => topic.add_message_to(partition_id, msg).await?,
+        (None, None) => topic.add_message(msg).await?,
+    };
+
+    // ISR 이 부족하면 메시지는 리더에 기록되었지만 에러로 응답
+    if acks == Acks::All {
+        let isr = partition.in_sync_replicas().await;
+        let min_insync_replicas = topic_manager.cluster().min_insync_replicas;
+        if isr.len() < min_insync_replicas {
+            return Err(MeierError::Storage(format!(
+                "Not enough in-sync replicas for partition {} of topic {}: {} (min: {})",
+                partition.id(),
+                topic.name(),
+                isr.len(),
+                min_insync_replicas
+            )));
+        }
+        // 확인 후 ISR 이 줄어 복제가 멈출 수 있으므로 대기 시간을 제한한다
+        let timeout = Duration::from_millis(topic_manager.cluster().ack_timeout_ms);
+        if !partition.wait_for_replication(offset, timeout).await? {
+            return Err(MeierError::NotEnoughReplicasAfterAppend(format!(
+                "offset {} of partition {} of topic {} was not replicated to the ISR within {:?}",
+                offset,
+                partition.id(),
+                topic.name(),
+                timeout
+            )));
+        }
+    }
+
+    topic_manager
+        .metrics()
+        .record_produce(topic.name(), size)
+        .await;
 
     Ok(Frame::Response {
         status: protocol::Status::ok(),
         data: None,
-        message: Some("Message produced successfully".to_string()),
+        message: Some(format!(
+            "Message produced successfully (partition={}, offset={})",
+            partition.id(),
+            offset
+        )),
+        record: None,
+        throttle_time_ms: None,
     })
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        config::{ClusterConfig, PeerConfig, StorageConfig},
+        protocol::TopicAssignment,
+    };
+
+    async fn replicated_topic_manager(replica_lag_time_max_ms: u64) -> TopicManager {
+        let storage = StorageConfig::default();
+        let cluster = ClusterConfig {
+            peers: vec![PeerConfig {
+                id: 1,
+                addr: "127.0.0.1:2401".to_string(),
+            }],
+            replication_factor: 2,
+            min_insync_replicas: 2,
+            replica_lag_time_max_ms,
+            ack_timeout_ms: 50,
+            ..ClusterConfig::default()
+        };
+        let topic_manager = TopicManager::new(
+            storage.max_topics,
+            storage.max_messages_per_partition,
+            storage.max_message_size_bytes,
+            storage.topic_defaults(),
+            cluster,
+        );
+        topic_manager
+            .create_assigned_topic(TopicAssignment {
+                name: "orders".to_string(),
+                configs: Default::default(),
+                replicas: vec![vec![0, 1]],
+                config: None,
+            })
+            .await
+            .unwrap();
+        topic_manager
+    }
+
+    async fn produce(topic_manager: &TopicManager, acks: Acks) -> Result<Frame> {
+        handle_produce(
+            topic_manager,
+            None,
+            "orders".to_string(),
+            Message::new(b"a".to_vec()),
+            Some(0),
+            None,
+            acks,
+        )
+        .await
+    }
+
+    #[tokio::test]
+    async fn acks_all_times_out_when_followers_do_not_replicate() {
+        let topic_manager = replicated_topic_manager(10_000).await;
+
+        assert!(produce(&topic_manager, Acks::Leader).await.is_ok());
+        let result = produce(&topic_manager, Acks::All).await;
+        assert!(matches!(
+            result,
+            Err(MeierError::NotEnoughReplicasAfterAppend(_))
+        ));
+
+        // 메시지는 리더에 기록된다
+        let partition = topic_manager.get_partition("orders", 0).await.unwrap();
+        assert_eq!(partition.log_end_offset().await, 2);
+    }
+
+    #[tokio::test]
+    async fn acks_all_is_rejected_when_isr_is_below_minimum() {
+        let topic_manager = replicated_topic_manager(20).await;
+        tokio::time::sleep(Duration::from_millis(50)).await;
+
+        let result = produce(&topic_manager, Acks::All).await;
+        let Err(MeierError::Storage(message)) = result else {
+            panic!("expected not enough in-sync replicas, got {:?}", result);
+        };
+        assert!(message.contains("Not enough in-sync replicas"));
+    }
+}
@@ -0,0 +1,51 @@
+use crate::{
+    Frame, MeierError, Result,
+    config::BrokerId,
+    protocol::{ReplicaFetchPartition, ReplicaFetchResult, ReplicaPartitionData},
+    storage::TopicManager,
+};
+
+/// 팔로워 요청 하나에 담는 파티션별 최대 메시지 바이트
+const REPLICA_FETCH_MAX_BYTES: usize = 1024 * 1024;
+
+pub async fn handle_replica_fetch(
+    topic_manager: &TopicManager,
+    replica_id: BrokerId,
+    partitions: Vec<ReplicaFetchPartition>,
+) -> Result<Frame> {
+    if topic_manager.cluster().peer(replica_id).is_none() {
+        return Err(MeierError::Protocol(format!(
+            "Unknown replica: broker {}",
+            replica_id
+        )));
+    }
+
+    let mut result = ReplicaFetchResult {
+        partitions: Vec::new(),
+    };
+
+    for request in partitions {
+        // 삭제되었거나 이 브로커가 리더가 아닌 파티션은 건너뛴다
+        let Ok(partition) = topic_manager
+            .get_leader_partition(&request.topic, request.partition_id)
+            .await
+        else {
+            continue;
+        };
+
+        let records = partition
+            .fetch_for_replica(replica_id, request.fetch_offset, REPLICA_FETCH_MAX_BYTES)
+            .await;
+
+        result.partitions.push(ReplicaPartitionData {
+            topic: request.topic,
+            partition_id: request.partition_id,
+            log_start_offset: partition.current_offset().await,
+            high_watermark: partition.high_watermark(),
+            isr: partition.in_sync_replicas().await,
+            records,
+        });
+    }
+
+    Frame::response_ok_json(&result)
+}
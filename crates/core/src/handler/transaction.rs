@@ -0,0 +1,80 @@
+use crate::{
+    Frame, Result,
+    protocol::{self, TopicPartition},
+    storage::{ControlType, TopicManager},
+};
+
+pub async fn handle_begin_txn(
+    topic_manager: &TopicManager,
+    transactional_id: String,
+) -> Result<Frame> {
+    let txn_id = topic_manager
+        .transactions()
+        .begin(transactional_id.clone())
+        .await?;
+
+    Ok(Frame::Response {
+        status: protocol::Status::ok(),
+        data: None,
+        message: Some(format!(
+            "Transaction {} started (id={})",
+            transactional_id, txn_id
+        )),
+        record: None,
+        throttle_time_ms: None,
+    })
+}
+
+pub async fn handle_add_partitions_to_txn(
+    topic_manager: &TopicManager,
+    transactional_id: String,
+    partitions: Vec<TopicPartition>,
+) -> Result<Frame> {
+    // 파티션 존재 및 리더 여부 확인(토픽이 없으면 생성)
+    for tp in &partitions {
+        topic_manager.get_or_create_topic(tp.topic.clone()).await?;
+        topic_manager
+            .get_leader_partition(&tp.topic, tp.partition_id)
+            .await?;
+    }
+
+    let count = partitions.len();
+    topic_manager
+        .transactions()
+        .add_partitions(&transactional_id, partitions)
+        .await?;
+
+    Ok(Frame::Response {
+        status: protocol::Status::ok(),
+        data: None,
+        message: Some(format!(
+            "Added {} partition(s) to transaction {}",
+            count, transactional_id
+        )),
+        record: None,
+        throttle_time_ms: None,
+    })
+}
+
+pub async fn handle_end_txn(
+    topic_manager: &TopicManager,
+    transactional_id: String,
+    control: ControlType,
+) -> Result<Frame> {
+    topic_manager
+        .end_transaction(&transactional_id, control)
+        .await?;
+
+    let action = match control {
+        ControlType::Commit => "committed",
+        ControlType::Abort => "aborted",
+    };
+
+    Ok(Frame::Response {
+        status: protocol::Status::ok(),
+        data: None,
+        message: Some(format!("Transaction {} {}", transactional_id, action)),
+        record: None,
+        throttle_time_ms: None,
+    })
+}
@@ -1,9 +1,18 @@
+pub mod acl;
+pub mod auth;
 pub mod config;
+pub mod connection;
+pub mod controller;
 pub mod error;
 pub mod handler;
+pub mod logging;
+pub mod metrics;
 pub mod protocol;
+pub mod quota;
+pub mod replication;
 pub mod server;
 pub mod storage;
+pub mod tls;
 
 pub use config::Config;
 pub use error::{MeierError, Result};
@@ -0,0 +1,166 @@
+use std::path::Path;
+use tracing::Subscriber;
+use tracing_appender::{
+    non_blocking::WorkerGuard,
+    rolling::{RollingFileAppender, Rotation},
+};
+use tracing_subscriber::{
+    EnvFilter, Layer, Registry,
+    filter::LevelFilter,
+    fmt::{self, MakeWriter},
+    layer::SubscriberExt,
+    registry::LookupSpan,
+    reload,
+    util::SubscriberInitExt,
+};
+
+use crate::{
+    MeierError, Result,
+    config::{LogFormat, LogRotation, LoggingConfig},
+};
+
+/// 실행 중 로그 설정을 바꾸기 위한 핸들
+///
+/// 파일 로그를 사용하는 경우 남은 로그가 기록되도록 종료 시까지 유지해야 한다.
+pub struct LogHandle {
+    filter: reload::Handle<EnvFilter, Registry>,
+    _file_guard: Option<WorkerGuard>,
+}
+
+impl LogHandle {
+    /// 로그 레벨과 모듈별 필터 변경(출력 형식과 파일은 재시작해야 적용된다)
+    pub fn reload(&self, config: &LoggingConfig) -> Result<()> {
+        let filter = env_filter(config)?;
+        self.filter
+            .reload(filter)
+            .map_err(|e| MeierError::Config(format!("Failed to reload log filter: {}", e)))
+    }
+}
+
+/// level 을 기본값으로 하고 filter 의 모듈별 지시어를 덧붙인 필터
+pub fn env_filter(config: &LoggingConfig) -> Result<EnvFilter> {
+    // with_default_directive 는 filter 가 비어 있을 때만 쓰이므로 level 을 직접 앞에 둔다
+    let level = LevelFilter::from_level(config.level.as_tracing_level());
+    let directives = match config.filter.as_deref() {
+        Some(filter) if !filter.is_empty() => format!("{},{}", level, filter),
+        _ => level.to_string(),
+    };
+
+    EnvFilter::builder()
+        .parse(directives)
+        .map_err(|e| MeierError::Config(format!("Invalid log filter: {}", e)))
+}
+
+/// 설정에 따라 전역 tracing subscriber 설치
+pub fn init(config: &LoggingConfig) -> Result<LogHandle> {
+    let (filter, filter_handle) = reload::Layer::new(env_filter(config)?);
+
+    let (file_layer, file_guard) = match &config.file {
+        Some(path) => {
+            let (writer, guard) =
+                tracing_appender::non_blocking(file_appender(Path::new(path), config.rotation)?);
+            (
+                Some(output_layer(config.format, writer, false)),
+                Some(guard),
+            )
+        }
+        None => (None, None),
+    };
+
+    tracing_subscriber::registry()
+        .with(filter)
+        .with(output_layer(config.format, std::io::stdout, true))
+        .with(file_layer)
+        .try_init()
+        .map_err(|e| MeierError::Config(format!("Failed to initialize logging: {}", e)))?;
+
+    Ok(LogHandle {
+        filter: filter_handle,
+        _file_guard: file_guard,
+    })
+}
+
+fn output_layer<S, W>(format: LogFormat, writer: W, ansi: bool) -> Box<dyn Layer<S> + Send + Sync>
+where
+    S: Subscriber + for<'a> LookupSpan<'a>,
+    W: for<'w> MakeWriter<'w> + Send + Sync + 'static,
+{
+    match format {
+        LogFormat::Text => fmt::layer().with_writer(writer).with_ansi(ansi).boxed(),
+        // 수집기가 연결 정보를 읽을 수 있도록 현재 span 과 상위 span 을 함께 기록
+        LogFormat::Json => fmt::layer()
+            .json()
+            .with_current_span(true)
+            .with_span_list(true)
+            .with_writer(writer)
+            .boxed(),
+    }
+}
+
+fn file_appender(path: &Path, rotation: LogRotation) -> Result<RollingFileAppender> {
+    let file_name = path
+        .file_name()
+        .ok_or_else(|| MeierError::Config(format!("Invalid log file path: {}", path.display())))?;
+    let dir = match path.parent() {
+        Some(dir) if !dir.as_os_str().is_empty() => dir,
+        _ => Path::new("."),
+    };
+
+    let rotation = match rotation {
+        LogRotation::Never => Rotation::NEVER,
+        LogRotation::Hourly => Rotation::HOURLY,
+        LogRotation::Daily => Rotation::DAILY,
+    };
+
+    RollingFileAppender::builder()
+        .rotation(rotation)
+        .filename_prefix(file_name.to_string_lossy())
+        .build(dir)
+        .map_err(|e| {
+            MeierError::Config(format!("Failed to open log file {}: {}", path.display(), e))
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::LogLevel;
+    use tracing::Level;
+
+    fn logging(level: LogLevel, filter: Option<&str>) -> LoggingConfig {
+        LoggingConfig {
+            level,
+            filter: filter.map(str::to_string),
+            ..LoggingConfig::default()
+        }
+    }
+
+    #[test]
+    fn env_filter_combines_level_and_module_directives() {
+        let filter =
+            env_filter(&logging(LogLevel::WARN, Some("meier_core::storage=debug"))).unwrap();
+        let directives = filter.to_string();
+        assert!(directives.contains("warn"));
+        assert!(directives.contains("meier_core::storage=debug"));
+        assert_eq!(filter.max_level_hint(), Some(LevelFilter::DEBUG));
+
+        assert!(env_filter(&logging(LogLevel::INFO, Some("meier_core=loud"))).is_err());
+    }
+
+    #[test]
+    fn reload_changes_the_active_level() {
+        let (filter, handle) =
+            reload::Layer::new(env_filter(&logging(LogLevel::INFO, None)).unwrap());
+        let log = LogHandle {
+            filter: handle,
+            _file_guard: None,
+        };
+
+        tracing::subscriber::with_default(tracing_subscriber::registry().with(filter), || {
+            assert!(!tracing::enabled!(Level::DEBUG));
+            log.reload(&logging(LogLevel::DEBUG, None)).unwrap();
+            assert!(tracing::enabled!(Level::DEBUG));
+            assert!(log.reload(&logging(LogLevel::DEBUG, Some("="))).is_err());
+        });
+    }
+}
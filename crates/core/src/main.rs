@@ -1,25 +1,154 @@
+mod cli;
+
 use clap::Parser;
-use meier_core::{Config, Result, server::Server};
-use tracing::{error, info};
+use meier_core::{
+    Config, Result,
+    config::LogLevel,
+    logging::{self, LogHandle},
+    server::Server,
+};
+use std::{
+    path::{Path, PathBuf},
+    sync::Arc,
+    time::{Duration, SystemTime},
+};
+use tracing::{error, info, warn};
+
+/// 설정 파일 변경 확인 주기
+const CONFIG_WATCH_INTERVAL: Duration = Duration::from_secs(2);
 
 #[derive(Parser, Debug)]
 #[command(name = "meier")]
 #[command(about = "A message broker server", long_about = None)]
-struct Args {}
+struct Args {
+    /// Config file path (default: ~/.config/meier/config.toml)
+    #[arg(short, long)]
+    config: Option<PathBuf>,
+
+    /// Address to listen on (e.g. 127.0.0.1:2369)
+    #[arg(short, long)]
+    bind: Option<String>,
+
+    /// Directory for broker data
+    #[arg(long)]
+    data_dir: Option<PathBuf>,
+
+    /// Log level
+    #[arg(long, value_enum, ignore_case = true)]
+    log_level: Option<LogLevel>,
+
+    /// Maximum number of topics
+    #[arg(long)]
+    max_topics: Option<usize>,
+
+    /// Maximum number of buffered messages
+    #[arg(long)]
+    max_messages_per_partition: Option<usize>,
+
+    /// Maximum message size in bytes
+    #[arg(long)]
+    max_message_size_bytes: Option<usize>,
+
+    /// Print the effective config and exit
+    #[arg(long)]
+    print_config: bool,
+
+    /// Check the config and exit
+    #[arg(long)]
+    check_config: bool,
+
+    #[command(subcommand)]
+    command: Option<cli::Command>,
+}
+
+impl Args {
+    /// 설정 파일/환경변수 위에 명령행 인자 적용
+    fn apply(&self, config: &mut Config) {
+        if let Some(bind) = &self.bind {
+            config.server.bind_addr = bind.clone();
+        }
+        if let Some(data_dir) = &self.data_dir {
+            config.storage.data_dir = data_dir.clone();
+        }
+        if let Some(level) = self.log_level {
+            config.logging.level = level;
+        }
+        if let Some(max_topics) = self.max_topics {
+            config.storage.max_topics = max_topics;
+        }
+        if let Some(max_messages) = self.max_messages_per_partition {
+            config.storage.max_messages_per_partition = max_messages;
+        }
+        if let Some(max_size) = self.max_message_size_bytes {
+            config.storage.max_message_size_bytes = max_size;
+        }
+    }
+}
+
+/// 설정 파일 -> 환경변수 -> 명령행 인자 순으로 적용한 뒤 검사
+///
+/// 사용자 설정 파일이 없으면 기본값을 사용한다(파일은 서버를 시작할 때만 만든다).
+fn load_config(args: &Args) -> Result<Config> {
+    let mut config = match &args.config {
+        // --config 가 지정되면 해당 파일을 사용한다.
+        Some(path) => Config::from_file(path)?,
+        None if Config::user_config_exists() => Config::load_user_config()?,
+        None => Config::from_env()?,
+    };
+
+    args.apply(&mut config);
+    config.validate()?;
+    Ok(config)
+}
+
 #[tokio::main]
 async fn main() -> Result<()> {
-    tracing_subscriber::fmt::init();
+    let mut args = Args::parse();
+
+    // 관리 명령이면 서버를 시작하지 않고 요청만 보낸다
+    if let Some(command) = args.command.take() {
+        if let Err(e) = cli::run(command).await {
+            eprintln!("{}", e);
+            std::process::exit(1);
+        }
+        return Ok(());
+    }
+
+    let config = match load_config(&args) {
+        Ok(config) => config,
+        Err(e) => {
+            eprintln!("{}", e);
+            std::process::exit(1);
+        }
+    };
+
+    if args.print_config {
+        let toml_content = toml::to_string_pretty(&config)
+            .map_err(|e| meier_core::MeierError::Config(e.to_string()))?;
+        print!("{}", toml_content);
+        return Ok(());
+    }
+
+    if args.check_config {
+        println!("Configuration OK");
+        return Ok(());
+    }
 
-    // config 파일이 있으면 가져오고 없으면 생성 후 그 내용을 가져온다.
-    let mut config = if Config::user_config_exists() {
-        Config::load_user_config()?
-    } else {
-        let default_config = Config::default();
-        default_config.save_user_config()?;
-        default_config
+    let log_handle = match logging::init(&config.logging) {
+        Ok(handle) => handle,
+        Err(e) => {
+            eprintln!("{}", e);
+            std::process::exit(1);
+        }
     };
 
-    config.server.bind_addr = format!("{}:{}", "127.0.0.1", "2369");
+    // 설정 파일이 없으면 수정할 수 있도록 기본값으로 만든다
+    if args.config.is_none() && !Config::user_config_exists() {
+        match Config::default().save_user_config() {
+            Ok(()) => info!("Created default config file"),
+            Err(e) => warn!("Failed to create default config file: {}", e),
+        }
+    }
 
     info!("Starting Meier server on {}", config.server.bind_addr);
     info!(
@@ -29,7 +158,24 @@ async fn main() -> Result<()> {
         config.storage.max_message_size_bytes
     );
 
-    let server = Server::new(config);
+    let server = match Server::new(config) {
+        Ok(server) => Arc::new(server),
+        Err(e) => {
+            error!("Failed to start server: {}", e);
+            return Err(e);
+        }
+    };
+
+    // SIGINT/SIGTERM 수신 시 서버 종료
+    let shutdown = server.shutdown_handle();
+    tokio::spawn(async move {
+        shutdown_signal().await;
+        info!("Shutdown signal received");
+        shutdown.shutdown();
+    });
+
+    // 설정 파일 변경 또는 SIGHUP 수신 시 설정 다시 읽기
+    tokio::spawn(watch_config(args, server.clone(), log_handle));
 
     if let Err(e) = server.run().await {
         error!("Server error: {}", e);
@@ -38,3 +184,182 @@ async fn main() -> Result<()> {
 
     Ok(())
 }
+
+fn config_path(args: &Args) -> Option<PathBuf> {
+    match &args.config {
+        Some(path) => Some(path.clone()),
+        None => Config::user_config_file().ok(),
+    }
+}
+
+fn modified_time(path: Option<&Path>) -> Option<SystemTime> {
+    std::fs::metadata(path?).and_then(|m| m.modified()).ok()
+}
+
+async fn watch_config(args: Args, server: Arc<Server>, log_handle: LogHandle) {
+    let path = config_path(&args);
+    let mut last_modified = modified_time(path.as_deref());
+    let mut interval = tokio::time::interval(CONFIG_WATCH_INTERVAL);
+
+    #[cfg(unix)]
+    let mut hangup = match tokio::signal::unix::signal(tokio::signal::unix::SignalKind::hangup()) {
+        Ok(signal) => Some(signal),
+        Err(e) => {
+            error!("Failed to listen for SIGHUP: {}", e);
+            None
+        }
+    };
+
+    loop {
+        #[cfg(unix)]
+        let hangup_received = async {
+            match hangup.as_mut() {
+                Some(signal) => {
+                    signal.recv().await;
+                }
+                None => std::future::pending().await,
+            }
+        };
+        #[cfg(not(unix))]
+        let hangup_received = std::future::pending::<()>();
+
+        tokio::select! {
+            _ = interval.tick() => {
+                let modified = modified_time(path.as_deref());
+                if modified == last_modified {
+                    continue;
+                }
+                last_modified = modified;
+                info!("Config file changed, reloading");
+            }
+            _ = hangup_received => {
+                info!("SIGHUP received, reloading config");
+            }
+        }
+
+        reload_config(&args, &server, &log_handle).await;
+    }
+}
+
+/// 설정을 다시 읽어 적용(실패하면 기존 설정 유지)
+async fn reload_config(args: &Args, server: &Server, log_handle: &LogHandle) {
+    let config = match load_config(args) {
+        Ok(config) => config,
+        Err(e) => {
+            error!("Config reload failed, keeping current config: {}", e);
+            return;
+        }
+    };
+    let logging = config.logging.clone();
+
+    let changes = match server.reload_config(config).await {
+        Ok(changes) => changes,
+        Err(e) => {
+            error!("Config reload failed, keeping current config: {}", e);
+            return;
+        }
+    };
+
+    if let Err(e) = log_handle.reload(&logging) {
+        error!("Failed to change log level: {}", e);
+    }
+
+    if changes.is_empty() {
+        info!("Config reloaded, no changes");
+        return;
+    }
+    if !changes.applied.is_empty() {
+        info!("Config reloaded, applied: {}", changes.applied.join(", "));
+    }
+    if !changes.restart_required.is_empty() {
+        warn!(
+            "Config changes require restart to take effect: {}",
+            changes.restart_required.join(", ")
+        );
+    }
+}
+
+async fn shutdown_signal() {
+    let ctrl_c = async {
+        if let Err(e) = tokio::signal::ctrl_c().await {
+            error!("Failed to listen for SIGINT: {}", e);
+            std::future::pending::<()>().await;
+        }
+    };
+
+    #[cfg(unix)]
+    let terminate = async {
+        match tokio::signal::unix::signal(tokio::signal::unix::SignalKind::terminate()) {
+            Ok(mut signal) => {
+                signal.recv().await;
+            }
+            Err(e) => {
+                error!("Failed to listen for SIGTERM: {}", e);
+                std::future::pending::<()>().await;
+            }
+        }
+    };
+
+    #[cfg(not(unix))]
+    let terminate = std::future::pending::<()>();
+
+    tokio::select! {
+        _ = ctrl_c => {}
+        _ = terminate => {}
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn command_line_overrides_config() {
+        let args = Args::try_parse_from([
+            "meier",
+            "--bind",
+            "0.0.0.0:2400",
+            "--data-dir",
+            "/var/lib/meier",
+            "--log-level",
+            "debug",
+            "--max-topics",
+            "5",
+            "--check-config",
+        ])
+        .unwrap();
+        let mut config = Config::default();
+        args.apply(&mut config);
+
+        assert!(args.check_config);
+        assert_eq!(config.server.bind_addr, "0.0.0.0:2400");
+        assert_eq!(config.storage.data_dir, PathBuf::from("/var/lib/meier"));
+        assert_eq!(config.logging.level, LogLevel::DEBUG);
+        assert_eq!(config.storage.max_topics, 5);
+        assert_eq!(
+            config.storage.max_messages_per_partition,
+            Config::default().storage.max_messages_per_partition
+        );
+    }
+
+    #[test]
+    fn checking_a_config_file_does_not_write_files() {
+        let dir = std::env::temp_dir().join(format!("meier-main-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("broker.toml");
+        std::fs::write(&path, "[storage]\nmax_topics = 7\n").unwrap();
+
+        let args = Args::try_parse_from([
+            "meier",
+            "--config",
+            path.to_str().unwrap(),
+            "--check-config",
+        ])
+        .unwrap();
+        let config = load_config(&args).unwrap();
+        assert_eq!(config.storage.max_topics, 7);
+        assert_eq!(std::fs::read_dir(&dir).unwrap().count(), 1);
+
+        std::fs::remove_dir_all(dir).unwrap();
+    }
+}
@@ -0,0 +1,565 @@
+use std::{
+    collections::BTreeMap,
+    fmt::Write as _,
+    sync::{
+        Arc,
+        atomic::{AtomicU64, Ordering},
+    },
+    time::Duration,
+};
+use tokio::{
+    io::{AsyncReadExt, AsyncWriteExt},
+    net::{TcpListener, TcpStream},
+    sync::RwLock,
+};
+use tokio_util::sync::CancellationToken;
+use tracing::{error, warn};
+
+use crate::{Result, connection::ConnectionTracker, protocol::ConsumerLag, storage::TopicManager};
+
+/// 메트릭 요청을 읽을 때 허용하는 최대 시간
+const HTTP_READ_TIMEOUT: Duration = Duration::from_secs(5);
+/// 메트릭 요청 헤더 최대 크기
+const HTTP_MAX_REQUEST_BYTES: usize = 8 * 1024;
+
+#[derive(Default)]
+struct TopicMetrics {
+    produced_messages: AtomicU64,
+    produced_bytes: AtomicU64,
+    consumed_messages: AtomicU64,
+    consumed_bytes: AtomicU64,
+}
+
+type TopicCounter = fn(&TopicMetrics) -> &AtomicU64;
+
+#[derive(Default)]
+struct RequestMetrics {
+    total: AtomicU64,
+    errors: AtomicU64,
+}
+
+/// 브로커 누적 카운터
+///
+/// 버퍼 사용량, 파티션 오프셋처럼 현재 상태를 나타내는 값은 수집 시점에 저장소에서 읽는다.
+#[derive(Default)]
+pub struct Metrics {
+    topics: RwLock<BTreeMap<String, TopicMetrics>>,
+    requests: RwLock<BTreeMap<&'static str, RequestMetrics>>,
+    connections_accepted: AtomicU64,
+    connections_rejected: AtomicU64,
+    invalid_frames: AtomicU64,
+    request_timeouts: AtomicU64,
+    authentication_failures: AtomicU64,
+    throttled_requests: AtomicU64,
+    throttle_time_ms: AtomicU64,
+}
+
+impl Metrics {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub async fn record_produce(&self, topic: &str, bytes: usize) {
+        self.with_topic(topic, |m| {
+            m.produced_messages.fetch_add(1, Ordering::Relaxed);
+            m.produced_bytes.fetch_add(bytes as u64, Ordering::Relaxed);
+        })
+        .await;
+    }
+
+    pub async fn record_consume(&self, topic: &str, bytes: usize) {
+        self.with_topic(topic, |m| {
+            m.consumed_messages.fetch_add(1, Ordering::Relaxed);
+            m.consumed_bytes.fetch_add(bytes as u64, Ordering::Relaxed);
+        })
+        .await;
+    }
+
+    /// 삭제된 토픽의 카운터 제거
+    pub async fn remove_topic(&self, topic: &str) {
+        self.topics.write().await.remove(topic);
+    }
+
+    /// 요청 처리 결과 기록(failed 는 에러 응답을 보낸 경우)
+    pub async fn record_request(&self, frame: &'static str, failed: bool) {
+        let record = |m: &RequestMetrics| {
+            m.total.fetch_add(1, Ordering::Relaxed);
+            if failed {
+                m.errors.fetch_add(1, Ordering::Relaxed);
+            }
+        };
+
+        if let Some(m) = self.requests.read().await.get(frame) {
+            record(m);
+            return;
+        }
+        record(self.requests.write().await.entry(frame).or_default());
+    }
+
+    pub fn record_connection_accepted(&self) {
+        self.connections_accepted.fetch_add(1, Ordering::Relaxed);
+    }
+
+    pub fn record_connection_rejected(&self) {
+        self.connections_rejected.fetch_add(1, Ordering::Relaxed);
+    }
+
+    pub fn record_invalid_frame(&self) {
+        self.invalid_frames.fetch_add(1, Ordering::Relaxed);
+    }
+
+    pub fn record_request_timeout(&self) {
+        self.request_timeouts.fetch_add(1, Ordering::Relaxed);
+    }
+
+    pub fn record_authentication_failure(&self) {
+        self.authentication_failures.fetch_add(1, Ordering::Relaxed);
+    }
+
+    /// 쿼터를 넘어 지연시킨 요청
+    pub fn record_throttle(&self, delay: Duration) {
+        self.throttled_requests.fetch_add(1, Ordering::Relaxed);
+        self.throttle_time_ms
+            .fetch_add(delay.as_millis() as u64, Ordering::Relaxed);
+    }
+
+    async fn with_topic(&self, topic: &str, record: impl Fn(&TopicMetrics)) {
+        if let Some(m) = self.topics.read().await.get(topic) {
+            record(m);
+            return;
+        }
+        record(
+            self.topics
+                .write()
+                .await
+                .entry(topic.to_string())
+                .or_default(),
+        );
+    }
+
+    /// Prometheus 텍스트 형식으로 출력
+    pub async fn render(
+        &self,
+        topic_manager: &TopicManager,
+        connections: &ConnectionTracker,
+    ) -> String {
+        let mut out = TextEncoder::default();
+
+        {
+            let topics = self.topics.read().await;
+            let counters: [(&str, &str, TopicCounter); 4] = [
+                ("meier_messages_produced_total", "Messages produced", |m| {
+                    &m.produced_messages
+                }),
+                ("meier_bytes_produced_total", "Bytes produced", |m| {
+                    &m.produced_bytes
+                }),
+                ("meier_messages_consumed_total", "Messages consumed", |m| {
+                    &m.consumed_messages
+                }),
+                ("meier_bytes_consumed_total", "Bytes consumed", |m| {
+                    &m.consumed_bytes
+                }),
+            ];
+            for (name, help, counter) in counters {
+                out.header(name, "counter", help);
+                for (topic, m) in topics.iter() {
+                    out.sample(name, &[("topic", topic)], load(counter(m)));
+                }
+            }
+        }
+
+        {
+            let requests = self.requests.read().await;
+            out.header(
+                "meier_requests_total",
+                "counter",
+                "Requests handled by frame type",
+            );
+            for (frame, m) in requests.iter() {
+                out.sample("meier_requests_total", &[("frame", frame)], load(&m.total));
+            }
+            out.header(
+                "meier_request_errors_total",
+                "counter",
+                "Requests answered with an error",
+            );
+            for (frame, m) in requests.iter() {
+                out.sample(
+                    "meier_request_errors_total",
+                    &[("frame", frame)],
+                    load(&m.errors),
+                );
+            }
+        }
+
+        out.single(
+            "meier_request_timeouts_total",
+            "counter",
+            "Requests that timed out",
+            load(&self.request_timeouts),
+        );
+        out.single(
+            "meier_invalid_frames_total",
+            "counter",
+            "Frames that could not be decoded",
+            load(&self.invalid_frames),
+        );
+        out.single(
+            "meier_authentication_failures_total",
+            "counter",
+            "Failed SASL authentications",
+            load(&self.authentication_failures),
+        );
+        out.single(
+            "meier_throttled_requests_total",
+            "counter",
+            "Requests delayed by client quotas",
+            load(&self.throttled_requests),
+        );
+        out.single(
+            "meier_throttle_time_ms_total",
+            "counter",
+            "Total delay applied by client quotas in milliseconds",
+            load(&self.throttle_time_ms),
+        );
+        out.single(
+            "meier_connections_accepted_total",
+            "counter",
+            "Accepted connections",
+            load(&self.connections_accepted),
+        );
+        out.single(
+            "meier_connections_rejected_total",
+            "counter",
+            "Connections rejected by connection limits",
+            load(&self.connections_rejected),
+        );
+        out.single(
+            "meier_connections_active",
+            "gauge",
+            "Open connections",
+            connections.total() as u64,
+        );
+
+        let buffer = topic_manager.buffer_usage().await;
+        out.single(
+            "meier_buffer_bytes",
+            "gauge",
+            "Bytes held in the message buffer",
+            buffer.size_bytes as u64,
+        );
+        out.single(
+            "meier_buffer_max_bytes",
+            "gauge",
+            "Message buffer size limit",
+            buffer.max_size_bytes as u64,
+        );
+        out.single(
+            "meier_buffer_messages",
+            "gauge",
+            "Messages held in the message buffer",
+            buffer.message_count as u64,
+        );
+        out.single(
+            "meier_buffer_max_messages",
+            "gauge",
+            "Message buffer count limit",
+            buffer.max_messages as u64,
+        );
+
+        let mut names = topic_manager.list_topics().await;
+        names.sort();
+        out.single(
+            "meier_topics",
+            "gauge",
+            "Number of topics",
+            names.len() as u64,
+        );
+
+        let mut partitions = Vec::new();
+        for name in names {
+            if let Some(topic) = topic_manager.get_topic(&name).await {
+                for (partition_id, partition) in topic.partitions() {
+                    partitions.push(PartitionSnapshot {
+                        topic: name.clone(),
+                        partition: partition_id.to_string(),
+                        log_start_offset: partition.current_offset().await,
+                        log_end_offset: partition.log_end_offset().await,
+                        messages: partition.message_count().await,
+                        bytes: partition.size_bytes().await,
+                    });
+                }
+            }
+        }
+
+        let gauges: [(&str, &str, PartitionGauge); 4] = [
+            (
+                "meier_partition_log_start_offset",
+                "First offset still stored in the partition",
+                |p| p.log_start_offset,
+            ),
+            (
+                "meier_partition_log_end_offset",
+                "Offset of the next message written to the partition",
+                |p| p.log_end_offset,
+            ),
+            (
+                "meier_partition_messages",
+                "Messages stored in the partition",
+                |p| p.messages,
+            ),
+            (
+                "meier_partition_bytes",
+                "Bytes stored in the partition",
+                |p| p.bytes,
+            ),
+        ];
+        for (name, help, gauge) in gauges {
+            out.header(name, "gauge", help);
+            for p in &partitions {
+                out.sample(
+                    name,
+                    &[("topic", &p.topic), ("partition", &p.partition)],
+                    gauge(p) as u64,
+                );
+            }
+        }
+
+        let lag = topic_manager.consumer_lag(None).await;
+        let gauges: [(&str, &str, LagGauge); 2] = [
+            (
+                "meier_consumer_lag",
+                "Messages between the committed offset and the log end offset",
+                |l| l.lag as u64,
+            ),
+            (
+                "meier_consumer_lag_ms",
+                "Age of the oldest message not yet consumed by the group",
+                |l| l.time_lag_ms,
+            ),
+        ];
+        for (name, help, gauge) in gauges {
+            out.header(name, "gauge", help);
+            for l in &lag {
+                let partition = l.partition_id.to_string();
+                let labels = [
+                    ("group", l.group.as_str()),
+                    ("topic", l.topic.as_str()),
+                    ("partition", partition.as_str()),
+                ];
+                out.sample(name, &labels, gauge(l));
+            }
+        }
+
+        out.finish()
+    }
+}
+
+struct PartitionSnapshot {
+    topic: String,
+    partition: String,
+    log_start_offset: usize,
+    log_end_offset: usize,
+    messages: usize,
+    bytes: usize,
+}
+
+type PartitionGauge = fn(&PartitionSnapshot) -> usize;
+
+type LagGauge = fn(&ConsumerLag) -> u64;
+
+fn load(counter: &AtomicU64) -> u64 {
+    counter.load(Ordering::Relaxed)
+}
+
+/// Prometheus 텍스트 형식 작성기
+#[derive(Default)]
+struct TextEncoder {
+    out: String,
+}
+
+impl TextEncoder {
+    fn header(&mut self, name: &str, kind: &str, help: &str) {
+        let _ = writeln!(self.out, "# HELP {} {}", name, help);
+        let _ = writeln!(self.out, "# TYPE {} {}", name, kind);
+    }
+
+    fn sample(&mut self, name: &str, labels: &[(&str, &str)], value: u64) {
+        self.out.push_str(name);
+        if !labels.is_empty() {
+            let labels: Vec<String> = labels
+                .iter()
+                .map(|(k, v)| format!("{}=\"{}\"", k, escape_label(v)))
+                .collect();
+            let _ = write!(self.out, "{{{}}}", labels.join(","));
+        }
+        let _ = writeln!(self.out, " {}", value);
+    }
+
+    fn single(&mut self, name: &str, kind: &str, help: &str, value: u64) {
+        self.header(name, kind, help);
+        self.sample(name, &[], value);
+    }
+
+    fn finish(self) -> String {
+        self.out
+    }
+}
+
+fn escape_label(value: &str) -> String {
+    value
+        .replace('\\', "\\\\")
+        .replace('"', "\\\"")
+        .replace('\n', "\\n")
+}
+
+/// HTTP 로 메트릭 제공(path 외의 요청에는 404)
+pub async fn serve(
+    listener: TcpListener,
+    path: String,
+    topic_manager: Arc<TopicManager>,
+    connections: Arc<ConnectionTracker>,
+    shutdown: CancellationToken,
+) {
+    let path = Arc::new(path);
+
+    loop {
+        let stream = tokio::select! {
+            result = listener.accept() => match result {
+                Ok((stream, _)) => stream,
+                Err(e) => {
+                    error!("Failed to accept metrics connection: {}", e);
+                    continue;
+                }
+            },
+            _ = shutdown.cancelled() => break,
+        };
+
+        let path = path.clone();
+        let topic_manager = topic_manager.clone();
+        let connections = connections.clone();
+        tokio::spawn(async move {
+            if let Err(e) = handle_http(stream, &path, &topic_manager, &connections).await {
+                warn!("Metrics request failed: {}", e);
+            }
+        });
+    }
+}
+
+async fn handle_http(
+    mut stream: TcpStream,
+    path: &str,
+    topic_manager: &TopicManager,
+    connections: &ConnectionTracker,
+) -> Result<()> {
+    let mut request = Vec::new();
+    let mut buf = [0u8; 1024];
+
+    // 요청 본문은 쓰지 않으므로 헤더 끝까지만 읽는다
+    let read_headers = async {
+        while !request.windows(4).any(|w| w == b"\r\n\r\n") {
+            let n = stream.read(&mut buf).await?;
+            if n == 0 || request.len() + n > HTTP_MAX_REQUEST_BYTES {
+                break;
+            }
+            request.extend_from_slice(&buf[..n]);
+        }
+        Ok::<_, std::io::Error>(())
+    };
+    if tokio::time::timeout(HTTP_READ_TIMEOUT, read_headers)
+        .await
+        .is_err()
+    {
+        return Ok(());
+    }
+
+    let request_line = String::from_utf8_lossy(&request);
+    let mut parts = request_line
+        .lines()
+        .next()
+        .unwrap_or_default()
+        .split_whitespace();
+    let (method, target) = (parts.next(), parts.next());
+    // 쿼리 문자열은 무시
+    let target = target.map(|t| t.split('?').next().unwrap_or(t));
+
+    let (status, content_type, body) = match (method, target) {
+        (Some("GET"), Some(target)) if target == path => (
+            "200 OK",
+            "text/plain; version=0.0.4; charset=utf-8",
+            topic_manager
+                .metrics()
+                .render(topic_manager, connections)
+                .await,
+        ),
+        (Some("GET"), _) => ("404 Not Found", "text/plain", "Not Found\n".to_string()),
+        _ => (
+            "405 Method Not Allowed",
+            "text/plain",
+            "Method Not Allowed\n".to_string(),
+        ),
+    };
+
+    let response = format!(
+        "HTTP/1.1 {}\r\nContent-Type: {}\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+        status,
+        content_type,
+        body.len(),
+        body
+    );
+    stream.write_all(response.as_bytes()).await?;
+    stream.shutdown().await?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{protocol::Frame, storage::topic::tests::topic_manager};
+
+    #[tokio::test]
+    async fn render_reports_counters_and_forgets_deleted_topics() {
+        let topics = topic_manager();
+        let connections = ConnectionTracker::new(10, 10);
+        topics
+            .create_topic("orders".to_string(), Default::default())
+            .await
+            .unwrap();
+
+        let metrics = topics.metrics();
+        metrics.record_produce("orders", 5).await;
+        metrics.record_produce("orders", 7).await;
+        for response in [
+            Frame::response_ok(None),
+            Frame::response_error("Partition not found".to_string()),
+        ] {
+            metrics
+                .record_request("Produce", response.is_error_response())
+                .await;
+        }
+
+        let text = metrics.render(&topics, &connections).await;
+        assert!(text.contains("# TYPE meier_messages_produced_total counter\n"));
+        assert!(text.contains("meier_messages_produced_total{topic=\"orders\"} 2\n"));
+        assert!(text.contains("meier_bytes_produced_total{topic=\"orders\"} 12\n"));
+        assert!(text.contains("meier_requests_total{frame=\"Produce\"} 2\n"));
+        assert!(text.contains("meier_request_errors_total{frame=\"Produce\"} 1\n"));
+        assert!(text.contains("meier_topics 1\n"));
+        assert!(
+            text.contains("meier_partition_log_end_offset{topic=\"orders\",partition=\"0\"} 0\n")
+        );
+
+        topics.delete_topic("orders").await.unwrap();
+        let text = metrics.render(&topics, &connections).await;
+        assert!(!text.contains("topic=\"orders\""));
+        assert!(text.contains("meier_topics 0\n"));
+    }
+
+    #[test]
+    fn label_values_are_escaped() {
+        let mut out = TextEncoder::default();
+        out.sample("meier_test", &[("topic", "a\"b\\c\nd")], 1);
+        assert_eq!(out.finish(), "meier_test{topic=\"a\\\"b\\\\c\\nd\"} 1\n");
+    }
+}
@@ -33,11 +33,9 @@ impl Default for MeierCodec {
     }
 }
 
-impl Decoder for MeierCodec {
-    type Item = Frame;
-    type Error = MeierError;
-
-    fn decode(&mut self, src: &mut BytesMut) -> Result<Option<Self::Item>> {
+impl MeierCodec {
+    /// 길이 필드를 읽고 프레임 데이터를 분리
+    fn decode_payload(&mut self, src: &mut BytesMut) -> Result<Option<BytesMut>> {
         if src.len() < 4 {
             return Ok(None);
         }
@@ -61,12 +59,55 @@ impl Decoder for MeierCodec {
         src.advance(4);
 
         // 데이터 추출
-        let data = src.split_to(length);
+        Ok(Some(src.split_to(length)))
+    }
+
+    /// 역직렬화 실패를 에러 대신 `Decoded::Invalid` 로 반환하는 디코더
+    pub fn lenient(self) -> LenientCodec {
+        LenientCodec { inner: self }
+    }
+}
 
-        // 역직렬화
-        match Frame::from_bytes(&data) {
-            Ok(frame) => Ok(Some(frame)),
-            Err(e) => Err(e),
+impl Decoder for MeierCodec {
+    type Item = Frame;
+    type Error = MeierError;
+
+    fn decode(&mut self, src: &mut BytesMut) -> Result<Option<Self::Item>> {
+        match self.decode_payload(src)? {
+            // 역직렬화
+            Some(data) => Frame::from_bytes(&data).map(Some),
+            None => Ok(None),
+        }
+    }
+}
+
+/// 디코딩 결과
+pub enum Decoded {
+    Frame(Frame),
+    /// 역직렬화 실패(프레임 경계는 유지되므로 다음 프레임을 계속 읽을 수 있다)
+    Invalid(MeierError),
+}
+
+/// 서버 측 디코더
+///
+/// 잘못된 JSON 은 `Decoded::Invalid` 로 전달하고, 프레임 경계를 잃는 오류(최대 길이 초과)만
+/// 에러로 반환한다.
+#[derive(Clone)]
+pub struct LenientCodec {
+    inner: MeierCodec,
+}
+
+impl Decoder for LenientCodec {
+    type Item = Decoded;
+    type Error = MeierError;
+
+    fn decode(&mut self, src: &mut BytesMut) -> Result<Option<Self::Item>> {
+        match self.inner.decode_payload(src)? {
+            Some(data) => Ok(Some(match Frame::from_bytes(&data) {
+                Ok(frame) => Decoded::Frame(frame),
+                Err(e) => Decoded::Invalid(e),
+            })),
+            None => Ok(None),
         }
     }
 }
@@ -97,3 +138,57 @@ impl Encoder<Frame> for MeierCodec {
         Ok(())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn encoded(payload: &[u8]) -> BytesMut {
+        let mut buf = BytesMut::new();
+        buf.put_u32(payload.len() as u32);
+        buf.put_slice(payload);
+        buf
+    }
+
+    #[test]
+    fn lenient_decoder_recovers_after_invalid_frame() {
+        let mut codec = MeierCodec::new().lenient();
+        let mut src = encoded(b"{\"Unknown\":{}}");
+        MeierCodec::new().encode(Frame::Ping, &mut src).unwrap();
+
+        assert!(matches!(
+            codec.decode(&mut src).unwrap(),
+            Some(Decoded::Invalid(_))
+        ));
+        assert!(matches!(
+            codec.decode(&mut src).unwrap(),
+            Some(Decoded::Frame(Frame::Ping))
+        ));
+        assert!(src.is_empty());
+    }
+
+    #[test]
+    fn waits_for_the_whole_frame() {
+        let mut codec = MeierCodec::new();
+        let mut full = BytesMut::new();
+        codec.encode(Frame::Ping, &mut full).unwrap();
+
+        let mut src = BytesMut::from(&full[..full.len() - 1]);
+        assert!(codec.decode(&mut src).unwrap().is_none());
+        src.put_u8(full[full.len() - 1]);
+        assert_eq!(codec.decode(&mut src).unwrap(), Some(Frame::Ping));
+    }
+
+    #[test]
+    fn rejects_frames_over_the_max_length() {
+        let mut codec = MeierCodec::with_max_length(8).lenient();
+        let mut src = encoded(&[b' '; 16]);
+        assert!(matches!(
+            codec.decode(&mut src),
+            Err(MeierError::Protocol(message)) if message.contains("Frame too large")
+        ));
+
+        let mut encoder = MeierCodec::with_max_length(4);
+        assert!(encoder.encode(Frame::Ping, &mut BytesMut::new()).is_err());
+    }
+}
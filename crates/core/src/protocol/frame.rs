@@ -1,34 +1,248 @@
-use crate::{MeierError, Result};
-use serde::{Deserialize, Serialize};
+use crate::{
+    MeierError, Result,
+    config::BrokerId,
+    protocol::{
+        AclBinding, AclFilter, MetadataEntry, PartitionReassignment, ReplicaFetchPartition,
+    },
+};
+use serde::{Deserialize, Serialize, de::DeserializeOwned};
+use std::{collections::BTreeMap, time::Duration};
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 pub enum Frame {
     Produce {
         topic: String,
         message: Vec<u8>,
+        /// 트랜잭션 produce 시 대상 파티션 지정
+        #[serde(default)]
+        partition_id: Option<usize>,
+        #[serde(default)]
+        transactional_id: Option<String>,
+        #[serde(default)]
+        headers: BTreeMap<String, String>,
+        /// 프로듀서가 지정한 이벤트 시각(ms)
+        #[serde(default)]
+        timestamp: Option<u64>,
+        #[serde(default)]
+        acks: Acks,
     },
     Consume {
         topic: String,
         partition_id: usize,
         offset: usize,
+        #[serde(default)]
+        isolation_level: IsolationLevel,
     },
     ConsumeNext {
         topic: String,
         partition_id: usize,
+        #[serde(default)]
+        isolation_level: IsolationLevel,
+    },
+    /// timestamp 이후 첫 오프셋 조회(EARLIEST_TIMESTAMP / LATEST_TIMESTAMP 지원)
+    ListOffsets {
+        topic: String,
+        partition_id: usize,
+        timestamp: i64,
+    },
+    /// 토픽/파티션 구성 조회(None 이면 전체 토픽)
+    Metadata {
+        #[serde(default)]
+        topics: Option<Vec<String>>,
+    },
+    /// 토픽 내 파티션별 오프셋/크기 조회
+    DescribePartitions {
+        topic: String,
+    },
+    CreateTopic {
+        topic: String,
+        /// 토픽별 설정(지정하지 않은 항목은 브로커 기본값)
+        #[serde(default)]
+        configs: BTreeMap<String, String>,
+    },
+    DeleteTopic {
+        topic: String,
+    },
+    /// before_offset 이전의 메시지 삭제
+    DeleteRecords {
+        topic: String,
+        partition_id: usize,
+        before_offset: usize,
+    },
+    DescribeConfigs {
+        topic: String,
+    },
+    /// set 항목을 적용하고 delete 항목은 기본값으로 복원
+    AlterConfigs {
+        topic: String,
+        #[serde(default)]
+        set: BTreeMap<String, String>,
+        #[serde(default)]
+        delete: Vec<String>,
+    },
+    BeginTxn {
+        transactional_id: String,
+    },
+    AddPartitionsToTxn {
+        transactional_id: String,
+        partitions: Vec<TopicPartition>,
+    },
+    CommitTxn {
+        transactional_id: String,
+    },
+    AbortTxn {
+        transactional_id: String,
+    },
+    /// 컨슈머 그룹이 다음에 읽을 오프셋 저장
+    OffsetCommit {
+        group: String,
+        topic: String,
+        partition_id: usize,
+        offset: usize,
+    },
+    OffsetFetch {
+        group: String,
+        topic: String,
+        partition_id: usize,
+    },
+    /// 컨슈머 그룹별 지연 조회(None 이면 전체 그룹)
+    DescribeConsumerLag {
+        #[serde(default)]
+        group: Option<String>,
+    },
+    /// 팔로워가 리더에게 복제할 메시지 요청
+    ReplicaFetch {
+        replica_id: BrokerId,
+        partitions: Vec<ReplicaFetchPartition>,
+    },
+    /// 컨트롤러 후보가 다른 컨트롤러에게 투표 요청
+    RaftVote {
+        term: u64,
+        candidate_id: BrokerId,
+        last_log_index: u64,
+        last_log_term: u64,
+    },
+    /// 컨트롤러 리더가 메타데이터 로그 복제(entries 가 비어 있으면 heartbeat)
+    RaftAppend {
+        term: u64,
+        leader_id: BrokerId,
+        prev_log_index: u64,
+        prev_log_term: u64,
+        entries: Vec<MetadataEntry>,
+        leader_commit: u64,
+    },
+    /// 컨트롤러 리더와 브로커 목록 조회
+    DescribeCluster,
+    /// 파티션 복제본을 다른 브로커로 옮긴다(새 복제본이 따라잡으면 기존 복제본 삭제)
+    ReassignPartitions {
+        reassignments: Vec<PartitionReassignment>,
+        /// 새 복제본이 가져오는 초당 최대 바이트(None 이면 제한 없음)
+        #[serde(default)]
+        throttle_bytes_per_sec: Option<u64>,
+    },
+    /// 브로커의 모든 파티션 복제본을 다른 브로커로 옮긴다
+    DecommissionBroker {
+        broker_id: BrokerId,
+        #[serde(default)]
+        throttle_bytes_per_sec: Option<u64>,
+    },
+    /// 진행 중인 재배치 조회
+    ListReassignments,
+    /// 인증에 사용할 SASL 메커니즘 선택(응답 data 는 브로커가 허용하는 메커니즘 목록)
+    SaslHandshake {
+        mechanism: String,
+    },
+    /// SASL 메시지 교환(응답 data 는 브로커가 보내는 다음 메시지)
+    SaslAuthenticate {
+        auth_bytes: Vec<u8>,
+    },
+    CreateAcls {
+        acls: Vec<AclBinding>,
+    },
+    /// 조건에 맞는 ACL 조회
+    DescribeAcls {
+        #[serde(default)]
+        filter: AclFilter,
+    },
+    /// 조건 중 하나라도 맞는 ACL 삭제(응답은 삭제한 ACL 목록)
+    DeleteAcls {
+        filters: Vec<AclFilter>,
+    },
+    /// 연결의 client id 지정(클라이언트별 쿼터를 적용할 때 사용)
+    SetClientId {
+        client_id: String,
     },
     Response {
         status: Status,
         data: Option<Vec<u8>>,
         message: Option<String>,
+        /// 반환된 메시지의 메타데이터
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        record: Option<RecordMetadata>,
+        /// 쿼터를 넘어 브로커가 이 연결의 다음 요청을 읽지 않는 시간(ms)
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        throttle_time_ms: Option<u64>,
     },
     Ping,
     Pong,
 }
 
+/// ListOffsets: 로그 끝 오프셋 조회
+pub const LATEST_TIMESTAMP: i64 = -1;
+/// ListOffsets: 로그 시작 오프셋 조회
+pub const EARLIEST_TIMESTAMP: i64 = -2;
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct RecordMetadata {
+    pub offset: usize,
+    /// 브로커 기록 시각(ms)
+    pub timestamp: u64,
+    /// 프로듀서가 지정한 이벤트 시각(ms)
+    pub event_timestamp: Option<u64>,
+    #[serde(default)]
+    pub headers: BTreeMap<String, String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, Hash)]
+pub struct TopicPartition {
+    pub topic: String,
+    pub partition_id: usize,
+}
+
+/// Produce 응답 시점
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq, Eq)]
+pub enum Acks {
+    /// acks=0: 응답을 보내지 않음
+    None,
+    /// acks=1: 리더에 기록되면 응답
+    #[default]
+    Leader,
+    /// acks=all: 모든 ISR 에 복제되면 응답
+    All,
+}
+
+/// Consume 시 트랜잭션 데이터 노출 범위
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq, Eq)]
+pub enum IsolationLevel {
+    /// 진행 중이거나 중단된 트랜잭션 메시지도 반환
+    #[default]
+    ReadUncommitted,
+    /// 커밋된 트랜잭션 메시지만 반환
+    ReadCommitted,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 pub enum Status {
     Ok,
     Error(String),
+    /// 요청한 파티션의 리더가 아님(클라이언트는 leader 브로커로 다시 보낸다)
+    NotLeaderForPartition {
+        topic: String,
+        partition_id: usize,
+        leader: BrokerId,
+    },
+    /// ACL 로 허용되지 않은 요청
+    NotAuthorized(String),
 }
 
 impl Frame {
@@ -48,8 +262,56 @@ impl Frame {
         Ok(self.to_bytes()?.len())
     }
 
+    /// 로그/메트릭에 쓰는 프레임 종류 이름
+    pub fn name(&self) -> &'static str {
+        match self {
+            Frame::Produce { .. } => "Produce",
+            Frame::Consume { .. } => "Consume",
+            Frame::ConsumeNext { .. } => "ConsumeNext",
+            Frame::ListOffsets { .. } => "ListOffsets",
+            Frame::Metadata { .. } => "Metadata",
+            Frame::DescribePartitions { .. } => "DescribePartitions",
+            Frame::CreateTopic { .. } => "CreateTopic",
+            Frame::DeleteTopic { .. } => "DeleteTopic",
+            Frame::DeleteRecords { .. } => "DeleteRecords",
+            Frame::DescribeConfigs { .. } => "DescribeConfigs",
+            Frame::AlterConfigs { .. } => "AlterConfigs",
+            Frame::BeginTxn { .. } => "BeginTxn",
+            Frame::AddPartitionsToTxn { .. } => "AddPartitionsToTxn",
+            Frame::CommitTxn { .. } => "CommitTxn",
+            Frame::AbortTxn { .. } => "AbortTxn",
+            Frame::OffsetCommit { .. } => "OffsetCommit",
+            Frame::OffsetFetch { .. } => "OffsetFetch",
+            Frame::DescribeConsumerLag { .. } => "DescribeConsumerLag",
+            Frame::ReplicaFetch { .. } => "ReplicaFetch",
+            Frame::RaftVote { .. } => "RaftVote",
+            Frame::RaftAppend { .. } => "RaftAppend",
+            Frame::DescribeCluster => "DescribeCluster",
+            Frame::ReassignPartitions { .. } => "ReassignPartitions",
+            Frame::DecommissionBroker { .. } => "DecommissionBroker",
+            Frame::ListReassignments => "ListReassignments",
+            Frame::SaslHandshake { .. } => "SaslHandshake",
+            Frame::SaslAuthenticate { .. } => "SaslAuthenticate",
+            Frame::CreateAcls { .. } => "CreateAcls",
+            Frame::DescribeAcls { .. } => "DescribeAcls",
+            Frame::DeleteAcls { .. } => "DeleteAcls",
+            Frame::SetClientId { .. } => "SetClientId",
+            Frame::Response { .. } => "Response",
+            Frame::Ping => "Ping",
+            Frame::Pong => "Pong",
+        }
+    }
+
     pub fn produce(topic: String, message: Vec<u8>) -> Self {
-        Self::Produce { topic, message }
+        Self::Produce {
+            topic,
+            message,
+            partition_id: None,
+            transactional_id: None,
+            headers: BTreeMap::new(),
+            timestamp: None,
+            acks: Acks::default(),
+        }
     }
 
     pub fn consume(topic: String, partition_id: usize, offset: usize) -> Self {
@@ -57,6 +319,7 @@ impl Frame {
             topic,
             partition_id,
             offset,
+            isolation_level: IsolationLevel::default(),
         }
     }
 
@@ -65,6 +328,8 @@ impl Frame {
             status: Status::ok(),
             data,
             message: None,
+            record: None,
+            throttle_time_ms: None,
         }
     }
 
@@ -73,6 +338,112 @@ impl Frame {
             status: Status::Error(message.clone()),
             data: None,
             message: Some(message),
+            record: None,
+            throttle_time_ms: None,
+        }
+    }
+
+    /// 처리 실패 응답(클라이언트가 구분해야 하는 에러는 전용 상태로 보낸다)
+    pub fn response_from_error(error: MeierError) -> Self {
+        let message = error.to_string();
+        let status = match error {
+            MeierError::NotLeaderForPartition {
+                topic,
+                partition_id,
+                leader,
+            } => Status::NotLeaderForPartition {
+                topic,
+                partition_id,
+                leader,
+            },
+            MeierError::NotAuthorized(reason) => Status::NotAuthorized(reason),
+            _ => Status::Error(message.clone()),
+        };
+
+        Self::Response {
+            status,
+            data: None,
+            message: Some(message),
+            record: None,
+            throttle_time_ms: None,
+        }
+    }
+
+    /// 응답 데이터를 JSON 으로 직렬화
+    pub fn response_ok_json<T: Serialize>(value: &T) -> Result<Self> {
+        let data = serde_json::to_vec(value)
+            .map_err(|e| MeierError::Protocol(format!("Serialization error: {}", e)))?;
+        Ok(Self::response_ok(Some(data)))
+    }
+
+    /// 에러 상태의 응답인지 여부
+    pub fn is_error_response(&self) -> bool {
+        matches!(self, Self::Response { status, .. } if *status != Status::Ok)
+    }
+
+    /// 성공 응답이면 그대로 반환하고 에러 응답이면 Err
+    pub fn into_result(self) -> Result<Self> {
+        match self {
+            Self::Response {
+                status: Status::Error(e),
+                ..
+            } => Err(MeierError::Protocol(e)),
+            Self::Response {
+                status:
+                    Status::NotLeaderForPartition {
+                        topic,
+                        partition_id,
+                        leader,
+                    },
+                ..
+            } => Err(MeierError::NotLeaderForPartition {
+                topic,
+                partition_id,
+                leader,
+            }),
+            Self::Response {
+                status: Status::NotAuthorized(e),
+                ..
+            } => Err(MeierError::NotAuthorized(e)),
+            Self::Response { .. } => Ok(self),
+            other => Err(MeierError::Protocol(format!(
+                "Unexpected response: {}",
+                other.name()
+            ))),
+        }
+    }
+
+    /// response_ok_json 으로 만든 응답에서 값 복원(에러 응답이면 Err)
+    pub fn into_json<T: DeserializeOwned>(self) -> Result<T> {
+        match self.into_result()? {
+            Self::Response {
+                data: Some(data), ..
+            } => serde_json::from_slice(&data)
+                .map_err(|e| MeierError::Protocol(format!("Deserialization error: {}", e))),
+            _ => Err(MeierError::Protocol("Response has no data".to_string())),
+        }
+    }
+
+    /// 쿼터 초과로 지연시킨 시간을 응답에 기록
+    pub fn with_throttle(mut self, throttle: Duration) -> Self {
+        if let Self::Response {
+            throttle_time_ms, ..
+        } = &mut self
+            && !throttle.is_zero()
+        {
+            *throttle_time_ms = Some(throttle.as_millis() as u64);
+        }
+        self
+    }
+
+    /// 브로커가 요청한 대기 시간(쿼터를 넘지 않았으면 None)
+    pub fn throttle_time(&self) -> Option<Duration> {
+        match self {
+            Self::Response {
+                throttle_time_ms: Some(ms),
+                ..
+            } => Some(Duration::from_millis(*ms)),
+            _ => None,
         }
     }
 
@@ -81,6 +452,8 @@ impl Frame {
             status: Status::ok(),
             data: Some(message.into_bytes()),
             message: None,
+            record: None,
+            throttle_time_ms: None,
         }
     }
 }
@@ -1,5 +1,17 @@
 pub mod codec;
 pub mod frame;
+pub mod response;
 
-pub use codec::MeierCodec;
-pub use frame::{Frame, Status};
+pub use codec::{Decoded, LenientCodec, MeierCodec};
+pub use frame::{
+    Acks, EARLIEST_TIMESTAMP, Frame, IsolationLevel, LATEST_TIMESTAMP, RecordMetadata, Status,
+    TopicPartition,
+};
+pub use response::{
+    AclBinding, AclFilter, AclOperation, AclPermission, BrokerInfo, ClusterResult, ConfigEntry,
+    ConfigSource, ConsumerLag, DeleteRecordsResult, ListOffsetsResult, MetadataEntry,
+    MetadataRecord, MetadataResult, OffsetFetchResult, PartitionInfo, PartitionMetadata,
+    PartitionReassignment, PatternType, RaftAppendResult, RaftVoteResult, ReassignmentStatus,
+    ReplicaFetchPartition, ReplicaFetchResult, ReplicaPartitionData, ReplicaRecord, ResourceType,
+    TopicAssignment, TopicMetadata,
+};
@@ -0,0 +1,376 @@
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+use crate::{
+    config::{BrokerId, TopicConfig},
+    storage::Message,
+};
+
+/// ListOffsets 응답
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct ListOffsetsResult {
+    pub topic: String,
+    pub partition_id: usize,
+    pub offset: usize,
+    /// 해당 오프셋 메시지의 기록 시각(ms), 로그 끝이면 None
+    pub timestamp: Option<u64>,
+}
+
+/// DescribePartitions 응답 항목
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct PartitionInfo {
+    pub partition_id: usize,
+    pub log_start_offset: usize,
+    pub log_end_offset: usize,
+    pub high_watermark: usize,
+    pub last_stable_offset: usize,
+    pub message_count: usize,
+    pub size_bytes: usize,
+    pub leader: BrokerId,
+    pub replicas: Vec<BrokerId>,
+    /// 리더를 따라잡고 있는 복제본
+    pub isr: Vec<BrokerId>,
+}
+
+/// Metadata 응답
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct MetadataResult {
+    pub topics: Vec<TopicMetadata>,
+    /// 파티션 리더의 주소를 찾기 위한 브로커 목록(단일 브로커면 비어 있음)
+    #[serde(default)]
+    pub brokers: Vec<BrokerInfo>,
+    #[serde(default)]
+    pub controller_id: Option<BrokerId>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct TopicMetadata {
+    pub name: String,
+    pub partitions: Vec<PartitionMetadata>,
+    pub config: BTreeMap<String, String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct PartitionMetadata {
+    pub partition_id: usize,
+    /// produce/consume 요청을 받을 브로커
+    pub leader: BrokerId,
+    pub replicas: Vec<BrokerId>,
+    pub isr: Vec<BrokerId>,
+}
+
+/// DeleteRecords 응답
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct DeleteRecordsResult {
+    pub topic: String,
+    pub partition_id: usize,
+    /// 삭제 후 로그 시작 오프셋
+    pub log_start_offset: usize,
+}
+
+/// DescribeConfigs 응답 항목
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct ConfigEntry {
+    pub name: String,
+    pub value: String,
+    pub source: ConfigSource,
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+pub enum ConfigSource {
+    /// 브로커 기본값
+    Default,
+    /// 토픽별 설정
+    Topic,
+}
+
+/// OffsetFetch 응답
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct OffsetFetchResult {
+    pub group: String,
+    pub topic: String,
+    pub partition_id: usize,
+    /// 커밋된 오프셋, 커밋한 적이 없으면 None
+    pub offset: Option<usize>,
+}
+
+/// DescribeConsumerLag 응답 항목
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct ConsumerLag {
+    pub group: String,
+    pub topic: String,
+    pub partition_id: usize,
+    pub committed_offset: usize,
+    pub log_end_offset: usize,
+    /// 아직 읽지 않은 메시지 수(log_end_offset - committed_offset)
+    pub lag: usize,
+    /// 읽지 않은 가장 오래된 메시지가 기록된 후 지난 시간(ms), 밀린 메시지가 없으면 0
+    pub time_lag_ms: u64,
+}
+
+/// ReplicaFetch 요청 항목
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct ReplicaFetchPartition {
+    pub topic: String,
+    pub partition_id: usize,
+    /// 팔로워가 다음에 기록할 오프셋
+    pub fetch_offset: usize,
+}
+
+/// ReplicaFetch 응답
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct ReplicaFetchResult {
+    pub partitions: Vec<ReplicaPartitionData>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct TopicAssignment {
+    pub name: String,
+    /// 토픽별로 지정된 설정
+    pub configs: BTreeMap<String, String>,
+    /// 파티션별 복제본(첫 번째가 최초 리더)
+    pub replicas: Vec<Vec<BrokerId>>,
+    /// 리더가 기본값에 configs 를 적용해 정한 설정(이전 버전 로그에는 없다)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub config: Option<TopicConfig>,
+}
+
+/// ReassignPartitions 요청 항목
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct PartitionReassignment {
+    pub topic: String,
+    pub partition_id: usize,
+    /// 옮긴 후의 복제본(첫 번째가 선호 리더)
+    pub replicas: Vec<BrokerId>,
+}
+
+/// ACL 을 적용할 리소스 종류
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, PartialOrd, Ord)]
+pub enum ResourceType {
+    Topic,
+    Group,
+    TransactionalId,
+    /// 클러스터 관리와 브로커 간 요청(이름은 CLUSTER_RESOURCE)
+    Cluster,
+}
+
+/// 리소스 이름을 비교하는 방식
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq, Eq, PartialOrd, Ord)]
+pub enum PatternType {
+    /// 이름이 같을 때("*" 는 모든 리소스)
+    #[default]
+    Literal,
+    /// 이름이 resource_name 으로 시작할 때
+    Prefixed,
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, PartialOrd, Ord)]
+pub enum AclOperation {
+    All,
+    Read,
+    Write,
+    Create,
+    Delete,
+    Alter,
+    /// Read, Write, Delete, Alter 를 허용하면 함께 허용된다
+    Describe,
+    /// 복제, 컨트롤러 요청 등 브로커 간 요청
+    ClusterAction,
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, PartialOrd, Ord)]
+pub enum AclPermission {
+    Allow,
+    /// 일치하는 Allow 보다 우선
+    Deny,
+}
+
+/// principal 이 리소스에 operation 을 할 수 있는지 정하는 규칙
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, PartialOrd, Ord)]
+pub struct AclBinding {
+    /// "User:<이름>", "User:*" 는 모든 사용자
+    pub principal: String,
+    pub resource_type: ResourceType,
+    pub resource_name: String,
+    #[serde(default)]
+    pub pattern_type: PatternType,
+    pub operation: AclOperation,
+    pub permission: AclPermission,
+}
+
+/// DescribeAcls / DeleteAcls 조건(None 인 항목은 모두 일치)
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+pub struct AclFilter {
+    #[serde(default)]
+    pub principal: Option<String>,
+    #[serde(default)]
+    pub resource_type: Option<ResourceType>,
+    #[serde(default)]
+    pub resource_name: Option<String>,
+    #[serde(default)]
+    pub pattern_type: Option<PatternType>,
+    #[serde(default)]
+    pub operation: Option<AclOperation>,
+    #[serde(default)]
+    pub permission: Option<AclPermission>,
+}
+
+impl AclFilter {
+    pub fn matches(&self, acl: &AclBinding) -> bool {
+        self.principal.as_ref().is_none_or(|p| *p == acl.principal)
+            && self.resource_type.is_none_or(|t| t == acl.resource_type)
+            && self
+                .resource_name
+                .as_ref()
+                .is_none_or(|n| *n == acl.resource_name)
+            && self.pattern_type.is_none_or(|t| t == acl.pattern_type)
+            && self.operation.is_none_or(|o| o == acl.operation)
+            && self.permission.is_none_or(|p| p == acl.permission)
+    }
+}
+
+/// ListReassignments 응답 항목
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct ReassignmentStatus {
+    pub topic: String,
+    pub partition_id: usize,
+    pub leader: BrokerId,
+    /// 기존 복제본과 새 복제본을 합친 현재 복제본
+    pub replicas: Vec<BrokerId>,
+    pub target: Vec<BrokerId>,
+    /// 아직 리더를 따라잡지 못했을 수 있는 새 복제본
+    pub adding: Vec<BrokerId>,
+    /// 완료 시 데이터가 삭제될 복제본
+    pub removing: Vec<BrokerId>,
+    /// 리더가 알려 준 ISR(리더에 연결할 수 없으면 비어 있음)
+    pub isr: Vec<BrokerId>,
+    pub throttle_bytes_per_sec: Option<u64>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct ReplicaPartitionData {
+    pub topic: String,
+    pub partition_id: usize,
+    pub log_start_offset: usize,
+    pub high_watermark: usize,
+    pub isr: Vec<BrokerId>,
+    pub records: Vec<ReplicaRecord>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct ReplicaRecord {
+    pub offset: usize,
+    pub message: Message,
+}
+
+/// 컨트롤러 메타데이터 로그 항목
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct MetadataEntry {
+    /// 항목을 추가한 컨트롤러 리더의 term
+    pub term: u64,
+    pub record: MetadataRecord,
+}
+
+/// 클러스터 메타데이터 변경(커밋되면 모든 브로커가 같은 순서로 적용)
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub enum MetadataRecord {
+    /// 새 컨트롤러 리더 선출(이전 term 의 항목을 커밋하기 위해 기록)
+    LeaderChange {
+        leader_id: BrokerId,
+    },
+    /// 브로커 등록(다시 등록하면 주소와 랙을 갱신)
+    RegisterBroker {
+        broker_id: BrokerId,
+        addr: String,
+        #[serde(default)]
+        rack: Option<String>,
+    },
+    /// 세션이 만료된 브로커(파티션 리더로 선택하지 않는다)
+    FenceBroker {
+        broker_id: BrokerId,
+    },
+    UnfenceBroker {
+        broker_id: BrokerId,
+    },
+    CreateTopic {
+        assignment: TopicAssignment,
+    },
+    DeleteTopic {
+        name: String,
+    },
+    AlterConfigs {
+        topic: String,
+        set: BTreeMap<String, String>,
+        delete: Vec<String>,
+    },
+    PartitionLeader {
+        topic: String,
+        partition_id: usize,
+        leader: BrokerId,
+    },
+    /// 복제본 재배치 시작(새 복제본이 따라잡을 때까지 기존 복제본과 함께 복제)
+    ReassignPartition {
+        topic: String,
+        partition_id: usize,
+        replicas: Vec<BrokerId>,
+        #[serde(default)]
+        throttle_bytes_per_sec: Option<u64>,
+    },
+    /// 재배치 완료(복제본 목록과 리더를 교체하고 빠진 복제본은 데이터 삭제)
+    CompleteReassignment {
+        topic: String,
+        partition_id: usize,
+        replicas: Vec<BrokerId>,
+        leader: BrokerId,
+    },
+    /// 브로커 제거 준비(새 복제본을 배치하지 않는다)
+    DecommissionBroker {
+        broker_id: BrokerId,
+    },
+    CreateAcls {
+        acls: Vec<AclBinding>,
+    },
+    /// 지울 ACL 을 조건이 아닌 규칙 그대로 기록
+    DeleteAcls {
+        acls: Vec<AclBinding>,
+    },
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct RaftVoteResult {
+    pub term: u64,
+    pub vote_granted: bool,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct RaftAppendResult {
+    pub term: u64,
+    pub success: bool,
+    /// 팔로워의 마지막 로그 인덱스(실패 시 리더가 다음 전송 위치를 정하는 데 사용)
+    pub last_log_index: u64,
+    /// 팔로워 브로커의 랙(리더가 브로커를 등록할 때 사용)
+    #[serde(default)]
+    pub rack: Option<String>,
+}
+
+/// DescribeCluster 응답
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct ClusterResult {
+    /// 현재 컨트롤러 리더(선거 중이면 None)
+    pub controller_id: Option<BrokerId>,
+    pub term: u64,
+    pub commit_index: u64,
+    pub brokers: Vec<BrokerInfo>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct BrokerInfo {
+    pub id: BrokerId,
+    pub addr: String,
+    #[serde(default)]
+    pub rack: Option<String>,
+    pub fenced: bool,
+    /// 파티션을 옮기는 중이거나 옮긴 브로커
+    #[serde(default)]
+    pub decommissioned: bool,
+}
@@ -0,0 +1,282 @@
+use std::{
+    collections::HashMap,
+    sync::Mutex,
+    time::{Duration, Instant},
+};
+
+use crate::{
+    acl::ANONYMOUS,
+    auth::Principal,
+    config::{QuotaConfig, QuotaLimits},
+};
+
+/// 한 번에 지연시키는 최대 시간(한도를 크게 넘어도 이 이상 기다리게 하지 않는다)
+pub const MAX_THROTTLE: Duration = Duration::from_secs(30);
+
+/// 한도만큼 쓰지 않은 시간이 쌓여 한꺼번에 보낼 수 있는 최대 시간
+const BURST: Duration = Duration::from_secs(1);
+
+/// 이 시간 동안 요청이 없는 클라이언트의 사용량은 삭제
+const IDLE_EXPIRY: Duration = Duration::from_secs(600);
+
+/// 사용량을 함께 계산하는 단위(principal 또는 client id 설정이 있으면 그 값만 사용)
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+struct QuotaEntity {
+    user: Option<String>,
+    client_id: Option<String>,
+}
+
+/// 초당 rate 만큼 채워지는 토큰 버킷
+///
+/// 한도를 넘으면 토큰이 음수가 되고, 다시 0 이 될 때까지의 시간만큼 지연시킨다.
+struct Bucket {
+    tokens: f64,
+    updated: Instant,
+}
+
+impl Bucket {
+    fn new(rate: u64, now: Instant) -> Self {
+        Self {
+            tokens: rate as f64 * BURST.as_secs_f64(),
+            updated: now,
+        }
+    }
+
+    fn consume(&mut self, rate: u64, amount: usize, now: Instant) -> Duration {
+        let rate = rate as f64;
+        let elapsed = now.duration_since(self.updated).as_secs_f64();
+        self.updated = now;
+        self.tokens = (self.tokens + elapsed * rate).min(rate * BURST.as_secs_f64());
+        self.tokens = (self.tokens - amount as f64).max(-rate * MAX_THROTTLE.as_secs_f64());
+
+        match self.tokens < 0.0 {
+            true => Duration::from_secs_f64(-self.tokens / rate),
+            false => Duration::ZERO,
+        }
+    }
+}
+
+struct Usage {
+    produce: Option<Bucket>,
+    fetch: Option<Bucket>,
+    requests: Option<Bucket>,
+    last_used: Instant,
+}
+
+/// principal / client id 별 처리량 제한
+///
+/// 요청을 처리한 뒤 사용량을 기록하고, 한도를 넘었으면 응답에 지연 시간을 알리고
+/// 그동안 연결의 다음 요청을 읽지 않는다.
+pub struct QuotaManager {
+    config: Mutex<QuotaConfig>,
+    usage: Mutex<HashMap<QuotaEntity, Usage>>,
+}
+
+impl QuotaManager {
+    pub fn new(config: QuotaConfig) -> Self {
+        Self {
+            config: Mutex::new(config),
+            usage: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// 한도 변경(기존 사용량은 유지)
+    pub fn set_config(&self, config: QuotaConfig) {
+        *self.config.lock().unwrap() = config;
+    }
+
+    /// 요청 하나의 사용량을 기록하고 한도를 넘었으면 지연시킬 시간 반환
+    ///
+    /// 여러 한도를 넘으면 가장 긴 지연 시간을 사용한다.
+    pub fn record(
+        &self,
+        principal: Option<&Principal>,
+        client_id: Option<&str>,
+        produce_bytes: usize,
+        fetch_bytes: usize,
+    ) -> Duration {
+        let (entity, limits) = self.resolve(principal, client_id);
+        if limits == QuotaLimits::default() {
+            return Duration::ZERO;
+        }
+
+        let now = Instant::now();
+        let mut usage = self.usage.lock().unwrap();
+        let usage = usage.entry(entity).or_insert_with(|| Usage {
+            produce: None,
+            fetch: None,
+            requests: None,
+            last_used: now,
+        });
+        usage.last_used = now;
+
+        [
+            consume(&mut usage.requests, limits.requests_per_sec, 1, now),
+            consume(
+                &mut usage.produce,
+                limits.produce_bytes_per_sec,
+                produce_bytes,
+                now,
+            ),
+            consume(
+                &mut usage.fetch,
+                limits.fetch_bytes_per_sec,
+                fetch_bytes,
+                now,
+            ),
+        ]
+        .into_iter()
+        .max()
+        .unwrap_or_default()
+    }
+
+    /// 오래 요청이 없는 클라이언트의 사용량 삭제
+    pub fn expire_idle(&self) {
+        let now = Instant::now();
+        self.usage
+            .lock()
+            .unwrap()
+            .retain(|_, usage| now.duration_since(usage.last_used) < IDLE_EXPIRY);
+    }
+
+    fn resolve(
+        &self,
+        principal: Option<&Principal>,
+        client_id: Option<&str>,
+    ) -> (QuotaEntity, QuotaLimits) {
+        let config = self.config.lock().unwrap();
+        let user = principal.map_or_else(|| ANONYMOUS.to_string(), Principal::to_string);
+        let user_limits = config.users.get(&user).copied();
+        let client_limits = client_id.and_then(|id| config.clients.get(id)).copied();
+
+        let limits = user_limits
+            .unwrap_or_default()
+            .or(client_limits.unwrap_or_default())
+            .or(config.default);
+        let entity = match (user_limits, client_limits) {
+            (Some(_), _) => QuotaEntity {
+                user: Some(user),
+                client_id: None,
+            },
+            (None, Some(_)) => QuotaEntity {
+                user: None,
+                client_id: client_id.map(str::to_string),
+            },
+            (None, None) => QuotaEntity {
+                user: Some(user),
+                client_id: client_id.map(str::to_string),
+            },
+        };
+        (entity, limits)
+    }
+}
+
+/// 한도가 있는 항목만 버킷에서 amount 를 뺀다
+fn consume(
+    bucket: &mut Option<Bucket>,
+    rate: Option<u64>,
+    amount: usize,
+    now: Instant,
+) -> Duration {
+    match rate {
+        Some(rate) if amount > 0 => bucket
+            .get_or_insert_with(|| Bucket::new(rate, now))
+            .consume(rate, amount, now),
+        _ => Duration::ZERO,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::SaslMechanism;
+
+    fn limits(produce_bytes_per_sec: u64) -> QuotaLimits {
+        QuotaLimits {
+            produce_bytes_per_sec: Some(produce_bytes_per_sec),
+            ..Default::default()
+        }
+    }
+
+    fn user(name: &str) -> Principal {
+        Principal {
+            name: name.to_string(),
+            mechanism: SaslMechanism::Plain,
+        }
+    }
+
+    #[test]
+    fn bucket_allows_one_second_burst() {
+        let start = Instant::now();
+        let mut bucket = Bucket::new(1000, start);
+
+        assert_eq!(bucket.consume(1000, 1000, start), Duration::ZERO);
+        assert_eq!(bucket.consume(1000, 500, start), Duration::from_millis(500));
+        let later = start + Duration::from_secs(1);
+        assert_eq!(bucket.consume(1000, 500, later), Duration::ZERO);
+
+        // 오래 쉬어도 BURST 이상은 쌓이지 않는다
+        let idle = later + Duration::from_secs(10);
+        assert_eq!(bucket.consume(1000, 1500, idle), Duration::from_millis(500));
+    }
+
+    #[test]
+    fn throttle_is_clamped_to_the_maximum() {
+        let start = Instant::now();
+        let mut bucket = Bucket::new(1000, start);
+
+        assert_eq!(bucket.consume(1000, 1_000_000, start), MAX_THROTTLE);
+        let later = start + Duration::from_secs(1);
+        assert_eq!(
+            bucket.consume(1000, 0, later),
+            MAX_THROTTLE - Duration::from_secs(1)
+        );
+    }
+
+    #[test]
+    fn entity_follows_the_most_specific_configured_limit() {
+        let manager = QuotaManager::new(QuotaConfig {
+            default: QuotaLimits {
+                requests_per_sec: Some(10),
+                ..Default::default()
+            },
+            users: [("User:alice".to_string(), limits(100))].into(),
+            clients: [("reporting".to_string(), limits(200))].into(),
+        });
+
+        let (entity, resolved) = manager.resolve(Some(&user("alice")), Some("reporting"));
+        assert_eq!(entity.user.as_deref(), Some("User:alice"));
+        assert_eq!(entity.client_id, None);
+        assert_eq!(resolved.produce_bytes_per_sec, Some(100));
+        assert_eq!(resolved.requests_per_sec, Some(10));
+
+        let (entity, resolved) = manager.resolve(Some(&user("bob")), Some("reporting"));
+        assert_eq!(entity.user, None);
+        assert_eq!(entity.client_id.as_deref(), Some("reporting"));
+        assert_eq!(resolved.produce_bytes_per_sec, Some(200));
+
+        let (entity, resolved) = manager.resolve(None, Some("other"));
+        assert_eq!(entity.user.as_deref(), Some(ANONYMOUS));
+        assert_eq!(entity.client_id.as_deref(), Some("other"));
+        assert_eq!(resolved, manager.config.lock().unwrap().default);
+    }
+
+    #[test]
+    fn user_quota_is_shared_across_client_ids() {
+        let manager = QuotaManager::new(QuotaConfig {
+            users: [("User:alice".to_string(), limits(1000))].into(),
+            ..Default::default()
+        });
+        let alice = user("alice");
+
+        assert_eq!(
+            manager.record(Some(&alice), Some("a"), 1000, 0),
+            Duration::ZERO
+        );
+        assert!(manager.record(Some(&alice), Some("b"), 1000, 0) > Duration::ZERO);
+        assert_eq!(
+            manager.record(Some(&user("bob")), Some("a"), 1_000_000, 0),
+            Duration::ZERO
+        );
+    }
+}
@@ -0,0 +1,154 @@
+use futures::{SinkExt, StreamExt};
+use std::{
+    sync::Arc,
+    time::{Duration, Instant},
+};
+use tokio_util::{codec::Framed, sync::CancellationToken};
+use tracing::{info, warn};
+
+use crate::{
+    Frame, MeierCodec, MeierError, Result,
+    config::{BrokerId, PeerConfig},
+    protocol::{ReplicaFetchPartition, ReplicaFetchResult, ReplicaPartitionData},
+    storage::TopicManager,
+    tls::Connector,
+};
+
+/// 연결이 끊겼을 때 다시 연결하기까지 기다리는 시간
+const RECONNECT_BACKOFF: Duration = Duration::from_secs(1);
+
+/// peer 가 리더인 파티션 복제
+pub async fn run_fetcher(
+    peer: PeerConfig,
+    topic_manager: Arc<TopicManager>,
+    connector: Connector,
+    max_frame_bytes: usize,
+    shutdown: CancellationToken,
+) {
+    loop {
+        tokio::select! {
+            result = fetch_from(&peer, &topic_manager, &connector, max_frame_bytes) => {
+                if let Err(e) = result {
+                    warn!("Replication from broker {} ({}) failed: {}", peer.id, peer.addr, e);
+                }
+            }
+            _ = shutdown.cancelled() => break,
+        }
+
+        tokio::select! {
+            _ = tokio::time::sleep(RECONNECT_BACKOFF) => {}
+            _ = shutdown.cancelled() => break,
+        }
+    }
+}
+
+async fn fetch_from(
+    peer: &PeerConfig,
+    topic_manager: &TopicManager,
+    connector: &Connector,
+    max_frame_bytes: usize,
+) -> Result<()> {
+    let stream = connector.connect(&peer.addr).await?;
+    let mut framed = Framed::new(stream, MeierCodec::with_max_length(max_frame_bytes));
+    info!("Replicating from broker {} ({})", peer.id, peer.addr);
+
+    let cluster = topic_manager.cluster();
+    let interval = Duration::from_millis(cluster.replica_fetch_interval_ms);
+    // 재배치로 추가된 파티션은 가져온 양에 따라 이 시각까지 요청하지 않는다
+    let mut throttled_until = Instant::now();
+
+    loop {
+        let include_throttled = Instant::now() >= throttled_until;
+        let request = Frame::ReplicaFetch {
+            replica_id: cluster.broker_id,
+            partitions: followed_partitions(topic_manager, peer.id, include_throttled).await,
+        };
+        framed.send(request).await?;
+
+        let response = framed.next().await.ok_or_else(|| {
+            MeierError::Protocol(format!("Connection to broker {} closed", peer.id))
+        })??;
+        let result: ReplicaFetchResult = response.into_json()?;
+
+        // 파티션 하나의 오류로 다른 파티션의 복제가 멈추지 않도록 기록만 하고 넘어간다
+        let mut fetched = 0;
+        for data in result.partitions {
+            let (topic, partition_id) = (data.topic.clone(), data.partition_id);
+            match apply_fetched(topic_manager, data, &mut throttled_until).await {
+                Ok(count) => fetched += count,
+                Err(e) => warn!(
+                    "Failed to replicate partition {}-{} from broker {}: {}",
+                    topic, partition_id, peer.id, e
+                ),
+            }
+        }
+
+        // 가져온 메시지가 없으면 잠시 쉬었다가 다시 요청
+        if fetched == 0 {
+            tokio::time::sleep(interval).await;
+        }
+    }
+}
+
+/// 리더에서 가져온 파티션 데이터를 기록하고 기록한 메시지 수 반환
+async fn apply_fetched(
+    topic_manager: &TopicManager,
+    data: ReplicaPartitionData,
+    throttled_until: &mut Instant,
+) -> Result<usize> {
+    let partition = topic_manager
+        .get_partition(&data.topic, data.partition_id)
+        .await?;
+
+    if let Some(rate) = partition.replica_state().await.reassignment_throttle() {
+        let bytes: usize = data.records.iter().map(|r| r.message.size()).sum();
+        let delay = Duration::from_secs_f64(bytes as f64 / rate.max(1) as f64);
+        *throttled_until = (*throttled_until).max(Instant::now()) + delay;
+    }
+
+    let fetched = data.records.len();
+    partition
+        .append_replica(
+            data.log_start_offset,
+            data.records,
+            data.high_watermark,
+            data.isr,
+        )
+        .await?;
+    Ok(fetched)
+}
+
+/// leader 가 리더이고 이 브로커가 팔로워인 파티션과 다음에 기록할 오프셋
+///
+/// include_throttled 가 false 면 속도 제한이 걸린 재배치 파티션은 제외한다.
+async fn followed_partitions(
+    topic_manager: &TopicManager,
+    leader: BrokerId,
+    include_throttled: bool,
+) -> Vec<ReplicaFetchPartition> {
+    let local_id = topic_manager.cluster().broker_id;
+    let mut names = topic_manager.list_topics().await;
+    names.sort();
+
+    let mut partitions = Vec::new();
+    for name in names {
+        let Some(topic) = topic_manager.get_topic(&name).await else {
+            continue;
+        };
+
+        for (partition_id, partition) in topic.partitions() {
+            let replica = partition.replica_state().await;
+            if !include_throttled && replica.reassignment_throttle().is_some() {
+                continue;
+            }
+            if replica.leader() == leader && replica.replicas().contains(&local_id) {
+                partitions.push(ReplicaFetchPartition {
+                    topic: name.clone(),
+                    partition_id,
+                    fetch_offset: partition.log_end_offset().await,
+                });
+            }
+        }
+    }
+    partitions
+}
@@ -1,86 +1,593 @@
 use futures::{SinkExt, StreamExt};
-use std::sync::Arc;
-use tokio::net::{TcpListener, TcpStream};
-use tokio_util::codec::{FramedRead, FramedWrite};
-use tracing::{error, info};
+use std::{
+    sync::{
+        Arc,
+        atomic::{AtomicU64, Ordering},
+    },
+    time::Duration,
+};
+use tokio::{
+    net::{TcpListener, TcpStream},
+    sync::RwLock,
+    task::JoinSet,
+};
+use tokio_util::{
+    codec::{FramedRead, FramedWrite},
+    sync::CancellationToken,
+};
+use tracing::{Instrument, debug, error, info, info_span, warn};
 
 use crate::{
     Config, Frame, MeierCodec, MeierError, Result,
-    handler::{handle_consume, handle_consume_next, handle_produce},
-    protocol,
-    storage::TopicManager,
+    acl::{self, Authorizer},
+    auth::{Authenticator, Principal, Session},
+    config::ConfigChanges,
+    connection::ConnectionTracker,
+    controller::Controller,
+    handler::{
+        handle_add_partitions_to_txn, handle_alter_configs, handle_begin_txn, handle_consume,
+        handle_consume_next, handle_create_acls, handle_create_topic, handle_decommission_broker,
+        handle_delete_acls, handle_delete_records, handle_delete_topic, handle_describe_acls,
+        handle_describe_cluster, handle_describe_configs, handle_describe_consumer_lag,
+        handle_describe_partitions, handle_end_txn, handle_list_offsets, handle_list_reassignments,
+        handle_metadata, handle_offset_commit, handle_offset_fetch, handle_produce,
+        handle_raft_append, handle_raft_vote, handle_reassign_partitions, handle_replica_fetch,
+    },
+    metrics,
+    protocol::{self, Acks, AclOperation, Decoded, ResourceType},
+    quota::QuotaManager,
+    replication,
+    storage::{ControlType, Message, TopicManager},
+    tls::{Acceptor, Connector, Stream},
 };
 
+/// 보존 기간 적용 및 ISR 확인 주기
+const MAINTENANCE_INTERVAL: Duration = Duration::from_secs(1);
+
+/// client id 최대 길이
+const MAX_CLIENT_ID_LEN: usize = 255;
+
+/// TLS 핸드셰이크 최대 시간
+const TLS_HANDSHAKE_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// 응답 시간이 지난 뒤에도 처리 중인 요청을 연결마다 이 수까지만 허용
+const MAX_PENDING_HANDLERS: usize = 4;
+
+/// 한도 초과 연결에 에러를 보내는 태스크 최대 수(넘으면 응답 없이 닫는다)
+const MAX_PENDING_REJECTS: usize = 16;
+
 pub struct Server {
-    config: Config,
+    config: Arc<RwLock<Config>>,
+    topic_manager: Arc<TopicManager>,
+    /// 클러스터 메타데이터 컨트롤러(단일 브로커면 None)
+    controller: Option<Arc<Controller>>,
+    connections: Arc<ConnectionTracker>,
+    /// 리스너 TLS(설정이 없으면 평문)
+    acceptor: Acceptor,
+    /// 클라이언트 SASL 인증(설정이 없으면 인증 없이 처리)
+    authenticator: Authenticator,
+    /// ACL 저장과 요청 인가(설정이 없으면 모두 허용)
+    authorizer: Arc<Authorizer>,
+    /// 클라이언트별 처리량 제한
+    quotas: Arc<QuotaManager>,
+    /// 다른 브로커로의 연결(복제, 컨트롤러)
+    connector: Connector,
+    /// 로그에서 연결을 구분하기 위한 ID
+    next_connection_id: AtomicU64,
+    shutdown: CancellationToken,
+}
+
+/// 연결 태스크가 함께 쓰는 브로커 상태
+struct ConnectionContext {
     topic_manager: Arc<TopicManager>,
+    controller: Option<Arc<Controller>>,
+    authorizer: Arc<Authorizer>,
+    quotas: Arc<QuotaManager>,
+    config: Arc<RwLock<Config>>,
+    shutdown: CancellationToken,
+}
+
+/// 실행 중인 서버를 종료시키는 핸들
+#[derive(Clone)]
+pub struct ShutdownHandle {
+    token: CancellationToken,
+}
+
+impl ShutdownHandle {
+    /// 새 연결 수락을 중단하고 처리 중인 요청이 끝나면 `Server::run` 을 반환시킨다.
+    pub fn shutdown(&self) {
+        self.token.cancel();
+    }
 }
 
 impl Server {
-    pub fn new(config: Config) -> Self {
+    pub fn new(config: Config) -> Result<Self> {
         let topic_manager = Arc::new(TopicManager::new(
             config.storage.max_topics,
             config.storage.max_messages_per_partition,
             config.storage.max_message_size_bytes,
+            config.storage.topic_defaults(),
+            config.cluster.clone(),
         ));
 
-        Self {
-            config,
+        let acceptor = Acceptor::new(config.server.tls.as_ref())?;
+        let authenticator = Authenticator::new(config.server.sasl.as_ref())?;
+        let connector = Connector::new(
+            config
+                .server
+                .tls
+                .as_ref()
+                .and_then(|tls| tls.peer_client_config())
+                .as_ref(),
+            config
+                .server
+                .sasl
+                .as_ref()
+                .and_then(|sasl| sasl.inter_broker.as_ref()),
+        )?;
+
+        // 클러스터 모드의 ACL 은 메타데이터 로그에서 다시 적용된다
+        let standalone = config.cluster.peers.is_empty();
+        let authorizer = Arc::new(Authorizer::new(
+            config.server.acl.as_ref(),
+            standalone.then(|| config.storage.data_dir.join(acl::ACL_FILE)),
+        )?);
+
+        let controller = match standalone {
+            true => None,
+            false => Some(Arc::new(Controller::open(
+                &config,
+                topic_manager.clone(),
+                authorizer.clone(),
+                connector.clone(),
+            )?)),
+        };
+
+        let connections = Arc::new(ConnectionTracker::new(
+            config.server.max_connections,
+            config.server.max_connections_per_ip,
+        ));
+        let quotas = Arc::new(QuotaManager::new(config.server.quotas.clone()));
+
+        Ok(Self {
+            config: Arc::new(RwLock::new(config)),
             topic_manager,
+            controller,
+            connections,
+            acceptor,
+            authenticator,
+            authorizer,
+            quotas,
+            connector,
+            next_connection_id: AtomicU64::new(0),
+            shutdown: CancellationToken::new(),
+        })
+    }
+
+    pub fn shutdown_handle(&self) -> ShutdownHandle {
+        ShutdownHandle {
+            token: self.shutdown.clone(),
         }
     }
 
+    /// 변경된 설정을 실행 중인 서버에 적용하고 변경 내역 반환
+    ///
+    /// 재시작이 필요한 항목은 새 값이 저장되지만 다시 시작하기 전까지 적용되지 않는다.
+    pub async fn reload_config(&self, new: Config) -> Result<ConfigChanges> {
+        new.validate()?;
+
+        let mut config = self.config.write().await;
+        let changes = config.changes(&new);
+
+        // 실패할 수 있는 확인을 모두 마친 뒤에 적용한다
+        let topic_defaults = new.storage.topic_defaults();
+        self.topic_manager
+            .check_topic_defaults(&topic_defaults)
+            .await?;
+
+        self.connections.set_limits(
+            new.server.max_connections,
+            new.server.max_connections_per_ip,
+        );
+        self.topic_manager
+            .set_limits(
+                new.storage.max_topics,
+                new.storage.max_messages_per_partition,
+                new.storage.max_message_size_bytes,
+            )
+            .await;
+        self.topic_manager.set_topic_defaults(topic_defaults).await;
+        self.quotas.set_config(new.server.quotas.clone());
+
+        *config = new;
+        Ok(changes)
+    }
+
     pub async fn run(&self) -> Result<()> {
-        let addr = &self.config.server.bind_addr;
-        let listener = TcpListener::bind(addr)
-            .await
-            .map_err(|e| MeierError::Io(e))?;
+        let (addr, metrics_config, max_frame_bytes) = {
+            let config = self.config.read().await;
+            (
+                config.server.bind_addr.clone(),
+                config.metrics.clone(),
+                config.server.max_frame_bytes,
+            )
+        };
+        let listener = TcpListener::bind(&addr).await.map_err(MeierError::Io)?;
+        if self.acceptor.is_tls() {
+            info!("TLS enabled on {}", addr);
+        }
+        if self.authenticator.is_enabled() {
+            info!("SASL authentication required on {}", addr);
+        }
+        if self.authorizer.is_enabled() {
+            info!("ACL authorization enabled");
+        }
+
+        if metrics_config.enabled {
+            let metrics_listener = TcpListener::bind(&metrics_config.bind_addr)
+                .await
+                .map_err(MeierError::Io)?;
+            info!(
+                "Serving metrics on http://{}{}",
+                metrics_config.bind_addr, metrics_config.path
+            );
+            tokio::spawn(metrics::serve(
+                metrics_listener,
+                metrics_config.path,
+                self.topic_manager.clone(),
+                self.connections.clone(),
+                self.shutdown.clone(),
+            ));
+        }
+
+        // 보존 기간이 지난 메시지와 시간이 지난 트랜잭션 정리 및 따라오지 못하는 팔로워를
+        // ISR 에서 제외
+        tokio::spawn({
+            let topic_manager = self.topic_manager.clone();
+            let quotas = self.quotas.clone();
+            let config = self.config.clone();
+            let shutdown = self.shutdown.clone();
+            async move {
+                let mut interval = tokio::time::interval(MAINTENANCE_INTERVAL);
+                loop {
+                    tokio::select! {
+                        _ = interval.tick() => {}
+                        _ = shutdown.cancelled() => break,
+                    }
+                    if let Err(e) = topic_manager.enforce_retention().await {
+                        error!("Retention error: {}", e);
+                    }
+                    let transaction_timeout =
+                        Duration::from_millis(config.read().await.storage.transaction_timeout_ms);
+                    topic_manager.expire_transactions(transaction_timeout).await;
+                    topic_manager.check_replicas().await;
+                    quotas.expire_idle();
+                }
+            }
+        });
+
+        if let Some(controller) = &self.controller {
+            controller.spawn(self.shutdown.clone());
+        }
+
+        // 다른 브로커가 리더인 파티션 복제
+        for peer in self.topic_manager.cluster().peers.clone() {
+            tokio::spawn(replication::run_fetcher(
+                peer,
+                self.topic_manager.clone(),
+                self.connector.clone(),
+                max_frame_bytes,
+                self.shutdown.clone(),
+            ));
+        }
+
+        let mut connections = JoinSet::new();
+        // 한도를 넘은 연결에 에러를 보내는 태스크(TLS 핸드셰이크를 포함하므로 수를 제한한다)
+        let mut rejects = JoinSet::new();
 
         loop {
-            match listener.accept().await {
-                Ok((stream, peer_addr)) => {
-                    info!("New Connection from {}", peer_addr);
+            tokio::select! {
+                result = listener.accept() => match result {
+                    Ok((stream, peer_addr)) => {
+                        let guard = match self.connections.acquire(peer_addr.ip()) {
+                            Ok(guard) => guard,
+                            Err(e) => {
+                                warn!("Rejecting connection from {}: {}", peer_addr, e);
+                                self.topic_manager.metrics().record_connection_rejected();
+                                while rejects.try_join_next().is_some() {}
+                                if rejects.len() < MAX_PENDING_REJECTS {
+                                    rejects.spawn(Self::reject_connection(
+                                        self.acceptor.clone(),
+                                        stream,
+                                        e,
+                                    ));
+                                }
+                                continue;
+                            }
+                        };
 
-                    let topic_manager = self.topic_manager.clone();
-                    let codec = MeierCodec::new();
+                        self.topic_manager.metrics().record_connection_accepted();
 
-                    tokio::spawn(async move {
-                        if let Err(e) = Self::handle_connection(stream, topic_manager, codec).await
-                        {
-                            error!("Connection error: {}", e);
-                        }
-                    });
-                }
-                Err(e) => {
-                    error!("Failed to accept connection: {}", e);
-                }
+                        // 연결 태스크의 모든 로그에 연결 ID 와 주소를 남긴다
+                        let connection_id = self.next_connection_id.fetch_add(1, Ordering::Relaxed);
+                        let span = info_span!("connection", id = connection_id, peer = %peer_addr);
+                        span.in_scope(|| {
+                            info!(
+                                "New Connection from {} ({} active)",
+                                peer_addr,
+                                self.connections.total()
+                            )
+                        });
+
+                        let context = ConnectionContext {
+                            topic_manager: self.topic_manager.clone(),
+                            controller: self.controller.clone(),
+                            authorizer: self.authorizer.clone(),
+                            quotas: self.quotas.clone(),
+                            config: self.config.clone(),
+                            shutdown: self.shutdown.clone(),
+                        };
+                        let acceptor = self.acceptor.clone();
+                        let session = self.authenticator.session();
+
+                        connections.spawn(async move {
+                            // 연결이 끝나면 guard 가 drop 되며 연결 수 감소
+                            let _guard = guard;
+                            let stream = match Self::handshake(&acceptor, stream).await {
+                                Ok(stream) => stream,
+                                Err(e) => {
+                                    warn!("Closing connection: {}", e);
+                                    return;
+                                }
+                            };
+                            if let Err(e) = Self::handle_connection(stream, session, context).await {
+                                error!("Connection error: {}", e);
+                            }
+                        }.instrument(span));
+                    }
+                    Err(e) => {
+                        error!("Failed to accept connection: {}", e);
+                    }
+                },
+
+                // 종료된 연결 태스크 정리
+                Some(_) = connections.join_next(), if !connections.is_empty() => {}
+
+                _ = self.shutdown.cancelled() => break,
+            }
+        }
+
+        // 새 연결 수락 중단
+        drop(listener);
+        rejects.shutdown().await;
+
+        let timeout = Duration::from_secs(self.config.read().await.server.shutdown_timeout_secs);
+        info!(
+            "Shutting down: waiting up to {:?} for {} connection(s)",
+            timeout,
+            connections.len()
+        );
+
+        let drain = async { while connections.join_next().await.is_some() {} };
+        if tokio::time::timeout(timeout, drain).await.is_err() {
+            warn!(
+                "Shutdown timeout elapsed, closing {} connection(s)",
+                connections.len()
+            );
+            connections.shutdown().await;
+        }
+
+        info!("Server stopped");
+        Ok(())
+    }
+
+    async fn handshake(acceptor: &Acceptor, stream: TcpStream) -> Result<Stream> {
+        tokio::time::timeout(TLS_HANDSHAKE_TIMEOUT, acceptor.accept(stream))
+            .await
+            .map_err(|_| MeierError::Tls("Handshake timed out".to_string()))?
+    }
+
+    /// 연결 한도 초과 시 에러 응답을 보내고 연결 종료
+    async fn reject_connection(acceptor: Acceptor, stream: TcpStream, reason: MeierError) {
+        let stream = match Self::handshake(&acceptor, stream).await {
+            Ok(stream) => stream,
+            Err(e) => {
+                warn!("Failed to reject connection: {}", e);
+                return;
             }
+        };
+        let mut writer = FramedWrite::new(stream, MeierCodec::default());
+        if let Err(e) = writer.send(Frame::response_error(reason.to_string())).await {
+            error!("Failed to send response: {}", e);
         }
     }
+
     async fn handle_connection(
-        stream: TcpStream,
-        topic_manager: Arc<TopicManager>,
-        codec: MeierCodec,
+        stream: Stream,
+        mut session: Session,
+        context: ConnectionContext,
     ) -> Result<()> {
-        let (read_half, write_half) = stream.into_split();
+        let ConnectionContext {
+            topic_manager,
+            controller,
+            authorizer,
+            quotas,
+            config,
+            shutdown,
+        } = context;
+        // SetClientId 로 지정한 이름(쿼터 적용에 사용)
+        let mut client_id = None;
+        let (read_half, write_half) = tokio::io::split(stream);
+        let codec = MeierCodec::with_max_length(config.read().await.server.max_frame_bytes);
 
-        let mut reader = FramedRead::new(read_half, codec.clone());
+        let mut reader = FramedRead::new(read_half, codec.clone().lenient());
         let mut writer = FramedWrite::new(write_half, codec);
+        // 이 연결의 요청 핸들러(연결 태스크가 중단되면 함께 중단된다)
+        let mut handlers = JoinSet::new();
 
         loop {
+            // 설정이 다시 로드될 수 있으므로 매 요청마다 읽는다
+            let (idle_timeout, request_timeout) = {
+                let config = config.read().await;
+                let idle_timeout = match config.server.idle_timeout_secs {
+                    0 => None,
+                    secs => Some(Duration::from_secs(secs)),
+                };
+                (
+                    idle_timeout,
+                    Duration::from_secs(config.server.request_timeout_secs),
+                )
+            };
+
+            // idle_timeout 동안 요청이 없으면 연결 종료
+            let idle = async {
+                match idle_timeout {
+                    Some(timeout) => tokio::time::sleep(timeout).await,
+                    None => std::future::pending().await,
+                }
+            };
+
             tokio::select! {
                 result = reader.next() => {
                     match result {
-                        Some(Ok(frame)) => {
-                            let response = Self::process_frame(frame, &topic_manager).await;
+                        Some(Ok(Decoded::Frame(frame))) => {
+                            // acks=0 이면 응답하지 않는다
+                            let no_response = matches!(
+                                frame,
+                                Frame::Produce {
+                                    acks: Acks::None,
+                                    ..
+                                }
+                            );
+                            // 쿼터는 클라이언트 요청에만 적용(연결 설정, 브로커 간 요청 제외)
+                            let metered = !matches!(
+                                frame,
+                                Frame::SaslHandshake { .. }
+                                    | Frame::SaslAuthenticate { .. }
+                                    | Frame::SetClientId { .. }
+                                    | Frame::ReplicaFetch { .. }
+                                    | Frame::RaftVote { .. }
+                                    | Frame::RaftAppend { .. }
+                            );
+                            let fetch = matches!(frame, Frame::Consume { .. } | Frame::ConsumeNext { .. });
+                            let produce_bytes = match &frame {
+                                Frame::Produce { message, .. } => message.len(),
+                                _ => 0,
+                            };
+                            let response = match frame {
+                                Frame::SaslHandshake { mechanism } => session
+                                    .handshake(&mechanism)
+                                    .unwrap_or_else(Frame::response_from_error),
+                                Frame::SaslAuthenticate { auth_bytes } => {
+                                    match session.authenticate(&auth_bytes) {
+                                        Ok(response) => {
+                                            if let Some(principal) = session.principal() {
+                                                info!("Authenticated as {} ({})", principal, principal.mechanism.name());
+                                            }
+                                            response
+                                        }
+                                        // 인증에 실패하면 사유를 알리고 연결 종료
+                                        Err(e) => {
+                                            warn!("{}", e);
+                                            topic_manager.metrics().record_authentication_failure();
+                                            if let Err(e) = writer.send(Frame::response_from_error(e)).await {
+                                                error!("Failed to send response: {}", e);
+                                            }
+                                            break;
+                                        }
+                                    }
+                                }
+                                Frame::SetClientId { client_id: id } => match validate_client_id(&id) {
+                                    Ok(()) => {
+                                        info!("Client id: {}", id);
+                                        client_id = Some(id);
+                                        Frame::response_ok(None)
+                                    }
+                                    Err(e) => Frame::response_from_error(e),
+                                },
+                                // 인증 전에는 SASL 요청만 받는다
+                                frame if !session.is_authenticated() => {
+                                    Frame::response_from_error(MeierError::Authentication(format!(
+                                        "{} requires an authenticated connection",
+                                        frame.name()
+                                    )))
+                                }
+                                frame => {
+                                    Self::run_frame(
+                                        frame,
+                                        &topic_manager,
+                                        &controller,
+                                        &authorizer,
+                                        session.principal().cloned(),
+                                        request_timeout,
+                                        &mut handlers,
+                                    )
+                                    .await
+                                }
+                            };
+
+                            let throttle = match metered {
+                                true => {
+                                    let fetch_bytes = match (fetch, &response) {
+                                        (true, Frame::Response { data: Some(data), .. }) => data.len(),
+                                        _ => 0,
+                                    };
+                                    quotas.record(
+                                        session.principal(),
+                                        client_id.as_deref(),
+                                        produce_bytes,
+                                        fetch_bytes,
+                                    )
+                                }
+                                false => Duration::ZERO,
+                            };
 
-                            if let Err(e) = writer.send(response).await {
+                            if !no_response
+                                && let Err(e) = writer.send(response.with_throttle(throttle)).await
+                            {
                                 error!("Failed to send response: {}", e);
                             }
+
+                            // 쿼터를 넘었으면 지연 시간 동안 다음 요청을 읽지 않는다
+                            if !throttle.is_zero() {
+                                debug!("Throttling connection for {:?}", throttle);
+                                topic_manager.metrics().record_throttle(throttle);
+                                tokio::select! {
+                                    _ = tokio::time::sleep(throttle) => {}
+                                    _ = shutdown.cancelled() => {
+                                        info!("Closing connection for shutdown");
+                                        break;
+                                    }
+                                }
+                            }
                         }
 
+                        // 프레임 경계는 유지되므로 에러 응답 후 계속 처리
+                        Some(Ok(Decoded::Invalid(e))) => {
+                            warn!("Invalid frame: {}", e);
+                            topic_manager.metrics().record_invalid_frame();
+
+                            if let Err(e) = writer.send(Frame::response_error(e.to_string())).await {
+                                error!("Failed to send response: {}", e);
+                            }
+                        }
+
+                        // TLS close_notify 없이 끊은 클라이언트도 정상 종료로 취급
+                        Some(Err(MeierError::Io(e))) if e.kind() == std::io::ErrorKind::UnexpectedEof => {
+                            info!("Connection closed by client");
+                            break;
+                        }
+
+                        // 프레임 경계를 알 수 없으므로 사유를 알리고 연결 종료
                         Some(Err(e)) => {
-                            error!("Frame decode error: {}", e);
+                            error!("Frame decode error, closing connection: {}", e);
+                            topic_manager.metrics().record_invalid_frame();
+
+                            let reason = format!("Closing connection: {}", e);
+                            if let Err(e) = writer.send(Frame::response_error(reason)).await {
+                                error!("Failed to send response: {}", e);
+                            }
+                            break;
                         }
 
                         None => {
@@ -89,55 +596,496 @@ impl Server {
                         }
                     }
                 }
+
+                _ = idle => {
+                    info!("Closing idle connection");
+                    break;
+                }
+
+                // 처리 중인 요청이 없을 때만 종료
+                _ = shutdown.cancelled() => {
+                    info!("Closing connection for shutdown");
+                    break;
+                }
             }
         }
+
+        // 시간이 지나 응답하지 못한 요청도 끝까지 처리한 뒤 연결을 정리한다
+        if !handlers.is_empty() {
+            debug!("Waiting for {} pending request(s)", handlers.len());
+            while handlers.join_next().await.is_some() {}
+        }
         Ok(())
     }
 
-    async fn process_frame(frame: Frame, topic_manager: &TopicManager) -> Frame {
-        match frame {
-            Frame::Produce { topic, message } => {
-                match handle_produce(topic_manager, topic, message).await {
-                    Ok(response) => response,
-                    Err(e) => Frame::Response {
-                        status: protocol::Status::error(e.to_string()),
-                        data: None,
-                        message: Some(e.to_string()),
-                    },
-                }
+    /// 요청을 별도 태스크에서 끝까지 처리하고 request_timeout 동안 응답을 기다린다
+    ///
+    /// 핸들러는 버퍼 사용량 기록, 트랜잭션 마커, 메타데이터 제안처럼 여러 단계로 상태를
+    /// 바꾸므로 중간에 취소하지 않는다. 시간이 지나면 클라이언트에는 실패를 알리지만
+    /// 요청은 계속 처리되어 반영될 수 있다.
+    async fn run_frame(
+        frame: Frame,
+        topic_manager: &Arc<TopicManager>,
+        controller: &Option<Arc<Controller>>,
+        authorizer: &Arc<Authorizer>,
+        principal: Option<Principal>,
+        request_timeout: Duration,
+        handlers: &mut JoinSet<Frame>,
+    ) -> Frame {
+        let task = {
+            let topic_manager = topic_manager.clone();
+            let controller = controller.clone();
+            let authorizer = authorizer.clone();
+            async move {
+                Self::process_frame(
+                    frame,
+                    &topic_manager,
+                    controller.as_deref(),
+                    &authorizer,
+                    principal.as_ref(),
+                )
+                .await
+            }
+        };
+
+        match run_handler(handlers, task, request_timeout).await {
+            Ok(response) => response,
+            Err(HandlerError::Busy) => Frame::response_error(format!(
+                "Too many timed out requests are still running on this connection (max {})",
+                MAX_PENDING_HANDLERS
+            )),
+            Err(HandlerError::Failed(e)) => {
+                error!("Request handler failed: {}", e);
+                Frame::response_error("Request handler failed".to_string())
+            }
+            Err(HandlerError::TimedOut) => {
+                topic_manager.metrics().record_request_timeout();
+                Frame::response_error(format!(
+                    "Request timed out after {:?} (it may still be applied)",
+                    request_timeout
+                ))
+            }
+        }
+    }
+
+    async fn process_frame(
+        frame: Frame,
+        topic_manager: &TopicManager,
+        controller: Option<&Controller>,
+        authorizer: &Authorizer,
+        principal: Option<&Principal>,
+    ) -> Frame {
+        let name = frame.name();
+        let authorized = async {
+            authorizer.authorize_frame(principal, &frame).await?;
+            authorizer
+                .authorize_auto_create(principal, &frame, topic_manager)
+                .await
+        }
+        .await;
+        if let Err(e) = authorized {
+            warn!("{}", e);
+            topic_manager.metrics().record_request(name, true).await;
+            return Frame::response_from_error(e);
+        }
+
+        let result = match frame {
+            Frame::Produce {
+                topic,
+                message,
+                partition_id,
+                transactional_id,
+                headers,
+                timestamp,
+                acks,
+            } => {
+                let msg = Message::new(message)
+                    .with_headers(headers)
+                    .with_event_timestamp(timestamp);
+                handle_produce(
+                    topic_manager,
+                    controller,
+                    topic,
+                    msg,
+                    partition_id,
+                    transactional_id,
+                    acks,
+                )
+                .await
             }
             Frame::Consume {
                 topic,
                 partition_id,
                 offset,
-            } => match handle_consume(topic_manager, topic, partition_id, offset).await {
-                Ok(response) => response,
-                Err(e) => Frame::Response {
-                    status: protocol::Status::error(e.to_string()),
-                    data: None,
-                    message: Some(e.to_string()),
-                },
-            },
+                isolation_level,
+            } => handle_consume(topic_manager, topic, partition_id, offset, isolation_level).await,
             Frame::ConsumeNext {
                 topic,
                 partition_id,
-            } => match handle_consume_next(topic_manager, topic, partition_id).await {
-                Ok(response) => response,
-                Err(e) => Frame::Response {
-                    status: protocol::Status::error(e.to_string()),
-                    data: None,
-                    message: Some(e.to_string()),
-                },
-            },
-            Frame::Ping => Frame::Pong,
-            Frame::Pong => Frame::Ping,
-            Frame::Response { .. } => Frame::Response {
+                isolation_level,
+            } => handle_consume_next(topic_manager, topic, partition_id, isolation_level).await,
+            Frame::ListOffsets {
+                topic,
+                partition_id,
+                timestamp,
+            } => handle_list_offsets(topic_manager, topic, partition_id, timestamp).await,
+            Frame::Metadata { topics } => {
+                // 전체 토픽 요청에는 조회 권한이 있는 토픽만 응답
+                let topics = match topics {
+                    None if authorizer.is_enabled() => {
+                        let mut names = Vec::new();
+                        for name in topic_manager.list_topics().await {
+                            if authorizer
+                                .is_authorized(
+                                    principal,
+                                    AclOperation::Describe,
+                                    ResourceType::Topic,
+                                    &name,
+                                )
+                                .await
+                            {
+                                names.push(name);
+                            }
+                        }
+                        Some(names)
+                    }
+                    topics => topics,
+                };
+                handle_metadata(topic_manager, controller, topics).await
+            }
+            Frame::DescribePartitions { topic } => {
+                handle_describe_partitions(topic_manager, topic).await
+            }
+            Frame::CreateTopic { topic, configs } => {
+                handle_create_topic(topic_manager, controller, topic, configs).await
+            }
+            Frame::DeleteTopic { topic } => {
+                handle_delete_topic(topic_manager, controller, topic).await
+            }
+            Frame::DeleteRecords {
+                topic,
+                partition_id,
+                before_offset,
+            } => handle_delete_records(topic_manager, topic, partition_id, before_offset).await,
+            Frame::DescribeConfigs { topic } => handle_describe_configs(topic_manager, topic).await,
+            Frame::AlterConfigs { topic, set, delete } => {
+                handle_alter_configs(topic_manager, controller, topic, set, delete).await
+            }
+            Frame::BeginTxn { transactional_id } => {
+                handle_begin_txn(topic_manager, transactional_id).await
+            }
+            Frame::AddPartitionsToTxn {
+                transactional_id,
+                partitions,
+            } => handle_add_partitions_to_txn(topic_manager, transactional_id, partitions).await,
+            Frame::CommitTxn { transactional_id } => {
+                handle_end_txn(topic_manager, transactional_id, ControlType::Commit).await
+            }
+            Frame::AbortTxn { transactional_id } => {
+                handle_end_txn(topic_manager, transactional_id, ControlType::Abort).await
+            }
+            Frame::OffsetCommit {
+                group,
+                topic,
+                partition_id,
+                offset,
+            } => handle_offset_commit(topic_manager, group, topic, partition_id, offset).await,
+            Frame::OffsetFetch {
+                group,
+                topic,
+                partition_id,
+            } => handle_offset_fetch(topic_manager, group, topic, partition_id).await,
+            Frame::DescribeConsumerLag { group } => {
+                handle_describe_consumer_lag(topic_manager, group).await
+            }
+            Frame::ReplicaFetch {
+                replica_id,
+                partitions,
+            } => handle_replica_fetch(topic_manager, replica_id, partitions).await,
+            Frame::RaftVote {
+                term,
+                candidate_id,
+                last_log_index,
+                last_log_term,
+            } => {
+                handle_raft_vote(
+                    controller,
+                    term,
+                    candidate_id,
+                    last_log_index,
+                    last_log_term,
+                )
+                .await
+            }
+            Frame::RaftAppend {
+                term,
+                leader_id,
+                prev_log_index,
+                prev_log_term,
+                entries,
+                leader_commit,
+            } => {
+                handle_raft_append(
+                    controller,
+                    term,
+                    leader_id,
+                    prev_log_index,
+                    prev_log_term,
+                    entries,
+                    leader_commit,
+                )
+                .await
+            }
+            Frame::DescribeCluster => handle_describe_cluster(controller).await,
+            Frame::ReassignPartitions {
+                reassignments,
+                throttle_bytes_per_sec,
+            } => {
+                handle_reassign_partitions(controller, reassignments, throttle_bytes_per_sec).await
+            }
+            Frame::DecommissionBroker {
+                broker_id,
+                throttle_bytes_per_sec,
+            } => handle_decommission_broker(controller, broker_id, throttle_bytes_per_sec).await,
+            Frame::ListReassignments => handle_list_reassignments(controller).await,
+            Frame::CreateAcls { acls } => handle_create_acls(authorizer, controller, acls).await,
+            Frame::DescribeAcls { filter } => handle_describe_acls(authorizer, filter).await,
+            Frame::DeleteAcls { filters } => {
+                handle_delete_acls(authorizer, controller, filters).await
+            }
+            // handle_connection 에서 처리
+            Frame::SaslHandshake { .. } | Frame::SaslAuthenticate { .. } => Err(
+                MeierError::Authentication("Unexpected SASL frame".to_string()),
+            ),
+            Frame::SetClientId { .. } => Err(MeierError::Protocol(
+                "Unexpected SetClientId frame".to_string(),
+            )),
+            Frame::Ping => Ok(Frame::Pong),
+            Frame::Pong => Ok(Frame::Ping),
+            Frame::Response { .. } => Ok(Frame::Response {
                 status: protocol::Status::error(
                     "Server does not accept response frames".to_string(),
                 ),
                 data: None,
                 message: Some("Invalid frame type".to_string()),
-            },
+                record: None,
+                throttle_time_ms: None,
+            }),
+        };
+
+        // 핸들러가 에러 상태의 응답을 직접 만든 경우도 실패로 센다
+        let response = result.unwrap_or_else(Frame::response_from_error);
+        topic_manager
+            .metrics()
+            .record_request(name, response.is_error_response())
+            .await;
+        response
+    }
+}
+
+#[derive(Debug)]
+enum HandlerError {
+    /// 이전 요청이 아직 처리 중이라 새 요청을 받지 않음
+    Busy,
+    /// 시간 안에 끝나지 않음(태스크는 handlers 에 남아 계속 처리된다)
+    TimedOut,
+    Failed(tokio::task::JoinError),
+}
+
+/// handlers 에서 task 를 실행하고 timeout 동안 결과를 기다린다
+///
+/// 시간이 지난 태스크는 취소하지 않고 handlers 에 남겨 두며, 그런 태스크가
+/// MAX_PENDING_HANDLERS 개면 새 요청을 실행하지 않는다.
+async fn run_handler<F>(
+    handlers: &mut JoinSet<Frame>,
+    task: F,
+    timeout: Duration,
+) -> std::result::Result<Frame, HandlerError>
+where
+    F: Future<Output = Frame> + Send + 'static,
+{
+    // 이미 끝난 이전 요청 정리
+    while handlers.try_join_next().is_some() {}
+    if handlers.len() >= MAX_PENDING_HANDLERS {
+        return Err(HandlerError::Busy);
+    }
+
+    let id = handlers.spawn(task.in_current_span()).id();
+    let wait = async {
+        while let Some(result) = handlers.join_next_with_id().await {
+            match result {
+                Ok((task_id, response)) if task_id == id => return Ok(response),
+                Err(e) if e.id() == id => return Err(HandlerError::Failed(e)),
+                // 이전에 시간이 지난 요청이 끝남
+                _ => {}
+            }
+        }
+        Err(HandlerError::TimedOut)
+    };
+    tokio::time::timeout(timeout, wait)
+        .await
+        .unwrap_or(Err(HandlerError::TimedOut))
+}
+
+/// client id 는 로그와 설정 키로 쓰므로 길이와 문자를 제한한다
+fn validate_client_id(client_id: &str) -> Result<()> {
+    let valid = !client_id.is_empty()
+        && client_id.len() <= MAX_CLIENT_ID_LEN
+        && client_id
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || matches!(c, '.' | '_' | '-'));
+    match valid {
+        true => Ok(()),
+        false => Err(MeierError::Protocol(format!(
+            "Invalid client id '{}': must be 1-{} characters of [a-zA-Z0-9._-]",
+            client_id, MAX_CLIENT_ID_LEN
+        ))),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tokio::task::JoinHandle;
+    use tokio_util::codec::Framed;
+
+    type Connection = Framed<TcpStream, MeierCodec>;
+
+    async fn start(
+        configure: impl FnOnce(&mut Config),
+    ) -> (String, ShutdownHandle, JoinHandle<Result<()>>) {
+        let port = std::net::TcpListener::bind("127.0.0.1:0")
+            .unwrap()
+            .local_addr()
+            .unwrap()
+            .port();
+        let mut config = Config::default();
+        config.server.bind_addr = format!("127.0.0.1:{}", port);
+        config.storage.data_dir = std::env::temp_dir().join(format!("meier-server-{}", port));
+        configure(&mut config);
+
+        let server = Server::new(config).unwrap();
+        let shutdown = server.shutdown_handle();
+        let task = tokio::spawn(async move { server.run().await });
+        (format!("127.0.0.1:{}", port), shutdown, task)
+    }
+
+    /// 서버가 연결을 받을 때까지 다시 시도
+    async fn connect(addr: &str) -> Connection {
+        for _ in 0..50 {
+            if let Ok(stream) = TcpStream::connect(addr).await {
+                return Framed::new(stream, MeierCodec::default());
+            }
+            tokio::time::sleep(Duration::from_millis(20)).await;
+        }
+        panic!("server at {} did not start", addr);
+    }
+
+    async fn request(connection: &mut Connection, frame: Frame) -> Frame {
+        connection.send(frame).await.unwrap();
+        connection.next().await.unwrap().unwrap()
+    }
+
+    #[tokio::test]
+    async fn shutdown_closes_idle_connections_and_stops_accepting() {
+        let (addr, shutdown, task) = start(|_| {}).await;
+        let mut connection = connect(&addr).await;
+        request(
+            &mut connection,
+            Frame::produce("orders".to_string(), b"created".to_vec()),
+        )
+        .await
+        .into_result()
+        .unwrap();
+
+        shutdown.shutdown();
+        tokio::time::timeout(Duration::from_secs(5), task)
+            .await
+            .unwrap()
+            .unwrap()
+            .unwrap();
+        assert!(connection.next().await.is_none());
+        assert!(TcpStream::connect(&addr).await.is_err());
+    }
+
+    #[tokio::test]
+    async fn timed_out_handlers_run_to_completion_and_are_bounded() {
+        let completed = Arc::new(AtomicU64::new(0));
+        let slow = || {
+            let completed = completed.clone();
+            async move {
+                tokio::time::sleep(Duration::from_millis(200)).await;
+                completed.fetch_add(1, Ordering::SeqCst);
+                Frame::response_ok(None)
+            }
+        };
+
+        let mut handlers = JoinSet::new();
+        for _ in 0..MAX_PENDING_HANDLERS {
+            let result = run_handler(&mut handlers, slow(), Duration::from_millis(10)).await;
+            assert!(matches!(result, Err(HandlerError::TimedOut)));
         }
+        let result = run_handler(&mut handlers, slow(), Duration::from_secs(1)).await;
+        assert!(matches!(result, Err(HandlerError::Busy)));
+
+        // 시간이 지난 요청도 취소되지 않고 끝난다
+        tokio::time::sleep(Duration::from_millis(300)).await;
+        assert_eq!(
+            completed.load(Ordering::SeqCst),
+            MAX_PENDING_HANDLERS as u64
+        );
+        let result = run_handler(&mut handlers, slow(), Duration::from_secs(1)).await;
+        assert!(result.is_ok());
+        assert!(handlers.is_empty());
+    }
+
+    #[tokio::test]
+    async fn rejects_connections_over_the_limit_and_closes_idle_ones() {
+        let (addr, _shutdown, _task) = start(|config| {
+            config.server.max_connections_per_ip = 1;
+            config.server.idle_timeout_secs = 1;
+        })
+        .await;
+
+        let mut first = connect(&addr).await;
+        assert_eq!(request(&mut first, Frame::Ping).await, Frame::Pong);
+
+        let mut second = connect(&addr).await;
+        let rejected = second.next().await.unwrap().unwrap().into_result();
+        assert!(rejected.is_err(), "{:?}", rejected);
+        assert!(second.next().await.is_none());
+
+        let closed = tokio::time::timeout(Duration::from_secs(5), first.next()).await;
+        assert!(matches!(closed, Ok(None)));
+        // 닫힌 연결은 한도에서 빠진다(연결 태스크가 끝날 때까지 다시 시도)
+        let mut accepted = false;
+        for _ in 0..20 {
+            let mut third = connect(&addr).await;
+            if request(&mut third, Frame::Ping).await == Frame::Pong {
+                accepted = true;
+                break;
+            }
+            tokio::time::sleep(Duration::from_millis(50)).await;
+        }
+        assert!(accepted);
+    }
+
+    #[tokio::test]
+    async fn keeps_connection_open_after_invalid_frame() {
+        let (addr, _shutdown, _task) = start(|_| {}).await;
+        let mut connection = connect(&addr).await;
+
+        let payload = b"{\"Unknown\":{}}";
+        let stream = connection.get_mut();
+        tokio::io::AsyncWriteExt::write_all(stream, &(payload.len() as u32).to_be_bytes())
+            .await
+            .unwrap();
+        tokio::io::AsyncWriteExt::write_all(stream, payload)
+            .await
+            .unwrap();
+        let response = connection.next().await.unwrap().unwrap().into_result();
+        assert!(response.is_err());
+
+        assert_eq!(request(&mut connection, Frame::Ping).await, Frame::Pong);
     }
 }
@@ -1,5 +1,14 @@
 use crate::{MeierError, Result};
 
+/// 버퍼 사용량과 한도
+#[derive(Debug, Clone, Copy)]
+pub struct BufferUsage {
+    pub size_bytes: usize,
+    pub max_size_bytes: usize,
+    pub message_count: usize,
+    pub max_messages: usize,
+}
+
 pub struct BufferManager {
     max_messages: usize,
     max_size_bytes: usize,
@@ -22,6 +31,11 @@ impl BufferManager {
             && self.current_size + message_size <= self.max_size_bytes
     }
 
+    /// 버퍼가 비어 있다면 추가할 수 있는 크기인지 여부
+    pub fn fits(&self, message_size: usize) -> bool {
+        self.max_messages > 0 && message_size <= self.max_size_bytes
+    }
+
     pub fn add_message(&mut self, size: usize) -> Result<()> {
         if !self.can_add(size) {
             return Err(MeierError::BufferOverflow(format!(
@@ -36,6 +50,12 @@ impl BufferManager {
         Ok(())
     }
 
+    /// 한도 검사 없이 추가(팔로워 복제본은 리더의 로그를 그대로 따른다)
+    pub fn force_add_message(&mut self, size: usize) {
+        self.current_size += size;
+        self.message_count += 1;
+    }
+
     pub fn remove_message(&mut self, size: usize) {
         if self.current_size >= size {
             self.current_size -= size;
@@ -46,6 +66,12 @@ impl BufferManager {
         }
     }
 
+    /// 한도 변경(초과분은 각 파티션에 메시지가 추가될 때 그 파티션의 오래된 메시지부터 제거된다)
+    pub fn set_limits(&mut self, max_messages: usize, max_size_bytes: usize) {
+        self.max_messages = max_messages;
+        self.max_size_bytes = max_size_bytes;
+    }
+
     pub fn current_size(&self) -> usize {
         self.current_size
     }
@@ -53,4 +79,13 @@ impl BufferManager {
     pub fn message_count(&self) -> usize {
         self.message_count
     }
+
+    pub fn usage(&self) -> BufferUsage {
+        BufferUsage {
+            size_bytes: self.current_size,
+            max_size_bytes: self.max_size_bytes,
+            message_count: self.message_count,
+            max_messages: self.max_messages,
+        }
+    }
 }
@@ -1,29 +1,156 @@
-use crate::MeierError;
+use flate2::{Compression, read::GzDecoder, write::GzEncoder};
+use serde::{Deserialize, Serialize};
+use std::{
+    collections::BTreeMap,
+    io::{Read, Write},
+};
 
-#[derive(Debug, Clone)]
+use crate::{MeierError, Result, config::CompressionType};
+
+/// 트랜잭션 종료 마커 종류
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ControlType {
+    Commit,
+    Abort,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct Message {
     pub data: Vec<u8>,
+    /// 브로커 기록 시각(ms)
     pub timestamp: u64,
+    /// 프로듀서가 지정한 이벤트 시각(ms)
+    pub event_timestamp: Option<u64>,
+    pub headers: BTreeMap<String, String>,
+    /// 메시지가 속한 트랜잭션 ID
+    pub txn_id: Option<u64>,
+    /// 트랜잭션 마커인 경우 설정
+    pub control: Option<ControlType>,
+    /// data 의 압축 방식
+    pub compression: CompressionType,
 }
 
 impl Message {
     pub fn new(data: Vec<u8>) -> Self {
         Self {
             data,
-            timestamp: std::time::SystemTime::now()
-                .duration_since(std::time::UNIX_EPOCH)
-                .unwrap()
-                .as_secs(),
+            timestamp: now_millis(),
+            event_timestamp: None,
+            headers: BTreeMap::new(),
+            txn_id: None,
+            control: None,
+            compression: CompressionType::None,
+        }
+    }
+
+    pub fn with_headers(mut self, headers: BTreeMap<String, String>) -> Self {
+        self.headers = headers;
+        self
+    }
+
+    pub fn with_event_timestamp(mut self, event_timestamp: Option<u64>) -> Self {
+        self.event_timestamp = event_timestamp;
+        self
+    }
+
+    pub fn with_txn_id(mut self, txn_id: u64) -> Self {
+        self.txn_id = Some(txn_id);
+        self
+    }
+
+    /// 트랜잭션 커밋/중단 마커 생성
+    pub fn control(txn_id: u64, control: ControlType) -> Self {
+        Self {
+            txn_id: Some(txn_id),
+            control: Some(control),
+            ..Self::new(Vec::new())
+        }
+    }
+
+    /// data 압축(이미 압축된 경우 그대로 반환)
+    pub fn compress(mut self, compression: CompressionType) -> Result<Self> {
+        if self.compression != CompressionType::None {
+            return Ok(self);
         }
+
+        match compression {
+            CompressionType::None => {}
+            CompressionType::Gzip => {
+                let mut encoder = GzEncoder::new(Vec::new(), Compression::default());
+                encoder.write_all(&self.data)?;
+                self.data = encoder.finish()?;
+            }
+        }
+        self.compression = compression;
+        Ok(self)
+    }
+
+    /// 압축된 data 복원
+    pub fn decompress(mut self) -> Result<Self> {
+        match self.compression {
+            CompressionType::None => {}
+            CompressionType::Gzip => {
+                let mut data = Vec::new();
+                GzDecoder::new(self.data.as_slice()).read_to_end(&mut data)?;
+                self.data = data;
+            }
+        }
+        self.compression = CompressionType::None;
+        Ok(self)
+    }
+
+    pub fn is_control(&self) -> bool {
+        self.control.is_some()
     }
 
-    /// Return messages's bytes size
+    /// Return messages's bytes size (data + headers)
     pub fn size(&self) -> usize {
         self.data.len()
+            + self
+                .headers
+                .iter()
+                .map(|(k, v)| k.len() + v.len())
+                .sum::<usize>()
     }
 
-    pub fn to_string(&self) -> crate::Result<String> {
+    pub fn to_string(&self) -> Result<String> {
         String::from_utf8(self.data.clone())
             .map_err(|e| MeierError::Protocol(format!("Invalid UTF-8: {}", e)))
     }
 }
+
+/// 현재 시각(UNIX epoch 기준 ms)
+pub fn now_millis() -> u64 {
+    std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .unwrap()
+        .as_millis() as u64
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn size_includes_headers() {
+        let msg = Message::new(b"created".to_vec()).with_headers(BTreeMap::from([(
+            "trace-id".to_string(),
+            "abc".to_string(),
+        )]));
+        assert_eq!(msg.size(), "created".len() + "trace-id".len() + "abc".len());
+    }
+
+    #[test]
+    fn gzip_round_trip() {
+        let data = b"created created created created".to_vec();
+        let compressed = Message::new(data.clone())
+            .compress(CompressionType::Gzip)
+            .unwrap();
+        assert_eq!(compressed.compression, CompressionType::Gzip);
+        assert_ne!(compressed.data, data);
+
+        let decompressed = compressed.decompress().unwrap();
+        assert_eq!(decompressed.compression, CompressionType::None);
+        assert_eq!(decompressed.data, data);
+    }
+}
@@ -1,9 +1,15 @@
 pub mod buffer;
 pub mod message;
+pub mod offset;
 pub mod partition;
+pub mod replica;
 pub mod topic;
+pub mod transaction;
 
-pub use buffer::BufferManager;
-pub use message::Message;
+pub use buffer::{BufferManager, BufferUsage};
+pub use message::{ControlType, Message};
+pub use offset::{CommittedOffset, OffsetManager};
 pub use partition::Partition;
+pub use replica::{Reassignment, ReplicaState};
 pub use topic::{Topic, TopicManager};
+pub use transaction::{Transaction, TransactionManager};
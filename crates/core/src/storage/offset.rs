@@ -0,0 +1,84 @@
+use std::collections::HashMap;
+use tokio::sync::RwLock;
+
+use crate::{protocol::TopicPartition, storage::message};
+
+/// 커밋된 오프셋(다음에 읽을 오프셋)과 커밋 시각
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CommittedOffset {
+    pub offset: usize,
+    /// 커밋 시각(ms)
+    pub timestamp: u64,
+}
+
+/// 컨슈머 그룹별 커밋된 오프셋 관리
+pub struct OffsetManager {
+    offsets: RwLock<HashMap<String, HashMap<TopicPartition, CommittedOffset>>>,
+}
+
+impl OffsetManager {
+    pub fn new() -> Self {
+        Self {
+            offsets: RwLock::new(HashMap::new()),
+        }
+    }
+
+    pub async fn commit(&self, group: String, partition: TopicPartition, offset: usize) {
+        let committed = CommittedOffset {
+            offset,
+            timestamp: message::now_millis(),
+        };
+
+        let mut offsets = self.offsets.write().await;
+        offsets
+            .entry(group)
+            .or_default()
+            .insert(partition, committed);
+    }
+
+    pub async fn fetch(&self, group: &str, partition: &TopicPartition) -> Option<CommittedOffset> {
+        let offsets = self.offsets.read().await;
+        offsets.get(group)?.get(partition).copied()
+    }
+
+    /// (그룹, 파티션) 순으로 정렬된 커밋 목록(group 이 None 이면 전체 그룹)
+    pub async fn list(
+        &self,
+        group: Option<&str>,
+    ) -> Vec<(String, TopicPartition, CommittedOffset)> {
+        let offsets = self.offsets.read().await;
+
+        let mut list: Vec<_> = offsets
+            .iter()
+            .filter(|(name, _)| group.is_none_or(|group| group == name.as_str()))
+            .flat_map(|(name, partitions)| {
+                partitions
+                    .iter()
+                    .map(|(tp, committed)| (name.clone(), tp.clone(), *committed))
+            })
+            .collect();
+        list.sort_by(|(a_group, a_tp, _), (b_group, b_tp, _)| {
+            (a_group, &a_tp.topic, a_tp.partition_id).cmp(&(
+                b_group,
+                &b_tp.topic,
+                b_tp.partition_id,
+            ))
+        });
+        list
+    }
+
+    /// 삭제된 토픽의 커밋 제거
+    pub async fn remove_topic(&self, topic: &str) {
+        let mut offsets = self.offsets.write().await;
+        for partitions in offsets.values_mut() {
+            partitions.retain(|tp, _| tp.topic != topic);
+        }
+        offsets.retain(|_, partitions| !partitions.is_empty());
+    }
+}
+
+impl Default for OffsetManager {
+    fn default() -> Self {
+        Self::new()
+    }
+}
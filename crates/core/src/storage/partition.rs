@@ -1,111 +1,807 @@
-use std::{collections::VecDeque, sync::Arc};
-use tokio::sync::RwLock;
+use std::{
+    collections::{HashMap, HashSet, VecDeque},
+    sync::{
+        Arc,
+        atomic::{AtomicBool, Ordering},
+    },
+    time::Duration,
+};
+use tokio::sync::{RwLock, watch};
 
 use crate::{
-    Result,
-    storage::{BufferManager, Message},
+    MeierError, Result,
+    config::BrokerId,
+    protocol::{IsolationLevel, ReplicaRecord},
+    storage::{BufferManager, ControlType, Message, ReplicaState, message},
 };
 
+/// 파티션 내 트랜잭션 상태
+#[derive(Default)]
+struct TxnIndex {
+    /// 진행 중인 트랜잭션 ID -> 첫 메시지 오프셋
+    ongoing: HashMap<u64, usize>,
+    /// 중단 마커가 로그에 남아있는 트랜잭션 ID
+    aborted: HashSet<u64>,
+}
+
+impl TxnIndex {
+    fn track(&mut self, msg: &Message, msg_offset: usize) {
+        let Some(txn_id) = msg.txn_id else {
+            return;
+        };
+
+        match msg.control {
+            Some(control) => {
+                self.ongoing.remove(&txn_id);
+                if control == ControlType::Abort {
+                    self.aborted.insert(txn_id);
+                }
+            }
+            None => {
+                self.ongoing.entry(txn_id).or_insert(msg_offset);
+            }
+        }
+    }
+
+    /// offset 이후가 잘려나간 로그 반영
+    fn truncate(&mut self, offset: usize) {
+        self.ongoing
+            .retain(|_, first_offset| *first_offset < offset);
+    }
+
+    /// 로그에서 제거된 메시지 반영
+    fn release(&mut self, msg: &Message) {
+        if let (Some(txn_id), Some(ControlType::Abort)) = (msg.txn_id, msg.control) {
+            self.aborted.remove(&txn_id);
+        }
+    }
+
+    /// 소비자에게 노출하지 않을 메시지(트랜잭션 마커, ReadCommitted 시 중단된 메시지)
+    fn is_hidden(&self, msg: &Message, read_committed: bool) -> bool {
+        let aborted = msg
+            .txn_id
+            .map(|txn_id| self.aborted.contains(&txn_id))
+            .unwrap_or(false);
+
+        msg.is_control() || (read_committed && aborted)
+    }
+
+    fn last_stable_offset(&self, log_end_offset: usize) -> usize {
+        self.ongoing
+            .values()
+            .copied()
+            .min()
+            .unwrap_or(log_end_offset)
+            .min(log_end_offset)
+    }
+}
+
 pub struct Partition {
     id: String,
     messages: RwLock<VecDeque<Message>>,
     offset: RwLock<usize>,
+    txns: RwLock<TxnIndex>,
     buffer_manager: Arc<RwLock<BufferManager>>,
+    replica: RwLock<ReplicaState>,
+    /// 모든 ISR 에 복제된 오프셋 다음 위치(소비자는 이 앞까지만 읽는다)
+    high_watermark: watch::Sender<usize>,
+    /// 토픽이 삭제되어 더 이상 메시지를 받지 않음
+    closed: AtomicBool,
 }
 
 impl Partition {
-    pub fn new(id: String, buffer_manager: Arc<RwLock<BufferManager>>) -> Self {
+    pub fn new(
+        id: String,
+        buffer_manager: Arc<RwLock<BufferManager>>,
+        replica: ReplicaState,
+    ) -> Self {
         Self {
             id,
             messages: RwLock::new(VecDeque::new()),
             offset: RwLock::new(0),
+            txns: RwLock::new(TxnIndex::default()),
             buffer_manager,
+            replica: RwLock::new(replica),
+            high_watermark: watch::Sender::new(0),
+            closed: AtomicBool::new(false),
         }
     }
 
     pub fn id(&self) -> &str {
-        &self.id.as_str()
+        self.id.as_str()
     }
 
-    /// 1. 버퍼에 메시지 길이 추가(Locked)
-    /// 2. 파티션에 메시지 삽입
-    /// 3. 버퍼에 메시지 추가(Locked)
-    /// 4. 오프셋 증가
-    pub async fn add_message(&self, msg: Message) -> Result<()> {
+    /// 메시지를 추가하고 그 오프셋 반환
+    pub async fn add_message(&self, msg: Message) -> Result<usize> {
+        let msg_offset = self.append(msg, true).await?;
+        self.update_high_watermark().await;
+        Ok(msg_offset)
+    }
+
+    /// 1. 파티션 잠금(삭제된 파티션이면 거부)
+    /// 2. 버퍼가 꽉찬 경우 오래된 메시지 제거 및 오프셋 증가
+    /// 3. 버퍼에 메시지 길이 추가
+    /// 4. 파티션에 메시지 삽입
+    ///
+    /// enforce_limits 가 false 면 2, 3 의 한도 검사 없이 기록한다(팔로워는 리더의 로그를 그대로 따른다).
+    async fn append(&self, msg: Message, enforce_limits: bool) -> Result<usize> {
         let msg_size = msg.size();
 
-        // 블럭 해제 시 락 해제
-        {
-            // 메시지 길이 만큼 버퍼에 삽입
-            let mut buffer = self.buffer_manager.write().await;
-            buffer.add_message(msg_size)?;
+        // 삭제와 같은 순서로 잠가 삭제 중에 추가된 메시지가 버퍼에 남지 않게 한다
+        let mut messages = self.messages.write().await;
+        let mut offset = self.offset.write().await;
+        let mut txns = self.txns.write().await;
+        if self.closed.load(Ordering::Acquire) {
+            return Err(MeierError::Storage(format!(
+                "Partition {} was deleted",
+                self.id
+            )));
         }
 
-        // 파티션 내 메시지 삽입
-        let mut messages = self.messages.write().await;
-        messages.push_back(msg);
+        let mut buffer = self.buffer_manager.write().await;
+        if enforce_limits {
+            if !buffer.fits(msg_size) {
+                return Err(MeierError::BufferOverflow(format!(
+                    "Message of {} bytes exceeds the buffer limits",
+                    msg_size
+                )));
+            }
 
-        // 블럭 해제 시 락 해제
-        {
-            // 메시지를 버퍼에 삽입
-            let mut buffer = self.buffer_manager.write().await;
-            // 메시지가 꽉찬 경우
-            while !buffer.can_add(0) && !messages.is_empty() {
+            // 버퍼가 꽉찬 경우(한도가 줄어든 경우 포함) 오래된 메시지부터 제거
+            while !buffer.can_add(msg_size) && !messages.is_empty() {
                 if let Some(old_msg) = messages.pop_front() {
-                    // 메시지 제거
                     buffer.remove_message(old_msg.size());
-
-                    // 오프셋 락
-                    let mut offset = self.offset.write().await;
+                    txns.release(&old_msg);
 
                     // 가변 참조 오프셋 증가
                     *offset += 1
                 }
             }
+
+            // 메시지 길이 만큼 버퍼에 삽입
+            buffer.add_message(msg_size)?;
+        } else {
+            buffer.force_add_message(msg_size);
         }
+
+        let msg_offset = *offset + messages.len();
+        txns.track(&msg, msg_offset);
+        messages.push_back(msg);
+        Ok(msg_offset)
+    }
+
+    /// before_offset 이전의 메시지를 삭제하고 새 로그 시작 오프셋 반환
+    pub async fn delete_records(&self, before_offset: usize) -> Result<usize> {
+        let mut messages = self.messages.write().await;
+        let mut offset = self.offset.write().await;
+        let mut txns = self.txns.write().await;
+
+        let log_end_offset = *offset + messages.len();
+        if before_offset > log_end_offset {
+            return Err(MeierError::Storage(format!(
+                "Offset {} is beyond log end offset {} of partition {}",
+                before_offset, log_end_offset, self.id
+            )));
+        }
+
+        let mut buffer = self.buffer_manager.write().await;
+        while *offset < before_offset {
+            if let Some(old_msg) = messages.pop_front() {
+                buffer.remove_message(old_msg.size());
+                txns.release(&old_msg);
+            }
+            *offset += 1;
+        }
+
+        Ok(*offset)
+    }
+
+    /// offset 이후의 메시지를 로그 끝에서부터 삭제
+    async fn truncate_to(&self, target: usize) {
+        let mut messages = self.messages.write().await;
+        let offset = self.offset.read().await;
+        let mut txns = self.txns.write().await;
+
+        let mut buffer = self.buffer_manager.write().await;
+        while *offset + messages.len() > target.max(*offset) {
+            if let Some(msg) = messages.pop_back() {
+                buffer.remove_message(msg.size());
+                txns.release(&msg);
+            }
+        }
+        txns.truncate(target);
+    }
+
+    /// cutoff(ms) 이전에 기록된 메시지 삭제
+    pub async fn expire_before(&self, cutoff: u64) -> Result<()> {
+        let (offset, _) = self.offset_for_timestamp(cutoff).await;
+        if offset > self.current_offset().await {
+            self.delete_records(offset).await?;
+        }
+        Ok(())
+    }
+
+    /// 모든 메시지를 삭제하고 버퍼에서 해제
+    pub async fn clear(&self) {
+        let mut messages = self.messages.write().await;
+        let mut offset = self.offset.write().await;
+        let mut txns = self.txns.write().await;
+
+        let mut buffer = self.buffer_manager.write().await;
+        for msg in messages.drain(..) {
+            buffer.remove_message(msg.size());
+            txns.release(&msg);
+            *offset += 1;
+        }
+    }
+
+    /// 토픽 삭제: 이후 메시지를 받지 않도록 닫고 모든 메시지 해제
+    pub async fn close(&self) {
+        self.closed.store(true, Ordering::Release);
+        self.clear().await;
+    }
+
+    /// 트랜잭션 커밋/중단 마커 기록
+    pub async fn write_marker(&self, txn_id: u64, control: ControlType) -> Result<()> {
+        self.add_message(Message::control(txn_id, control)).await?;
         Ok(())
     }
 
-    pub async fn get_message(&self, offset: usize) -> Option<Message> {
+    /// offset 이후 처음으로 노출 가능한 메시지와 그 오프셋 반환
+    pub async fn get_message(
+        &self,
+        offset: usize,
+        isolation_level: IsolationLevel,
+    ) -> Option<(usize, Message)> {
         let messages = self.messages.read().await;
         let current_offset = *self.offset.read().await;
+        let txns = self.txns.read().await;
 
         if offset < current_offset {
             return None;
         }
 
-        let index = offset - current_offset;
-        messages.get(index).cloned()
+        let high_watermark = self.high_watermark();
+        let read_committed = isolation_level == IsolationLevel::ReadCommitted;
+        let upper = if read_committed {
+            txns.last_stable_offset(high_watermark)
+        } else {
+            high_watermark
+        };
+
+        (offset..upper)
+            .filter_map(|o| messages.get(o - current_offset).map(|msg| (o, msg)))
+            .find(|(_, msg)| !txns.is_hidden(msg, read_committed))
+            .map(|(o, msg)| (o, msg.clone()))
     }
 
     pub async fn current_offset(&self) -> usize {
         *self.offset.read().await
     }
 
+    pub async fn log_end_offset(&self) -> usize {
+        let messages = self.messages.read().await;
+        *self.offset.read().await + messages.len()
+    }
+
+    /// 소비자에게 노출 가능한 마지막 오프셋 다음 위치
+    ///
+    /// 복제본이 없으면 로그 끝 오프셋과 같다.
+    pub fn high_watermark(&self) -> usize {
+        *self.high_watermark.borrow()
+    }
+
+    /// 진행 중인 트랜잭션의 첫 오프셋(없으면 high watermark)
+    pub async fn last_stable_offset(&self) -> usize {
+        let high_watermark = self.high_watermark();
+        self.txns.read().await.last_stable_offset(high_watermark)
+    }
+
+    /// timestamp(ms) 이후 기록된 첫 메시지의 오프셋과 기록 시각 반환
+    ///
+    /// 해당하는 메시지가 없으면 로그 끝 오프셋을 반환한다.
+    pub async fn offset_for_timestamp(&self, timestamp: u64) -> (usize, Option<u64>) {
+        let messages = self.messages.read().await;
+        let current_offset = *self.offset.read().await;
+
+        match messages.iter().position(|msg| msg.timestamp >= timestamp) {
+            Some(index) => (current_offset + index, Some(messages[index].timestamp)),
+            None => (current_offset + messages.len(), None),
+        }
+    }
+
+    /// offset 위치 메시지의 기록 시각(이미 삭제되었으면 로그 시작 메시지, 로그 끝이면 None)
+    pub async fn timestamp_at(&self, offset: usize) -> Option<u64> {
+        let messages = self.messages.read().await;
+        let current_offset = *self.offset.read().await;
+
+        messages
+            .get(offset.saturating_sub(current_offset))
+            .map(|msg| msg.timestamp)
+    }
+
     pub async fn message_count(&self) -> usize {
         self.messages.read().await.len()
     }
 
-    pub async fn consume_message(&self) -> Option<Message> {
+    pub async fn size_bytes(&self) -> usize {
+        self.messages.read().await.iter().map(Message::size).sum()
+    }
+
+    /// 맨 앞의 노출 가능한 메시지를 소비하고 그 오프셋과 함께 반환
+    ///
+    /// 노출하지 않을 메시지는 건너뛰며 제거된다.
+    pub async fn consume_message(
+        &self,
+        isolation_level: IsolationLevel,
+    ) -> Option<(usize, Message)> {
         let mut messages = self.messages.write().await;
         let mut offset = self.offset.write().await;
+        let mut txns = self.txns.write().await;
+
+        let read_committed = isolation_level == IsolationLevel::ReadCommitted;
+        let high_watermark = self.high_watermark();
+        let upper = if read_committed {
+            txns.last_stable_offset(high_watermark)
+        } else {
+            high_watermark
+        };
+        let mut released = Vec::new();
+        let mut consumed = None;
 
         // 메시지 소비
-        if let Some(msg) = messages.pop_front() {
-            let msg_size = msg.size();
+        while let Some(msg) = messages.front() {
+            if *offset >= upper {
+                break;
+            }
+
+            let skip = txns.is_hidden(msg, read_committed);
+            let msg = messages.pop_front().unwrap();
+            released.push(msg.size());
+            txns.release(&msg);
             *offset += 1;
 
+            if !skip {
+                consumed = Some((*offset - 1, msg));
+                break;
+            }
+        }
+
+        if !released.is_empty() {
             tokio::spawn({
                 let buffer = self.buffer_manager.clone();
                 async move {
                     let mut buffer = buffer.write().await;
-                    buffer.remove_message(msg_size);
+                    for msg_size in released {
+                        buffer.remove_message(msg_size);
+                    }
                 }
             });
+        }
 
-            Some(msg)
-        } else {
+        consumed
+    }
+
+    pub async fn replica_state(&self) -> ReplicaState {
+        self.replica.read().await.clone()
+    }
+
+    pub async fn is_leader(&self) -> bool {
+        self.replica.read().await.is_leader()
+    }
+
+    /// 컨트롤러가 선출한 리더로 변경
+    ///
+    /// 팔로워가 되면 커밋되지 않은 메시지(high watermark 이후)를 버리고 새 리더의 로그를 따른다.
+    pub async fn set_leader(&self, leader: BrokerId) {
+        let is_leader = {
+            let mut replica = self.replica.write().await;
+            replica.set_leader(leader, message::now_millis());
+            replica.is_leader()
+        };
+
+        if !is_leader {
+            self.truncate_to(self.high_watermark()).await;
+        }
+    }
+
+    /// 재배치 시작
+    pub async fn start_reassignment(
+        &self,
+        target: Vec<BrokerId>,
+        throttle_bytes_per_sec: Option<u64>,
+    ) {
+        self.replica
+            .write()
+            .await
+            .start_reassignment(target, throttle_bytes_per_sec);
+    }
+
+    /// 재배치 완료 후 리더 변경
+    ///
+    /// 복제본에서 빠진 브로커는 메시지를 모두 지우고 오프셋을 처음으로 되돌려
+    /// 다시 배치되면 리더의 로그 시작부터 복제한다.
+    pub async fn complete_reassignment(
+        &self,
+        replicas: Vec<BrokerId>,
+        leader: BrokerId,
+    ) -> Result<()> {
+        let (leader_changed, is_leader, is_replica) = {
+            let mut replica = self.replica.write().await;
+            replica.complete_reassignment(replicas);
+            let leader_changed = replica.leader() != leader;
+            if leader_changed {
+                replica.set_leader(leader, message::now_millis());
+            }
+            (leader_changed, replica.is_leader(), replica.is_replica())
+        };
+
+        if !is_replica {
+            self.clear().await;
+            *self.offset.write().await = 0;
+            self.high_watermark.send_replace(0);
+        } else if leader_changed && !is_leader {
+            self.truncate_to(self.high_watermark()).await;
+        }
+        Ok(())
+    }
+
+    pub async fn in_sync_replicas(&self) -> Vec<BrokerId> {
+        self.replica
+            .read()
+            .await
+            .in_sync_replicas(message::now_millis())
+    }
+
+    /// 리더: ISR 이 모두 복제한 위치까지 high watermark 이동
+    pub async fn update_high_watermark(&self) {
+        let log_end_offset = self.log_end_offset().await;
+        let replica = self.replica.read().await;
+        if !replica.is_leader() {
+            return;
+        }
+
+        let replicated = replica.replicated_offset(log_end_offset, message::now_millis());
+        self.advance_high_watermark(replicated);
+    }
+
+    fn advance_high_watermark(&self, offset: usize) {
+        self.high_watermark.send_if_modified(|hw| {
+            if offset > *hw {
+                *hw = offset;
+                true
+            } else {
+                false
+            }
+        });
+    }
+
+    /// offset 의 메시지가 ISR 에 모두 복제될 때까지 최대 timeout 동안 대기
+    ///
+    /// 시간 안에 복제되면 true, 시간이 지나면 false
+    pub async fn wait_for_replication(&self, offset: usize, timeout: Duration) -> Result<bool> {
+        let mut high_watermark = self.high_watermark.subscribe();
+        match tokio::time::timeout(timeout, high_watermark.wait_for(|hw| *hw > offset)).await {
+            Ok(result) => result
+                .map(|_| true)
+                .map_err(|e| MeierError::Storage(format!("Replication wait failed: {}", e))),
+            Err(_) => Ok(false),
+        }
+    }
+
+    /// 리더: 팔로워의 fetch 를 반영하고 fetch_offset 이후 메시지를 max_bytes 까지 반환
+    ///
+    /// fetch_offset 이 이미 삭제되었으면 로그 시작부터 반환한다.
+    pub async fn fetch_for_replica(
+        &self,
+        follower: BrokerId,
+        fetch_offset: usize,
+        max_bytes: usize,
+    ) -> Vec<ReplicaRecord> {
+        let log_end_offset = self.log_end_offset().await;
+        self.replica.write().await.record_fetch(
+            follower,
+            fetch_offset,
+            log_end_offset,
+            message::now_millis(),
+        );
+        self.update_high_watermark().await;
+
+        let messages = self.messages.read().await;
+        let current_offset = *self.offset.read().await;
+
+        let mut records = Vec::new();
+        let mut bytes = 0;
+        for (index, msg) in messages
+            .iter()
+            .enumerate()
+            .skip(fetch_offset.saturating_sub(current_offset))
+        {
+            // 메시지 하나가 max_bytes 보다 커도 최소 하나는 보낸다
+            if !records.is_empty() && bytes + msg.size() > max_bytes {
+                break;
+            }
+            bytes += msg.size();
+            records.push(ReplicaRecord {
+                offset: current_offset + index,
+                message: msg.clone(),
+            });
+        }
+        records
+    }
+
+    /// 팔로워: 리더에서 가져온 메시지를 같은 오프셋으로 기록
+    ///
+    /// 버퍼 한도는 리더가 지키므로 로그 시작과 끝 오프셋을 리더와 같게 맞춘다.
+    pub async fn append_replica(
+        &self,
+        log_start_offset: usize,
+        records: Vec<ReplicaRecord>,
+        leader_high_watermark: usize,
+        leader_isr: Vec<BrokerId>,
+    ) -> Result<()> {
+        // 리더의 로그가 이 복제본의 끝보다 앞서 시작하면 로그를 비우고 그 위치부터 이어서 기록
+        let start = records
+            .first()
+            .map(|record| record.offset)
+            .unwrap_or(log_start_offset)
+            .max(log_start_offset);
+        if start > self.log_end_offset().await {
+            self.clear().await;
+            *self.offset.write().await = start;
+        }
+
+        for record in records {
+            let log_end_offset = self.log_end_offset().await;
+            if record.offset < log_end_offset {
+                continue;
+            }
+            self.append(record.message, false).await?;
+        }
+
+        // 리더에서 삭제된 메시지 반영(팔로워는 스스로 메시지를 제거하지 않는다)
+        let log_end_offset = self.log_end_offset().await;
+        if log_start_offset > self.current_offset().await {
+            self.delete_records(log_start_offset.min(log_end_offset))
+                .await?;
+        }
+
+        self.advance_high_watermark(leader_high_watermark.min(log_end_offset));
+        self.replica.write().await.set_leader_isr(leader_isr);
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn partition(max_messages: usize) -> Partition {
+        Partition::new(
+            "orders-0".to_string(),
+            Arc::new(RwLock::new(BufferManager::new(max_messages, 1024 * 1024))),
+            ReplicaState::new(0, vec![0], 10_000, message::now_millis()),
+        )
+    }
+
+    async fn read(partition: &Partition, offset: usize, level: IsolationLevel) -> Option<usize> {
+        partition
+            .get_message(offset, level)
+            .await
+            .map(|(offset, _)| offset)
+    }
+
+    #[tokio::test]
+    async fn read_committed_stops_at_open_transaction() {
+        let partition = partition(100);
+        partition
+            .add_message(Message::new(b"a".to_vec()))
+            .await
+            .unwrap();
+        partition
+            .add_message(Message::new(b"t".to_vec()).with_txn_id(1))
+            .await
+            .unwrap();
+        partition
+            .add_message(Message::new(b"b".to_vec()))
+            .await
+            .unwrap();
+
+        assert_eq!(partition.last_stable_offset().await, 1);
+        assert_eq!(
+            read(&partition, 1, IsolationLevel::ReadUncommitted).await,
+            Some(1)
+        );
+        assert_eq!(
+            read(&partition, 0, IsolationLevel::ReadCommitted).await,
+            Some(0)
+        );
+        assert_eq!(
+            read(&partition, 1, IsolationLevel::ReadCommitted).await,
             None
+        );
+
+        partition
+            .write_marker(1, ControlType::Commit)
+            .await
+            .unwrap();
+        assert_eq!(partition.log_end_offset().await, 4);
+        assert_eq!(partition.last_stable_offset().await, 4);
+        assert_eq!(
+            read(&partition, 1, IsolationLevel::ReadCommitted).await,
+            Some(1)
+        );
+        assert_eq!(
+            read(&partition, 2, IsolationLevel::ReadCommitted).await,
+            Some(2)
+        );
+        // 마커는 소비자에게 노출되지 않는다
+        assert_eq!(
+            read(&partition, 3, IsolationLevel::ReadUncommitted).await,
+            None
+        );
+    }
+
+    #[tokio::test]
+    async fn read_committed_skips_aborted_messages() {
+        let partition = partition(100);
+        partition
+            .add_message(Message::new(b"x".to_vec()).with_txn_id(1))
+            .await
+            .unwrap();
+        partition
+            .add_message(Message::new(b"a".to_vec()))
+            .await
+            .unwrap();
+        partition.write_marker(1, ControlType::Abort).await.unwrap();
+        partition
+            .add_message(Message::new(b"b".to_vec()))
+            .await
+            .unwrap();
+
+        assert_eq!(
+            read(&partition, 0, IsolationLevel::ReadUncommitted).await,
+            Some(0)
+        );
+        assert_eq!(
+            read(&partition, 0, IsolationLevel::ReadCommitted).await,
+            Some(1)
+        );
+        assert_eq!(
+            read(&partition, 2, IsolationLevel::ReadCommitted).await,
+            Some(3)
+        );
+
+        let mut consumed = Vec::new();
+        while let Some((offset, msg)) = partition
+            .consume_message(IsolationLevel::ReadCommitted)
+            .await
+        {
+            consumed.push((offset, msg.data));
+        }
+        assert_eq!(consumed, [(1, b"a".to_vec()), (3, b"b".to_vec())]);
+    }
+
+    #[tokio::test]
+    async fn lowered_limits_evict_oldest_messages_before_append() {
+        let partition = partition(10);
+        for i in 0..10u8 {
+            partition.add_message(Message::new(vec![i])).await.unwrap();
         }
+        assert_eq!(partition.current_offset().await, 0);
+
+        partition.buffer_manager.write().await.set_limits(4, 1024);
+        assert_eq!(
+            partition
+                .add_message(Message::new(b"new".to_vec()))
+                .await
+                .unwrap(),
+            10
+        );
+        assert_eq!(partition.current_offset().await, 7);
+        assert_eq!(partition.log_end_offset().await, 11);
+        assert_eq!(partition.buffer_manager.read().await.message_count(), 4);
+
+        // 한도보다 큰 메시지는 기존 메시지를 지우지 않고 거절한다
+        let result = partition.add_message(Message::new(vec![0; 2048])).await;
+        assert!(matches!(result, Err(MeierError::BufferOverflow(_))));
+        assert_eq!(partition.current_offset().await, 7);
+        assert_eq!(partition.buffer_manager.read().await.message_count(), 4);
+    }
+
+    fn replicated_partition(
+        local_id: BrokerId,
+        max_messages: usize,
+        lag_time_max_ms: u64,
+    ) -> Partition {
+        Partition::new(
+            "orders-0".to_string(),
+            Arc::new(RwLock::new(BufferManager::new(max_messages, 1024 * 1024))),
+            ReplicaState::new(local_id, vec![0, 1], lag_time_max_ms, message::now_millis()),
+        )
+    }
+
+    #[tokio::test]
+    async fn high_watermark_waits_for_in_sync_followers() {
+        let leader = Arc::new(replicated_partition(0, 100, 10_000));
+        leader
+            .add_message(Message::new(b"a".to_vec()))
+            .await
+            .unwrap();
+        leader
+            .add_message(Message::new(b"b".to_vec()))
+            .await
+            .unwrap();
+        assert_eq!(leader.high_watermark(), 0);
+
+        // acks=all: 팔로워가 가져가지 않으면 시간이 지나 false
+        assert!(
+            !leader
+                .wait_for_replication(1, Duration::from_millis(50))
+                .await
+                .unwrap()
+        );
+
+        let waiter = tokio::spawn({
+            let leader = leader.clone();
+            async move { leader.wait_for_replication(1, Duration::from_secs(5)).await }
+        });
+        let records = leader.fetch_for_replica(1, 0, 1024).await;
+        assert_eq!(records.len(), 2);
+        assert_eq!(leader.high_watermark(), 0);
+
+        // 다음 fetch 에서 팔로워가 로그 끝까지 따라잡은 것이 반영된다
+        assert!(leader.fetch_for_replica(1, 2, 1024).await.is_empty());
+        assert_eq!(leader.high_watermark(), 2);
+        assert!(waiter.await.unwrap().unwrap());
+    }
+
+    #[tokio::test]
+    async fn lagging_follower_leaves_isr_and_stops_holding_high_watermark() {
+        let leader = replicated_partition(0, 100, 50);
+        leader
+            .add_message(Message::new(b"a".to_vec()))
+            .await
+            .unwrap();
+        assert_eq!(leader.in_sync_replicas().await, [0, 1]);
+        assert_eq!(leader.high_watermark(), 0);
+
+        tokio::time::sleep(Duration::from_millis(100)).await;
+        assert_eq!(leader.in_sync_replicas().await, [0]);
+        leader.update_high_watermark().await;
+        assert_eq!(leader.high_watermark(), 1);
+
+        // 로그 끝까지 따라잡으면 다시 ISR 에 들어간다
+        leader.fetch_for_replica(1, 1, 1024).await;
+        assert_eq!(leader.in_sync_replicas().await, [0, 1]);
+    }
+
+    #[tokio::test]
+    async fn follower_mirrors_leader_log_without_local_limits() {
+        let follower = replicated_partition(1, 2, 10_000);
+        let records = (0..4)
+            .map(|offset| ReplicaRecord {
+                offset,
+                message: Message::new(vec![offset as u8]),
+            })
+            .collect();
+        follower
+            .append_replica(0, records, 3, vec![0, 1])
+            .await
+            .unwrap();
+        assert_eq!(follower.current_offset().await, 0);
+        assert_eq!(follower.log_end_offset().await, 4);
+        assert_eq!(follower.high_watermark(), 3);
+
+        // 리더의 로그 시작과 ISR 을 따른다
+        follower
+            .append_replica(3, Vec::new(), 4, vec![0])
+            .await
+            .unwrap();
+        assert_eq!(follower.current_offset().await, 3);
+        assert_eq!(follower.log_end_offset().await, 4);
+        assert_eq!(follower.high_watermark(), 4);
+        assert_eq!(follower.in_sync_replicas().await, [0]);
+        assert_eq!(follower.buffer_manager.read().await.message_count(), 1);
     }
 }
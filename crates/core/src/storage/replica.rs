@@ -0,0 +1,255 @@
+use std::collections::HashMap;
+
+use crate::config::BrokerId;
+
+/// 리더가 관리하는 팔로워 복제 상태
+#[derive(Debug, Clone, Copy)]
+struct FollowerState {
+    /// 팔로워가 마지막으로 요청한 오프셋(그 이전은 복제 완료)
+    log_end_offset: usize,
+    /// 팔로워가 리더의 로그 끝까지 따라잡은 마지막 시각(ms)
+    last_caught_up_ms: u64,
+}
+
+/// 진행 중인 복제본 재배치
+#[derive(Debug, Clone, PartialEq)]
+pub struct Reassignment {
+    /// 완료 후 복제본
+    pub target: Vec<BrokerId>,
+    /// 새로 추가되어 리더를 따라잡아야 하는 복제본
+    pub adding: Vec<BrokerId>,
+    /// 완료 시 제거되는 복제본
+    pub removing: Vec<BrokerId>,
+    /// 새 복제본이 가져오는 초당 최대 바이트
+    pub throttle_bytes_per_sec: Option<u64>,
+}
+
+/// 파티션의 리더/팔로워 구성과 복제 진행 상태
+#[derive(Debug, Clone)]
+pub struct ReplicaState {
+    local_id: BrokerId,
+    leader: BrokerId,
+    replicas: Vec<BrokerId>,
+    followers: HashMap<BrokerId, FollowerState>,
+    lag_time_max_ms: u64,
+    /// 팔로워: 리더가 마지막으로 알려준 ISR
+    leader_isr: Vec<BrokerId>,
+    reassignment: Option<Reassignment>,
+}
+
+impl ReplicaState {
+    /// replicas 의 첫 번째 브로커가 리더
+    pub fn new(
+        local_id: BrokerId,
+        replicas: Vec<BrokerId>,
+        lag_time_max_ms: u64,
+        now: u64,
+    ) -> Self {
+        let leader = replicas.first().copied().unwrap_or(local_id);
+        let followers = Self::followers_of(leader, &replicas, now);
+
+        Self {
+            local_id,
+            leader,
+            leader_isr: replicas.clone(),
+            replicas,
+            followers,
+            lag_time_max_ms,
+            reassignment: None,
+        }
+    }
+
+    // 리더가 바뀐 직후에는 모든 복제본을 ISR 로 취급
+    fn followers_of(
+        leader: BrokerId,
+        replicas: &[BrokerId],
+        now: u64,
+    ) -> HashMap<BrokerId, FollowerState> {
+        replicas
+            .iter()
+            .filter(|&&id| id != leader)
+            .map(|&id| {
+                (
+                    id,
+                    FollowerState {
+                        log_end_offset: 0,
+                        last_caught_up_ms: now,
+                    },
+                )
+            })
+            .collect()
+    }
+
+    /// 복제 없는 단일 브로커 파티션
+    pub fn local(local_id: BrokerId) -> Self {
+        Self::new(local_id, vec![local_id], 0, 0)
+    }
+
+    pub fn leader(&self) -> BrokerId {
+        self.leader
+    }
+
+    pub fn replicas(&self) -> &[BrokerId] {
+        &self.replicas
+    }
+
+    pub fn is_leader(&self) -> bool {
+        self.leader == self.local_id
+    }
+
+    pub fn is_replica(&self) -> bool {
+        self.replicas.contains(&self.local_id)
+    }
+
+    pub fn reassignment(&self) -> Option<&Reassignment> {
+        self.reassignment.as_ref()
+    }
+
+    /// 이 브로커가 재배치로 추가된 복제본이면 복제 속도 제한 반환
+    pub fn reassignment_throttle(&self) -> Option<u64> {
+        self.reassignment
+            .as_ref()
+            .filter(|reassignment| reassignment.adding.contains(&self.local_id))
+            .and_then(|reassignment| reassignment.throttle_bytes_per_sec)
+    }
+
+    /// 재배치 시작: 새 복제본을 ISR 밖의 팔로워로 추가
+    pub fn start_reassignment(
+        &mut self,
+        target: Vec<BrokerId>,
+        throttle_bytes_per_sec: Option<u64>,
+    ) {
+        let adding: Vec<BrokerId> = target
+            .iter()
+            .copied()
+            .filter(|id| !self.replicas.contains(id))
+            .collect();
+        let removing = self
+            .replicas
+            .iter()
+            .copied()
+            .filter(|id| !target.contains(id))
+            .collect();
+
+        for &id in &adding {
+            self.replicas.push(id);
+            self.followers.insert(
+                id,
+                FollowerState {
+                    log_end_offset: 0,
+                    last_caught_up_ms: 0,
+                },
+            );
+        }
+        self.reassignment = Some(Reassignment {
+            target,
+            adding,
+            removing,
+            throttle_bytes_per_sec,
+        });
+    }
+
+    /// 재배치 완료: 복제본을 replicas 로 교체(리더는 set_leader 로 따로 변경)
+    pub fn complete_reassignment(&mut self, replicas: Vec<BrokerId>) {
+        self.followers.retain(|id, _| replicas.contains(id));
+        self.leader_isr.retain(|id| replicas.contains(id));
+        self.replicas = replicas;
+        self.reassignment = None;
+    }
+
+    /// 컨트롤러가 선출한 새 리더 반영
+    ///
+    /// 이전 ISR 에 있던 팔로워만 ISR 로 시작하며, 물러난 리더는 다시 따라잡아야 ISR 에 들어간다.
+    pub fn set_leader(&mut self, leader: BrokerId, now: u64) {
+        let previous = self.leader;
+        let isr = self.in_sync_replicas(now);
+
+        self.leader = leader;
+        self.followers = Self::followers_of(leader, &self.replicas, now);
+        for (id, state) in &mut self.followers {
+            if *id == previous || !isr.contains(id) {
+                state.last_caught_up_ms = 0;
+            }
+        }
+    }
+
+    /// 팔로워의 fetch 요청 반영
+    pub fn record_fetch(
+        &mut self,
+        follower: BrokerId,
+        fetch_offset: usize,
+        leader_log_end_offset: usize,
+        now: u64,
+    ) {
+        if let Some(state) = self.followers.get_mut(&follower) {
+            state.log_end_offset = fetch_offset;
+            if fetch_offset >= leader_log_end_offset {
+                state.last_caught_up_ms = now;
+            }
+        }
+    }
+
+    /// 팔로워: 리더가 알려준 ISR 저장
+    pub fn set_leader_isr(&mut self, isr: Vec<BrokerId>) {
+        self.leader_isr = isr;
+    }
+
+    /// 리더와 lag_time_max_ms 안에 로그 끝까지 따라잡은 복제본(팔로워는 리더가 알려준 값)
+    pub fn in_sync_replicas(&self, now: u64) -> Vec<BrokerId> {
+        if !self.is_leader() {
+            return self.leader_isr.clone();
+        }
+
+        self.replicas
+            .iter()
+            .copied()
+            .filter(|id| match self.followers.get(id) {
+                Some(state) => now.saturating_sub(state.last_caught_up_ms) <= self.lag_time_max_ms,
+                None => *id == self.leader,
+            })
+            .collect()
+    }
+
+    /// ISR 이 모두 복제한 오프셋(리더 기준 high watermark 후보)
+    pub fn replicated_offset(&self, leader_log_end_offset: usize, now: u64) -> usize {
+        self.in_sync_replicas(now)
+            .iter()
+            .filter_map(|id| self.followers.get(id))
+            .map(|state| state.log_end_offset)
+            .fold(leader_log_end_offset, usize::min)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn isr_shrinks_and_expands_with_follower_progress() {
+        let mut replica = ReplicaState::new(0, vec![0, 1, 2], 1_000, 0);
+        assert_eq!(replica.in_sync_replicas(500), [0, 1, 2]);
+
+        replica.record_fetch(1, 5, 5, 1_200);
+        // 로그 끝까지 따라잡지 못한 fetch 는 ISR 유지 시각을 갱신하지 않는다
+        replica.record_fetch(2, 3, 5, 1_200);
+        assert_eq!(replica.in_sync_replicas(1_300), [0, 1]);
+        assert_eq!(replica.replicated_offset(5, 1_300), 5);
+
+        replica.record_fetch(2, 5, 5, 1_400);
+        assert_eq!(replica.in_sync_replicas(1_500), [0, 1, 2]);
+        assert_eq!(replica.replicated_offset(7, 1_500), 5);
+    }
+
+    #[test]
+    fn new_leader_starts_without_the_previous_leader_in_isr() {
+        let mut replica = ReplicaState::new(1, vec![0, 1, 2], 1_000, 0);
+        assert!(!replica.is_leader());
+
+        replica.set_leader(1, 5_000);
+        assert!(replica.is_leader());
+        assert_eq!(replica.in_sync_replicas(5_000), [1, 2]);
+
+        replica.record_fetch(0, 0, 0, 5_100);
+        assert_eq!(replica.in_sync_replicas(5_100), [0, 1, 2]);
+    }
+}
@@ -1,26 +1,58 @@
-use std::{collections::HashMap, sync::Arc};
+use std::{
+    collections::{BTreeMap, HashMap, HashSet},
+    sync::Arc,
+    time::Duration,
+};
 use tokio::sync::RwLock;
+use tracing::warn;
 
 use crate::{
     MeierError, Result,
-    storage::{BufferManager, Message, Partition},
+    config::{BrokerId, ClusterConfig, TopicConfig},
+    metrics::Metrics,
+    protocol::{ConsumerLag, TopicAssignment, TopicPartition},
+    storage::{
+        BufferManager, BufferUsage, ControlType, Message, OffsetManager, Partition, ReplicaState,
+        TransactionManager, message,
+    },
 };
 
-const DEFAULT_PARTITIONS: usize = 3;
-
 pub struct Topic {
     name: String,
     partitions: HashMap<String, Arc<Partition>>,
     rr_count: RwLock<usize>,
+    /// 토픽별로 지정된 설정
+    overrides: RwLock<BTreeMap<String, String>>,
+    /// 기본값과 overrides 를 합친 설정
+    config: RwLock<TopicConfig>,
 }
 
 impl Topic {
-    pub fn new(name: String, buffer_manager: Arc<RwLock<BufferManager>>) -> Self {
+    /// replicas 는 파티션별 복제본 목록(첫 번째가 리더)
+    pub fn new(
+        name: String,
+        config: TopicConfig,
+        overrides: BTreeMap<String, String>,
+        replicas: Vec<Vec<BrokerId>>,
+        buffer_manager: Arc<RwLock<BufferManager>>,
+        cluster: &ClusterConfig,
+    ) -> Self {
         let mut partitions = HashMap::new();
+        let now = message::now_millis();
 
-        for i in 0..DEFAULT_PARTITIONS {
+        for (i, replicas) in replicas.into_iter().enumerate() {
             let partition_id = i.to_string();
-            let partition = Arc::new(Partition::new(partition_id.clone(), buffer_manager.clone()));
+            let replica = ReplicaState::new(
+                cluster.broker_id,
+                replicas,
+                cluster.replica_lag_time_max_ms,
+                now,
+            );
+            let partition = Arc::new(Partition::new(
+                partition_id.clone(),
+                buffer_manager.clone(),
+                replica,
+            ));
 
             partitions.insert(partition_id, partition);
         }
@@ -29,6 +61,8 @@ impl Topic {
             name,
             partitions,
             rr_count: RwLock::new(0),
+            overrides: RwLock::new(overrides),
+            config: RwLock::new(config),
         }
     }
 
@@ -36,16 +70,157 @@ impl Topic {
         &self.name
     }
 
-    pub async fn add_message(&self, msg: Message) -> Result<()> {
-        let partition = self.next_partition().await;
-        partition.add_message(msg).await
+    pub async fn config(&self) -> TopicConfig {
+        self.config.read().await.clone()
+    }
+
+    pub async fn overrides(&self) -> BTreeMap<String, String> {
+        self.overrides.read().await.clone()
+    }
+
+    /// 토픽 설정 변경(set 적용 후 delete 항목은 기본값으로 복원)
+    pub async fn alter_config(
+        &self,
+        defaults: &TopicConfig,
+        set: BTreeMap<String, String>,
+        delete: Vec<String>,
+    ) -> Result<()> {
+        let mut overrides = self.overrides.write().await;
+        let mut config = self.config.write().await;
+
+        let mut updated = overrides.clone();
+        updated.extend(set);
+        for key in &delete {
+            updated.remove(key);
+        }
+
+        let resolved = defaults.with_overrides(&updated)?;
+        if resolved.partitions != config.partitions {
+            return Err(MeierError::Config(format!(
+                "{} cannot be changed after topic creation",
+                TopicConfig::PARTITIONS
+            )));
+        }
+
+        *overrides = updated;
+        *config = resolved;
+        Ok(())
+    }
+
+    /// 브로커 기본값 변경 시 설정 재계산(파티션 수는 유지)
+    pub async fn apply_defaults(&self, defaults: &TopicConfig) -> Result<()> {
+        let overrides = self.overrides.read().await;
+        let mut config = self.config.write().await;
+
+        let partitions = config.partitions;
+        *config = defaults.with_overrides(&overrides)?;
+        config.partitions = partitions;
+        Ok(())
+    }
+
+    /// defaults 에 토픽별 설정을 적용할 수 있는지 확인
+    pub async fn check_defaults(&self, defaults: &TopicConfig) -> Result<()> {
+        defaults.with_overrides(&*self.overrides.read().await)?;
+        Ok(())
+    }
+
+    /// 이 브로커가 리더인 파티션에 라운드 로빈으로 추가하고 (파티션, 오프셋) 반환
+    pub async fn add_message(&self, msg: Message) -> Result<(Arc<Partition>, usize)> {
+        let msg = self.prepare(msg).await?;
+        let partition = self.next_partition().await?;
+        let offset = partition.add_message(msg).await?;
+        Ok((partition, offset))
+    }
+
+    pub async fn add_message_to(
+        &self,
+        partition_id: usize,
+        msg: Message,
+    ) -> Result<(Arc<Partition>, usize)> {
+        let partition = self.leader_partition(partition_id).await?;
+
+        let msg = self.prepare(msg).await?;
+        let offset = partition.add_message(msg).await?;
+        Ok((partition, offset))
+    }
+
+    /// 이 브로커가 리더인 파티션 반환
+    pub async fn leader_partition(&self, partition_id: usize) -> Result<Arc<Partition>> {
+        let partition = self
+            .get_partition(&partition_id.to_string())
+            .ok_or_else(|| {
+                MeierError::PartitionNotFound(format!(
+                    "Partition {} not found in topic {}",
+                    partition_id, self.name
+                ))
+            })?;
+
+        let replica = partition.replica_state().await;
+        if !replica.is_leader() {
+            return Err(MeierError::NotLeaderForPartition {
+                topic: self.name.clone(),
+                partition_id,
+                leader: replica.leader(),
+            });
+        }
+        Ok(partition)
+    }
+
+    /// 파티션 번호 순 복제본 목록
+    pub async fn assignment(&self) -> Vec<Vec<BrokerId>> {
+        let mut replicas = Vec::new();
+        for (_, partition) in self.partitions() {
+            replicas.push(partition.replica_state().await.replicas().to_vec());
+        }
+        replicas
+    }
+
+    /// 토픽 설정에 따라 메시지 크기 확인 및 압축
+    async fn prepare(&self, msg: Message) -> Result<Message> {
+        let config = self.config.read().await;
+
+        if msg.size() > config.max_message_size_bytes {
+            return Err(MeierError::Storage(format!(
+                "Message too large: {} bytes (max: {})",
+                msg.size(),
+                config.max_message_size_bytes
+            )));
+        }
+
+        msg.compress(config.compression)
+    }
+
+    /// 보존 기간이 지난 메시지 삭제
+    pub async fn enforce_retention(&self) -> Result<()> {
+        let Some(retention_ms) = self.config.read().await.retention_ms else {
+            return Ok(());
+        };
+
+        let cutoff = message::now_millis().saturating_sub(retention_ms);
+        for partition in self.partitions.values() {
+            partition.expire_before(cutoff).await?;
+        }
+        Ok(())
     }
 
-    async fn next_partition(&self) -> Arc<Partition> {
+    async fn next_partition(&self) -> Result<Arc<Partition>> {
+        let mut leaders = Vec::new();
+        for (_, partition) in self.partitions() {
+            if partition.is_leader().await {
+                leaders.push(partition);
+            }
+        }
+        if leaders.is_empty() {
+            return Err(MeierError::Storage(format!(
+                "This broker is not the leader for any partition of topic {}",
+                self.name
+            )));
+        }
+
         let mut count = self.rr_count.write().await;
-        let partition_id = (*count % self.partitions.len()).to_string();
+        let partition = leaders[*count % leaders.len()].clone();
         *count += 1;
-        self.partitions.get(&partition_id).unwrap().clone()
+        Ok(partition)
     }
 
     pub fn get_partition(&self, partition_id: &str) -> Option<Arc<Partition>> {
@@ -55,12 +230,29 @@ impl Topic {
     pub fn partition_ids(&self) -> Vec<String> {
         self.partitions.keys().cloned().collect()
     }
+
+    /// 파티션 번호 순으로 정렬된 (번호, 파티션) 목록
+    pub fn partitions(&self) -> Vec<(usize, Arc<Partition>)> {
+        let mut partitions: Vec<_> = self
+            .partitions
+            .iter()
+            .filter_map(|(id, partition)| id.parse().ok().map(|id| (id, partition.clone())))
+            .collect();
+        partitions.sort_by_key(|(id, _)| *id);
+        partitions
+    }
 }
 
 pub struct TopicManager {
     topics: RwLock<HashMap<String, Arc<Topic>>>,
     buffer_manager: Arc<RwLock<BufferManager>>,
-    max_topics: usize,
+    transactions: TransactionManager,
+    offsets: OffsetManager,
+    /// 브로커 기본 토픽 설정
+    topic_defaults: RwLock<TopicConfig>,
+    max_topics: RwLock<usize>,
+    metrics: Arc<Metrics>,
+    cluster: ClusterConfig,
 }
 
 impl TopicManager {
@@ -68,6 +260,8 @@ impl TopicManager {
         max_topics: usize,
         max_messages_per_partition: usize,
         max_messages_size_bytes: usize,
+        topic_defaults: TopicConfig,
+        cluster: ClusterConfig,
     ) -> Self {
         let buffer_manager = Arc::new(RwLock::new(BufferManager::new(
             max_messages_per_partition,
@@ -77,17 +271,72 @@ impl TopicManager {
         Self {
             topics: RwLock::new(HashMap::new()),
             buffer_manager,
-            max_topics,
+            transactions: TransactionManager::new(),
+            offsets: OffsetManager::new(),
+            topic_defaults: RwLock::new(topic_defaults),
+            max_topics: RwLock::new(max_topics),
+            metrics: Arc::new(Metrics::new()),
+            cluster,
         }
     }
 
-    pub async fn create_topic(&self, name: String) -> Result<Arc<Topic>> {
+    pub async fn create_topic(
+        &self,
+        name: String,
+        overrides: BTreeMap<String, String>,
+    ) -> Result<Arc<Topic>> {
+        let config = self
+            .topic_defaults
+            .read()
+            .await
+            .with_overrides(&overrides)?;
+        let replicas = self
+            .assign_replicas(
+                config.partitions,
+                &self.cluster.broker_ids(),
+                &HashMap::new(),
+            )
+            .await;
+
+        self.insert_topic(name, config, overrides, replicas).await
+    }
+
+    /// 컨트롤러가 정한 복제본 배치와 설정으로 토픽 생성
+    pub async fn create_assigned_topic(&self, assignment: TopicAssignment) -> Result<()> {
+        let mut config = match assignment.config {
+            Some(config) => config,
+            None => self
+                .topic_defaults
+                .read()
+                .await
+                .with_overrides(&assignment.configs)?,
+        };
+        config.partitions = assignment.replicas.len();
+
+        self.insert_topic(
+            assignment.name,
+            config,
+            assignment.configs,
+            assignment.replicas,
+        )
+        .await?;
+        Ok(())
+    }
+
+    async fn insert_topic(
+        &self,
+        name: String,
+        config: TopicConfig,
+        overrides: BTreeMap<String, String>,
+        replicas: Vec<Vec<BrokerId>>,
+    ) -> Result<Arc<Topic>> {
+        let max_topics = *self.max_topics.read().await;
         let mut topics = self.topics.write().await;
 
-        if topics.len() >= self.max_topics {
+        if topics.len() >= max_topics {
             return Err(MeierError::Storage(format!(
                 "Maximum topics limit reached: {}",
-                self.max_topics
+                max_topics
             )));
         }
 
@@ -98,22 +347,115 @@ impl TopicManager {
             )));
         }
 
-        let topic = Arc::new(Topic::new(name.clone(), self.buffer_manager.clone()));
+        let topic = Arc::new(Topic::new(
+            name.clone(),
+            config,
+            overrides,
+            replicas,
+            self.buffer_manager.clone(),
+            &self.cluster,
+        ));
         topics.insert(name, topic.clone());
 
         Ok(topic)
     }
 
+    /// 랙을 고려해 파티션별 복제본 배치(첫 번째가 리더)
+    ///
+    /// 랙을 번갈아 가며 나열한 브로커 목록에서 파티션마다 리더를 차례로 정하고,
+    /// 팔로워는 아직 복제본이 없는 랙의 브로커부터 고른다. 리더 위치는 기존 파티션 수
+    /// 만큼 이어서 시작해 토픽이 여러 개여도 리더가 한 브로커에 몰리지 않는다.
+    /// 랙이 없는 브로커는 각자 별도의 랙으로 취급한다. brokers 는 비어 있으면 안 된다.
+    pub async fn assign_replicas(
+        &self,
+        partitions: usize,
+        brokers: &[BrokerId],
+        racks: &HashMap<BrokerId, String>,
+    ) -> Vec<Vec<BrokerId>> {
+        let brokers = rack_alternated(brokers, racks);
+        let count = brokers.len();
+        let replication_factor = self.cluster.replication_factor.min(count);
+        let start: usize = self
+            .topics
+            .read()
+            .await
+            .values()
+            .map(|topic| topic.partitions.len())
+            .sum();
+
+        (start..start + partitions)
+            .map(|p| {
+                let leader = p % count;
+                // 팔로워 시작 위치를 돌려 같은 리더의 파티션도 팔로워가 나뉘도록 한다
+                let shift = match count {
+                    1 => 0,
+                    _ => 1 + (p / count) % (count - 1),
+                };
+
+                let mut replicas = vec![brokers[leader]];
+                let mut used: Vec<Option<&String>> = vec![racks.get(&brokers[leader])];
+                for prefer_new_rack in [true, false] {
+                    for i in 0..count {
+                        if replicas.len() >= replication_factor {
+                            break;
+                        }
+                        let id = brokers[(leader + shift + i) % count];
+                        let rack = racks.get(&id);
+                        if replicas.contains(&id)
+                            || (prefer_new_rack && rack.is_some() && used.contains(&rack))
+                        {
+                            continue;
+                        }
+                        replicas.push(id);
+                        used.push(rack);
+                    }
+                }
+                replicas
+            })
+            .collect()
+    }
+
     pub async fn get_topic(&self, name: &str) -> Option<Arc<Topic>> {
         let topics = self.topics.read().await;
         topics.get(name).cloned()
     }
 
+    pub async fn get_partition(&self, topic: &str, partition_id: usize) -> Result<Arc<Partition>> {
+        let topic = self
+            .get_topic(topic)
+            .await
+            .ok_or_else(|| MeierError::TopicNotFound(topic.to_string()))?;
+
+        topic
+            .get_partition(&partition_id.to_string())
+            .ok_or_else(|| {
+                MeierError::PartitionNotFound(format!(
+                    "Partition {} not found in topic {}",
+                    partition_id,
+                    topic.name()
+                ))
+            })
+    }
+
+    /// 이 브로커가 리더인 파티션 반환
+    pub async fn get_leader_partition(
+        &self,
+        topic: &str,
+        partition_id: usize,
+    ) -> Result<Arc<Partition>> {
+        let topic = self
+            .get_topic(topic)
+            .await
+            .ok_or_else(|| MeierError::TopicNotFound(topic.to_string()))?;
+
+        topic.leader_partition(partition_id).await
+    }
+
     pub async fn get_or_create_topic(&self, name: String) -> Result<Arc<Topic>> {
         if let Some(topic) = self.get_topic(&name).await {
             Ok(topic)
         } else {
-            self.create_topic(name).await
+            self.create_topic(name, BTreeMap::new()).await
         }
     }
 
@@ -123,10 +465,488 @@ impl TopicManager {
     }
 
     pub async fn delete_topic(&self, name: &str) -> Result<()> {
-        let mut topics = self.topics.write().await;
-        topics
-            .remove(name)
+        let topic = {
+            let mut topics = self.topics.write().await;
+            topics
+                .remove(name)
+                .ok_or_else(|| MeierError::TopicNotFound(name.to_string()))?
+        };
+
+        // 삭제된 토픽의 메시지를 버퍼에서 해제
+        for (_, partition) in topic.partitions() {
+            partition.close().await;
+        }
+        self.offsets.remove_topic(name).await;
+        self.metrics.remove_topic(name).await;
+        Ok(())
+    }
+
+    pub fn cluster(&self) -> &ClusterConfig {
+        &self.cluster
+    }
+
+    /// 리더 파티션의 ISR 과 high watermark 갱신(따라오지 못하는 팔로워 제외)
+    pub async fn check_replicas(&self) {
+        let topics: Vec<_> = self.topics.read().await.values().cloned().collect();
+        for topic in topics {
+            for (_, partition) in topic.partitions() {
+                partition.update_high_watermark().await;
+            }
+        }
+    }
+
+    pub async fn topic_defaults(&self) -> TopicConfig {
+        self.topic_defaults.read().await.clone()
+    }
+
+    /// 저장소 한도 변경
+    pub async fn set_limits(
+        &self,
+        max_topics: usize,
+        max_messages_per_partition: usize,
+        max_messages_size_bytes: usize,
+    ) {
+        *self.max_topics.write().await = max_topics;
+        self.buffer_manager
+            .write()
+            .await
+            .set_limits(max_messages_per_partition, max_messages_size_bytes);
+    }
+
+    /// 모든 토픽의 토픽별 설정과 함께 적용할 수 있는 기본 토픽 설정인지 확인
+    pub async fn check_topic_defaults(&self, defaults: &TopicConfig) -> Result<()> {
+        let topics: Vec<_> = self.topics.read().await.values().cloned().collect();
+        for topic in topics {
+            topic
+                .check_defaults(defaults)
+                .await
+                .map_err(|e| MeierError::Config(format!("Topic {}: {}", topic.name(), e)))?;
+        }
+        Ok(())
+    }
+
+    /// 기본 토픽 설정 변경 후 토픽별 설정이 없는 항목에 반영
+    ///
+    /// check_topic_defaults 로 먼저 확인한다. 그 사이 설정이 바뀌어 적용할 수 없는 토픽은
+    /// 이전 설정을 유지한다.
+    pub async fn set_topic_defaults(&self, defaults: TopicConfig) {
+        *self.topic_defaults.write().await = defaults.clone();
+
+        let topics: Vec<_> = self.topics.read().await.values().cloned().collect();
+        for topic in topics {
+            if let Err(e) = topic.apply_defaults(&defaults).await {
+                warn!("Keeping previous config of topic {}: {}", topic.name(), e);
+            }
+        }
+    }
+
+    pub async fn alter_topic_config(
+        &self,
+        name: &str,
+        set: BTreeMap<String, String>,
+        delete: Vec<String>,
+    ) -> Result<()> {
+        let topic = self
+            .get_topic(name)
+            .await
             .ok_or_else(|| MeierError::TopicNotFound(name.to_string()))?;
+        let defaults = self.topic_defaults().await;
+
+        topic.alter_config(&defaults, set, delete).await
+    }
+
+    /// 모든 토픽의 보존 기간 적용
+    pub async fn enforce_retention(&self) -> Result<()> {
+        let topics: Vec<_> = self.topics.read().await.values().cloned().collect();
+        for topic in topics {
+            topic.enforce_retention().await?;
+        }
+        Ok(())
+    }
+
+    pub async fn buffer_usage(&self) -> BufferUsage {
+        self.buffer_manager.read().await.usage()
+    }
+
+    pub fn metrics(&self) -> &Arc<Metrics> {
+        &self.metrics
+    }
+
+    pub fn transactions(&self) -> &TransactionManager {
+        &self.transactions
+    }
+
+    pub fn offsets(&self) -> &OffsetManager {
+        &self.offsets
+    }
+
+    /// 컨슈머 그룹의 오프셋 커밋(로그 끝 오프셋까지 허용)
+    pub async fn commit_offset(
+        &self,
+        group: String,
+        topic: &str,
+        partition_id: usize,
+        offset: usize,
+    ) -> Result<()> {
+        let partition = self.get_partition(topic, partition_id).await?;
+
+        let log_end_offset = partition.log_end_offset().await;
+        if offset > log_end_offset {
+            return Err(MeierError::Storage(format!(
+                "Offset {} is beyond log end offset {}",
+                offset, log_end_offset
+            )));
+        }
+
+        let tp = TopicPartition {
+            topic: topic.to_string(),
+            partition_id,
+        };
+        self.offsets.commit(group, tp, offset).await;
         Ok(())
     }
+
+    /// 커밋된 오프셋 기준 컨슈머 그룹별 지연(group 이 None 이면 전체 그룹)
+    pub async fn consumer_lag(&self, group: Option<&str>) -> Vec<ConsumerLag> {
+        let now = message::now_millis();
+        let mut result = Vec::new();
+
+        for (group, tp, committed) in self.offsets.list(group).await {
+            // 커밋 후 삭제된 파티션은 건너뛴다
+            let Ok(partition) = self.get_partition(&tp.topic, tp.partition_id).await else {
+                continue;
+            };
+
+            let log_end_offset = partition.log_end_offset().await;
+            let lag = log_end_offset.saturating_sub(committed.offset);
+            let time_lag_ms = match lag {
+                0 => 0,
+                _ => partition
+                    .timestamp_at(committed.offset)
+                    .await
+                    .map(|ts| now.saturating_sub(ts))
+                    .unwrap_or(0),
+            };
+
+            result.push(ConsumerLag {
+                group,
+                topic: tp.topic,
+                partition_id: tp.partition_id,
+                committed_offset: committed.offset,
+                log_end_offset,
+                lag,
+                time_lag_ms,
+            });
+        }
+
+        result
+    }
+
+    /// 트랜잭션에 포함된 모든 파티션에 커밋/중단 마커 기록
+    ///
+    /// 일부 파티션에 기록하지 못해도 나머지 파티션에는 기록하고, 실패한 파티션은
+    /// 트랜잭션에 남겨 같은 요청을 다시 보내면 이어서 기록한다.
+    pub async fn end_transaction(
+        &self,
+        transactional_id: &str,
+        control: ControlType,
+    ) -> Result<()> {
+        let (txn_id, partitions) = self
+            .transactions
+            .begin_end(transactional_id, control)
+            .await?;
+
+        let mut failed = HashSet::new();
+        let mut errors = Vec::new();
+        for tp in partitions {
+            // 트랜잭션 도중 삭제된 토픽은 건너뛴다
+            let Ok(partition) = self.get_partition(&tp.topic, tp.partition_id).await else {
+                continue;
+            };
+            if let Err(e) = partition.write_marker(txn_id, control).await {
+                errors.push(format!(
+                    "partition {} of topic {}: {}",
+                    tp.partition_id, tp.topic, e
+                ));
+                failed.insert(tp);
+            }
+        }
+        self.transactions
+            .complete_end(transactional_id, failed)
+            .await;
+
+        if errors.is_empty() {
+            Ok(())
+        } else {
+            Err(MeierError::Transaction(format!(
+                "Failed to write {:?} markers for transaction {} (retry to continue): {}",
+                control,
+                transactional_id,
+                errors.join(", ")
+            )))
+        }
+    }
+
+    /// timeout 이 지나도록 끝나지 않은 트랜잭션을 중단하고 마커 기록
+    ///
+    /// 프로듀서가 종료 요청 없이 사라져도 read_committed 소비자가 계속 막히지 않게 한다.
+    pub async fn expire_transactions(&self, timeout: Duration) {
+        for (transactional_id, control) in self.transactions.expired(timeout).await {
+            warn!(
+                "Transaction {} exceeded timeout {:?}, ending with {:?}",
+                transactional_id, timeout, control
+            );
+            if let Err(e) = self.end_transaction(&transactional_id, control).await {
+                warn!("Failed to end expired transaction: {}", e);
+            }
+        }
+    }
+}
+
+/// 랙마다 브로커를 하나씩 번갈아 나열(a1, b1, c1, a2, b2, ...)
+fn rack_alternated(brokers: &[BrokerId], racks: &HashMap<BrokerId, String>) -> Vec<BrokerId> {
+    let mut groups: BTreeMap<Option<&String>, Vec<BrokerId>> = BTreeMap::new();
+    for &id in brokers {
+        match racks.get(&id) {
+            Some(rack) => groups.entry(Some(rack)).or_default().push(id),
+            None => groups.entry(None).or_default().push(id),
+        }
+    }
+
+    // 랙이 없는 브로커는 각자 하나의 그룹
+    let mut groups: Vec<Vec<BrokerId>> = groups
+        .into_iter()
+        .flat_map(|(rack, ids)| match rack {
+            Some(_) => vec![ids],
+            None => ids.into_iter().map(|id| vec![id]).collect(),
+        })
+        .collect();
+    groups.sort_by_key(|ids| ids[0]);
+
+    let rounds = groups.iter().map(Vec::len).max().unwrap_or(0);
+    (0..rounds)
+        .flat_map(|round| groups.iter().filter_map(move |ids| ids.get(round).copied()))
+        .collect()
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use super::*;
+    use crate::{config::StorageConfig, protocol::IsolationLevel};
+
+    /// 기본 설정의 단일 브로커 TopicManager
+    pub(crate) fn topic_manager() -> TopicManager {
+        let storage = StorageConfig::default();
+        TopicManager::new(
+            storage.max_topics,
+            storage.max_messages_per_partition,
+            storage.max_message_size_bytes,
+            storage.topic_defaults(),
+            ClusterConfig::default(),
+        )
+    }
+
+    #[tokio::test]
+    async fn expired_transactions_are_aborted_on_every_partition() {
+        let topics = topic_manager();
+        topics
+            .create_topic("orders".to_string(), BTreeMap::new())
+            .await
+            .unwrap();
+        let partitions: Vec<_> = (0..2)
+            .map(|partition_id| TopicPartition {
+                topic: "orders".to_string(),
+                partition_id,
+            })
+            .collect();
+
+        let txn_id = topics
+            .transactions()
+            .begin("stuck".to_string())
+            .await
+            .unwrap();
+        topics
+            .transactions()
+            .add_partitions("stuck", partitions.clone())
+            .await
+            .unwrap();
+        for tp in &partitions {
+            let partition = topics
+                .get_partition(&tp.topic, tp.partition_id)
+                .await
+                .unwrap();
+            partition
+                .add_message(Message::new(b"pending".to_vec()).with_txn_id(txn_id))
+                .await
+                .unwrap();
+            assert_eq!(partition.last_stable_offset().await, 0);
+        }
+
+        topics.expire_transactions(Duration::from_secs(60)).await;
+        let partition = topics.get_partition("orders", 0).await.unwrap();
+        assert_eq!(partition.last_stable_offset().await, 0);
+
+        topics.expire_transactions(Duration::ZERO).await;
+        for tp in &partitions {
+            let partition = topics
+                .get_partition(&tp.topic, tp.partition_id)
+                .await
+                .unwrap();
+            assert_eq!(partition.log_end_offset().await, 2);
+            assert_eq!(partition.last_stable_offset().await, 2);
+            assert!(
+                partition
+                    .get_message(0, IsolationLevel::ReadCommitted)
+                    .await
+                    .is_none()
+            );
+        }
+        assert!(
+            topics
+                .end_transaction("stuck", ControlType::Commit)
+                .await
+                .is_err()
+        );
+    }
+
+    #[tokio::test]
+    async fn consumer_lag_follows_committed_offsets() {
+        let topics = topic_manager();
+        topics
+            .create_topic("orders".to_string(), BTreeMap::new())
+            .await
+            .unwrap();
+        let topic = topics.get_topic("orders").await.unwrap();
+        for data in [b"a", b"b", b"c"] {
+            topic
+                .add_message_to(0, Message::new(data.to_vec()))
+                .await
+                .unwrap();
+        }
+
+        topics
+            .commit_offset("billing".to_string(), "orders", 0, 1)
+            .await
+            .unwrap();
+        topics
+            .commit_offset("audit".to_string(), "orders", 0, 3)
+            .await
+            .unwrap();
+        assert!(
+            topics
+                .commit_offset("audit".to_string(), "orders", 0, 4)
+                .await
+                .is_err()
+        );
+
+        let lag = topics.consumer_lag(None).await;
+        let summary: Vec<_> = lag
+            .iter()
+            .map(|l| {
+                (
+                    l.group.as_str(),
+                    l.committed_offset,
+                    l.log_end_offset,
+                    l.lag,
+                )
+            })
+            .collect();
+        assert_eq!(summary, [("audit", 3, 3, 0), ("billing", 1, 3, 2)]);
+        assert_eq!(lag[0].time_lag_ms, 0);
+        assert!(lag[1].time_lag_ms < 60_000);
+
+        assert_eq!(topics.consumer_lag(Some("billing")).await.len(), 1);
+
+        // 삭제된 토픽의 커밋은 함께 사라진다
+        topics.delete_topic("orders").await.unwrap();
+        assert!(topics.consumer_lag(None).await.is_empty());
+    }
+
+    fn cluster_topic_manager(replication_factor: usize) -> TopicManager {
+        let storage = StorageConfig::default();
+        TopicManager::new(
+            storage.max_topics,
+            storage.max_messages_per_partition,
+            storage.max_message_size_bytes,
+            storage.topic_defaults(),
+            ClusterConfig {
+                replication_factor,
+                ..ClusterConfig::default()
+            },
+        )
+    }
+
+    fn racks(entries: &[(BrokerId, &str)]) -> HashMap<BrokerId, String> {
+        entries
+            .iter()
+            .map(|&(id, rack)| (id, rack.to_string()))
+            .collect()
+    }
+
+    #[test]
+    fn rack_alternated_takes_one_broker_per_rack_in_turn() {
+        let racks = racks(&[(1, "a"), (2, "a"), (3, "b"), (4, "b")]);
+        assert_eq!(rack_alternated(&[1, 2, 3, 4, 5], &racks), [1, 3, 5, 2, 4]);
+        assert_eq!(rack_alternated(&[2, 1], &HashMap::new()), [1, 2]);
+    }
+
+    #[tokio::test]
+    async fn replicas_are_spread_across_racks_and_lead_evenly() {
+        let topics = cluster_topic_manager(3);
+        let racks = racks(&[(1, "a"), (2, "a"), (3, "b"), (4, "b"), (5, "c"), (6, "c")]);
+        let replicas = topics.assign_replicas(6, &[1, 2, 3, 4, 5, 6], &racks).await;
+
+        for partition in &replicas {
+            let mut used: Vec<&String> = partition.iter().map(|id| &racks[id]).collect();
+            used.sort();
+            used.dedup();
+            assert_eq!(used.len(), 3, "replicas {:?} share a rack", partition);
+        }
+        let mut leaders: Vec<BrokerId> = replicas.iter().map(|partition| partition[0]).collect();
+        leaders.sort();
+        assert_eq!(leaders, [1, 2, 3, 4, 5, 6]);
+    }
+
+    #[tokio::test]
+    async fn leaders_continue_across_topics() {
+        let topics = cluster_topic_manager(2);
+        let brokers = [1, 2, 3];
+        let first = topics.assign_replicas(2, &brokers, &HashMap::new()).await;
+        assert_eq!(first.iter().map(|p| p[0]).collect::<Vec<_>>(), [1, 2]);
+        topics
+            .create_assigned_topic(TopicAssignment {
+                name: "orders".to_string(),
+                configs: BTreeMap::new(),
+                replicas: first,
+                config: None,
+            })
+            .await
+            .unwrap();
+
+        // 이전 토픽의 파티션 수만큼 이어서 시작해 리더가 한 브로커에 몰리지 않는다
+        let second = topics.assign_replicas(2, &brokers, &HashMap::new()).await;
+        assert_eq!(second.iter().map(|p| p[0]).collect::<Vec<_>>(), [3, 1]);
+        assert!(second.iter().all(|p| p.len() == 2 && p[0] != p[1]));
+    }
+
+    #[tokio::test]
+    async fn replication_factor_beyond_racks_reuses_racks() {
+        let topics = cluster_topic_manager(3);
+        let racks = racks(&[(1, "a"), (2, "a"), (3, "b"), (4, "b")]);
+        let replicas = topics.assign_replicas(4, &[1, 2, 3, 4], &racks).await;
+
+        for partition in &replicas {
+            let mut ids = partition.clone();
+            ids.sort();
+            ids.dedup();
+            assert_eq!(ids.len(), 3);
+            // 두 랙 모두에 복제본이 있다
+            assert!(partition.iter().any(|id| racks[id] == "a"));
+            assert!(partition.iter().any(|id| racks[id] == "b"));
+        }
+
+        // 복제 수가 브로커 수보다 많으면 브로커 수만큼만 배치한다
+        let replicas = topics.assign_replicas(1, &[1, 3], &racks).await;
+        assert_eq!(replicas, [vec![1, 3]]);
+    }
 }
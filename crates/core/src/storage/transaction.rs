@@ -0,0 +1,238 @@
+use std::{
+    collections::{HashMap, HashSet},
+    sync::atomic::{AtomicU64, Ordering},
+    time::{Duration, Instant},
+};
+use tokio::sync::RwLock;
+
+use crate::{MeierError, Result, protocol::TopicPartition, storage::ControlType};
+
+/// 진행 중인 트랜잭션
+pub struct Transaction {
+    pub id: u64,
+    pub partitions: HashSet<TopicPartition>,
+    /// 커밋/중단을 시작했으면 그 종류(이후 produce 는 받지 않는다)
+    ending: Option<ControlType>,
+    /// 마커를 기록하는 중
+    writing: bool,
+    started: Instant,
+}
+
+/// transactional_id 별 진행 중인 트랜잭션 관리
+pub struct TransactionManager {
+    transactions: RwLock<HashMap<String, Transaction>>,
+    next_id: AtomicU64,
+}
+
+impl TransactionManager {
+    pub fn new() -> Self {
+        Self {
+            transactions: RwLock::new(HashMap::new()),
+            next_id: AtomicU64::new(0),
+        }
+    }
+
+    pub async fn begin(&self, transactional_id: String) -> Result<u64> {
+        let mut transactions = self.transactions.write().await;
+
+        if transactions.contains_key(&transactional_id) {
+            return Err(MeierError::Transaction(format!(
+                "Transaction already in progress: {}",
+                transactional_id
+            )));
+        }
+
+        let id = self.next_id.fetch_add(1, Ordering::Relaxed) + 1;
+
+        transactions.insert(
+            transactional_id,
+            Transaction {
+                id,
+                partitions: HashSet::new(),
+                ending: None,
+                writing: false,
+                started: Instant::now(),
+            },
+        );
+
+        Ok(id)
+    }
+
+    pub async fn add_partitions(
+        &self,
+        transactional_id: &str,
+        partitions: Vec<TopicPartition>,
+    ) -> Result<()> {
+        let mut transactions = self.transactions.write().await;
+        let txn = transactions
+            .get_mut(transactional_id)
+            .ok_or_else(|| Self::not_found(transactional_id))?;
+        Self::check_open(transactional_id, txn)?;
+
+        txn.partitions.extend(partitions);
+        Ok(())
+    }
+
+    /// 파티션이 트랜잭션에 포함되어 있으면 트랜잭션 ID 반환
+    pub async fn txn_id_for(
+        &self,
+        transactional_id: &str,
+        partition: &TopicPartition,
+    ) -> Result<u64> {
+        let transactions = self.transactions.read().await;
+        let txn = transactions
+            .get(transactional_id)
+            .ok_or_else(|| Self::not_found(transactional_id))?;
+        Self::check_open(transactional_id, txn)?;
+
+        if !txn.partitions.contains(partition) {
+            return Err(MeierError::Transaction(format!(
+                "Partition {} of topic {} is not part of transaction {}",
+                partition.partition_id, partition.topic, transactional_id
+            )));
+        }
+
+        Ok(txn.id)
+    }
+
+    /// 커밋/중단 시작: 마커를 기록할 (트랜잭션 ID, 파티션) 반환
+    ///
+    /// 마커 기록에 실패한 파티션이 남아 있으면 같은 종류로만 다시 종료할 수 있고,
+    /// 다른 요청이 마커를 기록하는 중이면 거부한다.
+    pub async fn begin_end(
+        &self,
+        transactional_id: &str,
+        control: ControlType,
+    ) -> Result<(u64, Vec<TopicPartition>)> {
+        let mut transactions = self.transactions.write().await;
+        let txn = transactions
+            .get_mut(transactional_id)
+            .ok_or_else(|| Self::not_found(transactional_id))?;
+
+        match txn.ending {
+            Some(ending) if ending != control => {
+                return Err(MeierError::Transaction(format!(
+                    "Transaction {} is already ending with {:?}",
+                    transactional_id, ending
+                )));
+            }
+            _ if txn.writing => {
+                return Err(MeierError::Transaction(format!(
+                    "Transaction {} is already writing {:?} markers",
+                    transactional_id, control
+                )));
+            }
+            _ => txn.ending = Some(control),
+        }
+        txn.writing = true;
+        Ok((txn.id, txn.partitions.iter().cloned().collect()))
+    }
+
+    /// 마커를 기록하지 못한 파티션만 남기고, 모두 기록했으면 트랜잭션 제거
+    pub async fn complete_end(&self, transactional_id: &str, failed: HashSet<TopicPartition>) {
+        let mut transactions = self.transactions.write().await;
+        if failed.is_empty() {
+            transactions.remove(transactional_id);
+        } else if let Some(txn) = transactions.get_mut(transactional_id) {
+            txn.partitions = failed;
+            txn.writing = false;
+        }
+    }
+
+    /// 시작 후 timeout 이 지난 트랜잭션과 종료할 방식 반환
+    ///
+    /// 진행 중이면 중단하고, 마커 기록에 실패해 남아 있으면 시작한 방식으로 다시 기록한다.
+    pub async fn expired(&self, timeout: Duration) -> Vec<(String, ControlType)> {
+        self.transactions
+            .read()
+            .await
+            .iter()
+            .filter(|(_, txn)| !txn.writing && txn.started.elapsed() >= timeout)
+            .map(|(id, txn)| (id.clone(), txn.ending.unwrap_or(ControlType::Abort)))
+            .collect()
+    }
+
+    fn check_open(transactional_id: &str, txn: &Transaction) -> Result<()> {
+        match txn.ending {
+            Some(ending) => Err(MeierError::Transaction(format!(
+                "Transaction {} is ending with {:?}",
+                transactional_id, ending
+            ))),
+            None => Ok(()),
+        }
+    }
+
+    fn not_found(transactional_id: &str) -> MeierError {
+        MeierError::Transaction(format!("No transaction in progress: {}", transactional_id))
+    }
+}
+
+impl Default for TransactionManager {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn orders(partition_id: usize) -> TopicPartition {
+        TopicPartition {
+            topic: "orders".to_string(),
+            partition_id,
+        }
+    }
+
+    #[tokio::test]
+    async fn expired_aborts_open_and_retries_failed_ends() {
+        let transactions = TransactionManager::new();
+        transactions.begin("open".to_string()).await.unwrap();
+        transactions.begin("failed".to_string()).await.unwrap();
+        transactions
+            .add_partitions("failed", vec![orders(0), orders(1)])
+            .await
+            .unwrap();
+
+        transactions
+            .begin_end("failed", ControlType::Commit)
+            .await
+            .unwrap();
+        // 마커를 기록하는 중인 트랜잭션은 건너뛴다
+        assert_eq!(
+            transactions.expired(Duration::ZERO).await,
+            [("open".to_string(), ControlType::Abort)]
+        );
+        assert!(
+            transactions
+                .begin_end("failed", ControlType::Commit)
+                .await
+                .is_err()
+        );
+
+        transactions
+            .complete_end("failed", HashSet::from([orders(1)]))
+            .await;
+        let mut expired = transactions.expired(Duration::ZERO).await;
+        expired.sort_by(|a, b| a.0.cmp(&b.0));
+        assert_eq!(
+            expired,
+            [
+                ("failed".to_string(), ControlType::Commit),
+                ("open".to_string(), ControlType::Abort)
+            ]
+        );
+        assert!(
+            transactions
+                .expired(Duration::from_secs(60))
+                .await
+                .is_empty()
+        );
+
+        let (_, partitions) = transactions
+            .begin_end("failed", ControlType::Commit)
+            .await
+            .unwrap();
+        assert_eq!(partitions, [orders(1)]);
+    }
+}
@@ -0,0 +1,220 @@
+use rustls::{
+    ClientConfig, RootCertStore, ServerConfig,
+    pki_types::{CertificateDer, PrivateKeyDer, ServerName, pem::PemObject},
+    server::WebPkiClientVerifier,
+};
+use std::{
+    io,
+    path::Path,
+    pin::Pin,
+    sync::Arc,
+    task::{Context, Poll},
+};
+use tokio::{
+    io::{AsyncRead, AsyncWrite, ReadBuf},
+    net::TcpStream,
+};
+use tokio_rustls::{TlsAcceptor, TlsConnector, TlsStream};
+use tokio_util::codec::Framed;
+
+use crate::{
+    MeierCodec, MeierError, Result, auth,
+    config::{ClientTlsConfig, SaslCredentials, TlsConfig},
+};
+
+/// 평문 또는 TLS 연결
+pub enum Stream {
+    Plain(TcpStream),
+    Tls(Box<TlsStream<TcpStream>>),
+}
+
+impl AsyncRead for Stream {
+    fn poll_read(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &mut ReadBuf<'_>,
+    ) -> Poll<io::Result<()>> {
+        match self.get_mut() {
+            Stream::Plain(stream) => Pin::new(stream).poll_read(cx, buf),
+            Stream::Tls(stream) => Pin::new(stream.as_mut()).poll_read(cx, buf),
+        }
+    }
+}
+
+impl AsyncWrite for Stream {
+    fn poll_write(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &[u8],
+    ) -> Poll<io::Result<usize>> {
+        match self.get_mut() {
+            Stream::Plain(stream) => Pin::new(stream).poll_write(cx, buf),
+            Stream::Tls(stream) => Pin::new(stream.as_mut()).poll_write(cx, buf),
+        }
+    }
+
+    fn poll_flush(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        match self.get_mut() {
+            Stream::Plain(stream) => Pin::new(stream).poll_flush(cx),
+            Stream::Tls(stream) => Pin::new(stream.as_mut()).poll_flush(cx),
+        }
+    }
+
+    fn poll_shutdown(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        match self.get_mut() {
+            Stream::Plain(stream) => Pin::new(stream).poll_shutdown(cx),
+            Stream::Tls(stream) => Pin::new(stream.as_mut()).poll_shutdown(cx),
+        }
+    }
+}
+
+/// 리스너에서 받은 연결의 TLS 핸드셰이크(설정이 없으면 평문)
+#[derive(Clone, Default)]
+pub struct Acceptor {
+    tls: Option<TlsAcceptor>,
+}
+
+impl Acceptor {
+    pub fn new(config: Option<&TlsConfig>) -> Result<Self> {
+        let Some(config) = config else {
+            return Ok(Self::default());
+        };
+
+        let builder = match &config.client_ca_path {
+            Some(path) => {
+                let verifier = WebPkiClientVerifier::builder(Arc::new(load_roots(path)?))
+                    .build()
+                    .map_err(|e| MeierError::Tls(format!("Invalid client CA: {}", e)))?;
+                ServerConfig::builder().with_client_cert_verifier(verifier)
+            }
+            None => ServerConfig::builder().with_no_client_auth(),
+        };
+        let server_config = builder
+            .with_single_cert(load_certs(&config.cert_path)?, load_key(&config.key_path)?)
+            .map_err(|e| MeierError::Tls(format!("Invalid server certificate: {}", e)))?;
+
+        Ok(Self {
+            tls: Some(TlsAcceptor::from(Arc::new(server_config))),
+        })
+    }
+
+    pub fn is_tls(&self) -> bool {
+        self.tls.is_some()
+    }
+
+    pub async fn accept(&self, stream: TcpStream) -> Result<Stream> {
+        match &self.tls {
+            Some(acceptor) => {
+                let stream = acceptor
+                    .accept(stream)
+                    .await
+                    .map_err(|e| MeierError::Tls(format!("Handshake failed: {}", e)))?;
+                Ok(Stream::Tls(Box::new(stream.into())))
+            }
+            None => Ok(Stream::Plain(stream)),
+        }
+    }
+}
+
+/// 브로커에 연결(TLS 설정이 없으면 평문, SASL 자격 증명이 있으면 연결 후 인증)
+#[derive(Clone, Default)]
+pub struct Connector {
+    tls: Option<(TlsConnector, Option<ServerName<'static>>)>,
+    sasl: Option<SaslCredentials>,
+}
+
+impl Connector {
+    pub fn new(config: Option<&ClientTlsConfig>, sasl: Option<&SaslCredentials>) -> Result<Self> {
+        let sasl = sasl.cloned();
+        let Some(config) = config else {
+            return Ok(Self { tls: None, sasl });
+        };
+
+        let builder = ClientConfig::builder().with_root_certificates(load_roots(&config.ca_path)?);
+        let client_config = match (&config.cert_path, &config.key_path) {
+            (Some(cert_path), Some(key_path)) => builder
+                .with_client_auth_cert(load_certs(cert_path)?, load_key(key_path)?)
+                .map_err(|e| MeierError::Tls(format!("Invalid client certificate: {}", e)))?,
+            (None, None) => builder.with_no_client_auth(),
+            _ => {
+                return Err(MeierError::Tls(
+                    "cert_path and key_path must be set together".to_string(),
+                ));
+            }
+        };
+
+        let server_name = config.server_name.as_deref().map(server_name).transpose()?;
+
+        Ok(Self {
+            tls: Some((TlsConnector::from(Arc::new(client_config)), server_name)),
+            sasl,
+        })
+    }
+
+    pub async fn connect(&self, addr: &str) -> Result<Stream> {
+        let mut stream = self.connect_tls(addr).await?;
+        if let Some(credentials) = &self.sasl {
+            let mut framed = Framed::new(&mut stream, MeierCodec::default());
+            auth::authenticate(&mut framed, credentials).await?;
+        }
+        Ok(stream)
+    }
+
+    async fn connect_tls(&self, addr: &str) -> Result<Stream> {
+        let stream = TcpStream::connect(addr).await?;
+        let Some((connector, name)) = &self.tls else {
+            return Ok(Stream::Plain(stream));
+        };
+
+        // 이름을 지정하지 않았으면 주소의 호스트 부분으로 인증서 확인
+        let name = match name {
+            Some(name) => name.clone(),
+            None => {
+                let host = addr.rsplit_once(':').map_or(addr, |(host, _)| host);
+                server_name(host.trim_start_matches('[').trim_end_matches(']'))?
+            }
+        };
+
+        let stream = connector
+            .connect(name, stream)
+            .await
+            .map_err(|e| MeierError::Tls(format!("Handshake with {} failed: {}", addr, e)))?;
+        Ok(Stream::Tls(Box::new(stream.into())))
+    }
+}
+
+fn server_name(name: &str) -> Result<ServerName<'static>> {
+    ServerName::try_from(name.to_string())
+        .map_err(|e| MeierError::Tls(format!("Invalid server name '{}': {}", name, e)))
+}
+
+fn load_certs(path: &Path) -> Result<Vec<CertificateDer<'static>>> {
+    let certs = CertificateDer::pem_file_iter(path)
+        .and_then(|certs| certs.collect::<std::result::Result<Vec<_>, _>>())
+        .map_err(|e| pem_error(path, e))?;
+    if certs.is_empty() {
+        return Err(MeierError::Tls(format!(
+            "No certificate found in {}",
+            path.display()
+        )));
+    }
+    Ok(certs)
+}
+
+fn load_key(path: &Path) -> Result<PrivateKeyDer<'static>> {
+    PrivateKeyDer::from_pem_file(path).map_err(|e| pem_error(path, e))
+}
+
+fn load_roots(path: &Path) -> Result<RootCertStore> {
+    let mut roots = RootCertStore::empty();
+    for cert in load_certs(path)? {
+        roots
+            .add(cert)
+            .map_err(|e| MeierError::Tls(format!("Invalid CA in {}: {}", path.display(), e)))?;
+    }
+    Ok(roots)
+}
+
+fn pem_error(path: &Path, e: rustls::pki_types::pem::Error) -> MeierError {
+    MeierError::Tls(format!("Failed to read {}: {}", path.display(), e))
+}
@@ -0,0 +1,1305 @@
+use futures::{SinkExt, StreamExt};
+use std::{
+    collections::HashMap,
+    sync::{
+        Arc,
+        atomic::{AtomicUsize, Ordering},
+    },
+    time::Duration,
+};
+use tokio::sync::{Mutex, MutexGuard, RwLock};
+use tokio_util::codec::Framed;
+use tracing::debug;
+
+use meier_core::{
+    Frame, MeierCodec, MeierError, Result,
+    config::{ClientTlsConfig, SaslCredentials},
+    protocol::{Acks, IsolationLevel, MetadataResult},
+    tls::{Connector, Stream},
+};
+
+use crate::metadata::MetadataCache;
+
+type Connection = Framed<Stream, MeierCodec>;
+
+/// 요청이 끝나지 않아 닫힌 연결은 None
+type SharedConnection = Arc<Mutex<Option<Connection>>>;
+
+/// 클라이언트 설정
+#[derive(Debug, Clone)]
+pub struct ClientConfig {
+    /// 처음 메타데이터를 가져올 브로커 주소
+    pub bootstrap_servers: Vec<String>,
+    /// 리더 변경이나 연결 실패 시 다시 보내는 최대 횟수
+    pub max_retries: usize,
+    /// 연결 실패 후 다시 보내기까지 기다리는 시간
+    pub retry_backoff: Duration,
+    /// 브로커 TLS 설정(None 이면 평문)
+    pub tls: Option<ClientTlsConfig>,
+    /// 브로커가 SASL 인증을 요구할 때 사용할 자격 증명
+    pub sasl: Option<SaslCredentials>,
+    /// 연결마다 브로커에 알리는 이름(client id 별 쿼터에 사용)
+    pub client_id: Option<String>,
+    /// 요청 하나의 응답을 기다리는 최대 시간(연결, 쿼터 지연 포함)
+    pub request_timeout: Duration,
+}
+
+impl Default for ClientConfig {
+    fn default() -> Self {
+        Self {
+            bootstrap_servers: vec!["127.0.0.1:2369".to_string()],
+            max_retries: 10,
+            retry_backoff: Duration::from_millis(500),
+            tls: None,
+            sasl: None,
+            client_id: None,
+            request_timeout: Duration::from_secs(40),
+        }
+    }
+}
+
+/// 파티션 리더를 찾아 요청을 보내는 클라이언트
+///
+/// 메타데이터를 캐시해 두고 `NotLeaderForPartition` 응답이나 연결 실패 시
+/// 리더를 갱신해 다시 보낸다.
+pub struct Client {
+    config: ClientConfig,
+    metadata: RwLock<MetadataCache>,
+    /// 마지막으로 메타데이터를 응답한 브로커(브로커 목록이 없는 단일 브로커용)
+    default_addr: RwLock<String>,
+    connector: Connector,
+    /// 브로커별 연결(응답을 받지 못한 요청의 연결은 닫고 목록에서 뺀다)
+    connections: std::sync::Mutex<HashMap<String, SharedConnection>>,
+    /// 파티션을 지정하지 않은 produce 의 라운드 로빈 위치
+    rr_count: AtomicUsize,
+}
+
+impl Client {
+    pub async fn connect(config: ClientConfig) -> Result<Self> {
+        let default_addr =
+            config.bootstrap_servers.first().cloned().ok_or_else(|| {
+                MeierError::Config("bootstrap_servers must not be empty".to_string())
+            })?;
+        let connector = Connector::new(config.tls.as_ref(), config.sasl.as_ref())?;
+
+        let client = Self {
+            config,
+            metadata: RwLock::new(MetadataCache::default()),
+            default_addr: RwLock::new(default_addr),
+            connector,
+            connections: std::sync::Mutex::new(HashMap::new()),
+            rr_count: AtomicUsize::new(0),
+        };
+        client.refresh_metadata(None).await?;
+        Ok(client)
+    }
+
+    /// 알고 있는 브로커에서 메타데이터를 다시 가져와 캐시 갱신(None 이면 전체 토픽)
+    pub async fn refresh_metadata(&self, topics: Option<Vec<String>>) -> Result<MetadataResult> {
+        let mut addrs: Vec<String> = self
+            .metadata
+            .read()
+            .await
+            .broker_addrs()
+            .map(str::to_string)
+            .collect();
+        addrs.extend(self.config.bootstrap_servers.iter().cloned());
+        addrs.dedup();
+
+        let mut last_error = None;
+        for addr in addrs {
+            let frame = Frame::Metadata {
+                topics: topics.clone(),
+            };
+            match self.send(&addr, frame).await {
+                Ok(response) => {
+                    let result: MetadataResult = response.into_json()?;
+                    self.metadata.write().await.update(result.clone());
+                    *self.default_addr.write().await = addr;
+                    return Ok(result);
+                }
+                Err(e) => {
+                    debug!("Failed to fetch metadata from {}: {}", addr, e);
+                    last_error = Some(e);
+                }
+            }
+        }
+
+        Err(last_error
+            .unwrap_or_else(|| MeierError::Config("No broker to fetch metadata from".to_string())))
+    }
+
+    /// 파티션과 관계없는 요청(토픽 생성 등)을 브로커 하나에 보낸다
+    pub async fn request(&self, frame: Frame) -> Result<Frame> {
+        let addr = self.default_addr.read().await.clone();
+        let response = self.send(&addr, frame).await?.into_result()?;
+        Ok(response)
+    }
+
+    /// 파티션을 라운드 로빈으로 골라 리더에게 produce
+    pub async fn produce(&self, topic: &str, message: Vec<u8>, acks: Acks) -> Result<Frame> {
+        let partitions = match self.partition_count(topic).await {
+            Some(partitions) => partitions,
+            None => {
+                // 없는 토픽은 브로커가 생성하도록 파티션 없이 보내고 메타데이터 갱신
+                let response = self
+                    .request(Frame::Produce {
+                        topic: topic.to_string(),
+                        message,
+                        partition_id: None,
+                        transactional_id: None,
+                        headers: Default::default(),
+                        timestamp: None,
+                        acks,
+                    })
+                    .await?;
+                self.refresh_metadata(Some(vec![topic.to_string()])).await?;
+                return Ok(response);
+            }
+        };
+
+        let partition_id = self.rr_count.fetch_add(1, Ordering::Relaxed) % partitions.max(1);
+        self.produce_to(topic, partition_id, message, acks).await
+    }
+
+    pub async fn produce_to(
+        &self,
+        topic: &str,
+        partition_id: usize,
+        message: Vec<u8>,
+        acks: Acks,
+    ) -> Result<Frame> {
+        let frame = Frame::Produce {
+            topic: topic.to_string(),
+            message,
+            partition_id: Some(partition_id),
+            transactional_id: None,
+            headers: Default::default(),
+            timestamp: None,
+            acks,
+        };
+        self.send_to_leader(topic, partition_id, frame).await
+    }
+
+    pub async fn consume(&self, topic: &str, partition_id: usize, offset: usize) -> Result<Frame> {
+        let frame = Frame::Consume {
+            topic: topic.to_string(),
+            partition_id,
+            offset,
+            isolation_level: IsolationLevel::default(),
+        };
+        self.send_to_leader(topic, partition_id, frame).await
+    }
+
+    /// 파티션 리더에게 요청을 보내고 리더가 바뀌었으면 새 리더로 다시 보낸다
+    pub async fn send_to_leader(
+        &self,
+        topic: &str,
+        partition_id: usize,
+        frame: Frame,
+    ) -> Result<Frame> {
+        let mut last_error = None;
+        for attempt in 0..=self.config.max_retries {
+            let addr = self.leader_addr(topic, partition_id).await?;
+
+            match self
+                .send(&addr, frame.clone())
+                .await
+                .and_then(Frame::into_result)
+            {
+                // 응답에 담긴 리더로 바로 다시 보낸다
+                Err(MeierError::NotLeaderForPartition {
+                    topic: ref t,
+                    partition_id: p,
+                    leader,
+                }) if t == topic && p == partition_id => {
+                    debug!(
+                        "Partition {} of topic {} moved to broker {}",
+                        partition_id, topic, leader
+                    );
+                    let known = {
+                        let mut metadata = self.metadata.write().await;
+                        metadata.set_leader(topic, partition_id, leader);
+                        metadata.broker_addr(leader).is_some()
+                    };
+                    if !known {
+                        self.refresh_metadata(Some(vec![topic.to_string()])).await?;
+                    }
+                    last_error = Some(MeierError::NotLeaderForPartition {
+                        topic: topic.to_string(),
+                        partition_id,
+                        leader,
+                    });
+                }
+                // 리더가 종료되었을 수 있으므로 잠시 후 메타데이터를 갱신해 다시 보낸다
+                Err(e @ MeierError::Io(_)) => {
+                    debug!(
+                        "Request to {} failed (attempt {}): {}",
+                        addr,
+                        attempt + 1,
+                        e
+                    );
+                    last_error = Some(e);
+                    tokio::time::sleep(self.config.retry_backoff).await;
+                    if let Err(e) = self.refresh_metadata(Some(vec![topic.to_string()])).await {
+                        debug!("Failed to refresh metadata: {}", e);
+                    }
+                }
+                result => return result,
+            }
+        }
+
+        Err(last_error.unwrap_or_else(|| {
+            MeierError::Protocol(format!(
+                "No response for partition {} of topic {}",
+                partition_id, topic
+            ))
+        }))
+    }
+
+    async fn partition_count(&self, topic: &str) -> Option<usize> {
+        if let Some(count) = self.metadata.read().await.partition_count(topic) {
+            return Some(count);
+        }
+        self.refresh_metadata(Some(vec![topic.to_string()]))
+            .await
+            .ok()?;
+        self.metadata.read().await.partition_count(topic)
+    }
+
+    async fn leader_addr(&self, topic: &str, partition_id: usize) -> Result<String> {
+        if !self.metadata.read().await.contains_topic(topic) {
+            self.refresh_metadata(Some(vec![topic.to_string()])).await?;
+        }
+
+        let metadata = self.metadata.read().await;
+        let leader = metadata.leader(topic, partition_id).ok_or_else(|| {
+            MeierError::PartitionNotFound(format!(
+                "Partition {} not found in topic {}",
+                partition_id, topic
+            ))
+        })?;
+
+        match metadata.broker_addr(leader) {
+            Some(addr) => Ok(addr.to_string()),
+            None => Ok(self.default_addr.read().await.clone()),
+        }
+    }
+
+    /// addr 의 브로커에 요청을 보내고 응답 반환(acks=0 produce 는 응답을 기다리지 않는다)
+    ///
+    /// 쿼터를 넘어 브로커가 지연 시간을 알리면 그동안 이 연결로 다음 요청을 보내지 않는다.
+    /// request_timeout 안에 끝나지 않거나 호출한 쪽에서 취소하면 연결을 닫는다.
+    pub async fn send(&self, addr: &str, frame: Frame) -> Result<Frame> {
+        let timeout = self.config.request_timeout;
+        tokio::time::timeout(timeout, self.send_on_connection(addr, frame))
+            .await
+            .unwrap_or_else(|_| {
+                Err(MeierError::Io(std::io::Error::new(
+                    std::io::ErrorKind::TimedOut,
+                    format!("Request to {} timed out after {:?}", addr, timeout),
+                )))
+            })
+    }
+
+    async fn send_on_connection(&self, addr: &str, frame: Frame) -> Result<Frame> {
+        let expects_response = !matches!(
+            frame,
+            Frame::Produce {
+                acks: Acks::None,
+                ..
+            }
+        );
+
+        let connection = self.connection(addr).await?;
+        let mut request = InFlight {
+            client: self,
+            addr,
+            connection: &connection,
+            guard: connection.lock().await,
+            completed: false,
+        };
+        let Some(framed) = request.guard.as_mut() else {
+            return Err(MeierError::Io(std::io::Error::new(
+                std::io::ErrorKind::NotConnected,
+                format!("Connection to {} was closed", addr),
+            )));
+        };
+
+        framed.send(frame).await?;
+        if !expects_response {
+            request.completed = true;
+            return Ok(Frame::response_ok(None));
+        }
+        let response = framed.next().await.unwrap_or_else(|| {
+            Err(MeierError::Io(std::io::Error::new(
+                std::io::ErrorKind::UnexpectedEof,
+                format!("Connection to {} closed", addr),
+            )))
+        })?;
+        request.completed = true;
+
+        if let Some(throttle) = response.throttle_time() {
+            debug!("Throttled by {} for {:?}", addr, throttle);
+            tokio::time::sleep(throttle).await;
+        }
+        Ok(response)
+    }
+
+    async fn connection(&self, addr: &str) -> Result<SharedConnection> {
+        if let Some(connection) = self.connections.lock().unwrap().get(addr) {
+            return Ok(connection.clone());
+        }
+
+        let stream = self.connector.connect(addr).await?;
+        let mut framed = Framed::new(stream, MeierCodec::default());
+        if let Some(client_id) = &self.config.client_id {
+            framed
+                .send(Frame::SetClientId {
+                    client_id: client_id.clone(),
+                })
+                .await?;
+            framed
+                .next()
+                .await
+                .ok_or_else(|| MeierError::Protocol(format!("Connection to {} closed", addr)))??
+                .into_result()?;
+        }
+        let connection = Arc::new(Mutex::new(Some(framed)));
+        Ok(self
+            .connections
+            .lock()
+            .unwrap()
+            .entry(addr.to_string())
+            .or_insert(connection)
+            .clone())
+    }
+
+    /// 다음 요청 때 다시 연결하도록 목록에서 제거(그 사이 새로 맺은 연결은 유지)
+    fn evict(&self, addr: &str, connection: &SharedConnection) {
+        let mut connections = self.connections.lock().unwrap();
+        if connections
+            .get(addr)
+            .is_some_and(|current| Arc::ptr_eq(current, connection))
+        {
+            connections.remove(addr);
+        }
+    }
+}
+
+/// 응답을 받을 때까지 연결을 잠가 두는 요청
+///
+/// 응답을 읽기 전에 끝나면(에러, 시간 초과, 취소) 늦게 도착한 응답을 다음 요청이 읽지 않도록
+/// 연결을 닫고 목록에서 뺀다.
+struct InFlight<'a> {
+    client: &'a Client,
+    addr: &'a str,
+    connection: &'a SharedConnection,
+    guard: MutexGuard<'a, Option<Connection>>,
+    completed: bool,
+}
+
+impl Drop for InFlight<'_> {
+    fn drop(&mut self) {
+        if !self.completed {
+            *self.guard = None;
+            self.client.evict(self.addr, self.connection);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use meier_core::{
+        Config,
+        auth::ScramCredential,
+        config::{AclConfig, PeerConfig, QuotaLimits, SaslConfig, SaslMechanism, TlsConfig},
+        protocol::{
+            AclBinding, AclOperation, AclPermission, BrokerInfo, ClusterResult, PartitionMetadata,
+            PartitionReassignment, PatternType, ResourceType, TopicMetadata,
+        },
+        server::Server,
+    };
+    use std::{path::PathBuf, sync::atomic::AtomicBool};
+
+    /// 테스트마다 자체 서명 인증서를 만들어 PEM 파일로 저장
+    fn self_signed(dir: &std::path::Path, name: &str) -> (PathBuf, PathBuf) {
+        let certified = rcgen::generate_simple_self_signed(vec!["localhost".to_string()]).unwrap();
+        let cert_path = dir.join(format!("{}.crt", name));
+        let key_path = dir.join(format!("{}.key", name));
+        std::fs::write(&cert_path, certified.cert.pem()).unwrap();
+        std::fs::write(&key_path, certified.signing_key.serialize_pem()).unwrap();
+        (cert_path, key_path)
+    }
+
+    fn temp_dir(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("meier-sdk-{}-{}", name, std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    async fn start_server(configure: impl FnOnce(&mut Config)) -> String {
+        let port = std::net::TcpListener::bind("127.0.0.1:0")
+            .unwrap()
+            .local_addr()
+            .unwrap()
+            .port();
+        let mut config = Config::default();
+        config.server.bind_addr = format!("127.0.0.1:{}", port);
+        configure(&mut config);
+
+        let server = Server::new(config).unwrap();
+        tokio::spawn(async move { server.run().await });
+
+        let addr = format!("localhost:{}", port);
+        for _ in 0..50 {
+            if tokio::net::TcpStream::connect(&addr).await.is_ok() {
+                break;
+            }
+            tokio::time::sleep(Duration::from_millis(20)).await;
+        }
+        addr
+    }
+
+    async fn start_sasl_server(dir: &std::path::Path, acl: Option<AclConfig>) -> String {
+        let credentials_path = dir.join("credentials.toml");
+        let scram = ScramCredential::new("orders-secret", 4096)
+            .unwrap()
+            .to_entry();
+        std::fs::write(
+            &credentials_path,
+            format!(
+                "[users.billing]\npassword = \"billing-secret\"\n\n\
+                 [users.orders.scram_sha_256]\nsalt = \"{}\"\niterations = {}\n\
+                 stored_key = \"{}\"\nserver_key = \"{}\"\n",
+                scram.salt, scram.iterations, scram.stored_key, scram.server_key
+            ),
+        )
+        .unwrap();
+
+        start_server(|config| {
+            config.storage.data_dir = dir.to_path_buf();
+            config.server.sasl = Some(SaslConfig {
+                credentials_path,
+                mechanisms: vec![SaslMechanism::ScramSha256, SaslMechanism::Plain],
+                inter_broker: None,
+            });
+            config.server.acl = acl;
+        })
+        .await
+    }
+
+    /// 파티션 0 의 리더가 자신(0 번)이라는 오래된 메타데이터를 응답하고
+    /// produce 에는 1 번 브로커(real_addr)가 리더라고 응답하는 브로커
+    async fn start_stale_leader(real_addr: String, produces: Arc<AtomicUsize>) -> String {
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap().to_string();
+        let stale_addr = addr.clone();
+
+        tokio::spawn(async move {
+            while let Ok((stream, _)) = listener.accept().await {
+                let (stale_addr, real_addr) = (stale_addr.clone(), real_addr.clone());
+                let produces = produces.clone();
+                tokio::spawn(async move {
+                    let mut framed = Framed::new(stream, MeierCodec::default());
+                    while let Some(Ok(frame)) = framed.next().await {
+                        let response = match frame {
+                            Frame::Metadata { .. } => Frame::response_ok_json(&MetadataResult {
+                                topics: vec![TopicMetadata {
+                                    name: "orders".to_string(),
+                                    partitions: vec![PartitionMetadata {
+                                        partition_id: 0,
+                                        leader: 0,
+                                        replicas: vec![0, 1],
+                                        isr: vec![0, 1],
+                                    }],
+                                    config: Default::default(),
+                                }],
+                                brokers: [(0, &stale_addr), (1, &real_addr)]
+                                    .into_iter()
+                                    .map(|(id, addr)| BrokerInfo {
+                                        id,
+                                        addr: addr.clone(),
+                                        rack: None,
+                                        fenced: false,
+                                        decommissioned: false,
+                                    })
+                                    .collect(),
+                                controller_id: Some(1),
+                            })
+                            .unwrap(),
+                            Frame::Produce { topic, .. } => {
+                                produces.fetch_add(1, Ordering::SeqCst);
+                                Frame::response_from_error(MeierError::NotLeaderForPartition {
+                                    topic,
+                                    partition_id: 0,
+                                    leader: 1,
+                                })
+                            }
+                            frame => Frame::response_error(format!(
+                                "Unexpected request {}",
+                                frame.name()
+                            )),
+                        };
+                        if framed.send(response).await.is_err() {
+                            break;
+                        }
+                    }
+                });
+            }
+        });
+        addr
+    }
+
+    fn sasl_config(
+        addr: &str,
+        mechanism: SaslMechanism,
+        username: &str,
+        password: &str,
+    ) -> ClientConfig {
+        ClientConfig {
+            bootstrap_servers: vec![addr.to_string()],
+            max_retries: 0,
+            sasl: Some(SaslCredentials {
+                mechanism,
+                username: username.to_string(),
+                password: password.to_string(),
+            }),
+            ..Default::default()
+        }
+    }
+
+    fn client_config(addr: &str, tls: ClientTlsConfig) -> ClientConfig {
+        ClientConfig {
+            bootstrap_servers: vec![addr.to_string()],
+            max_retries: 0,
+            tls: Some(tls),
+            ..Default::default()
+        }
+    }
+
+    #[tokio::test]
+    async fn produce_and_consume_over_tls() {
+        let dir = temp_dir("tls");
+        let (cert_path, key_path) = self_signed(&dir, "server");
+        let addr = start_server(|config| {
+            config.server.tls = Some(TlsConfig {
+                cert_path: cert_path.clone(),
+                key_path,
+                client_ca_path: None,
+                peer_ca_path: None,
+            })
+        })
+        .await;
+
+        let client = Client::connect(client_config(
+            &addr,
+            ClientTlsConfig {
+                ca_path: cert_path,
+                cert_path: None,
+                key_path: None,
+                server_name: None,
+            },
+        ))
+        .await
+        .unwrap();
+
+        client
+            .request(Frame::CreateTopic {
+                topic: "orders".to_string(),
+                configs: Default::default(),
+            })
+            .await
+            .unwrap();
+        client.refresh_metadata(None).await.unwrap();
+        client
+            .produce_to("orders", 0, b"hello".to_vec(), Acks::Leader)
+            .await
+            .unwrap();
+        let response = client.consume("orders", 0, 0).await.unwrap();
+        assert!(matches!(
+            response,
+            Frame::Response {
+                record: Some(_),
+                ..
+            }
+        ));
+
+        std::fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[tokio::test]
+    async fn mtls_requires_client_certificate() {
+        let dir = temp_dir("mtls");
+        let (server_cert, server_key) = self_signed(&dir, "server");
+        let (client_cert, client_key) = self_signed(&dir, "client");
+        let addr = start_server(|config| {
+            config.server.tls = Some(TlsConfig {
+                cert_path: server_cert.clone(),
+                key_path: server_key,
+                client_ca_path: Some(client_cert.clone()),
+                peer_ca_path: None,
+            })
+        })
+        .await;
+
+        let anonymous = ClientTlsConfig {
+            ca_path: server_cert.clone(),
+            cert_path: None,
+            key_path: None,
+            server_name: None,
+        };
+        assert!(
+            Client::connect(client_config(&addr, anonymous))
+                .await
+                .is_err()
+        );
+
+        let authenticated = ClientTlsConfig {
+            ca_path: server_cert,
+            cert_path: Some(client_cert),
+            key_path: Some(client_key),
+            server_name: None,
+        };
+        assert!(
+            Client::connect(client_config(&addr, authenticated))
+                .await
+                .is_ok()
+        );
+
+        std::fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[tokio::test]
+    async fn authenticates_with_scram_and_plain() {
+        let dir = temp_dir("sasl");
+        let addr = start_sasl_server(&dir, None).await;
+
+        for (mechanism, username, password) in [
+            (SaslMechanism::ScramSha256, "orders", "orders-secret"),
+            (SaslMechanism::Plain, "orders", "orders-secret"),
+            (SaslMechanism::ScramSha256, "billing", "billing-secret"),
+            (SaslMechanism::Plain, "billing", "billing-secret"),
+        ] {
+            let client = Client::connect(sasl_config(&addr, mechanism, username, password)).await;
+            assert!(
+                client.is_ok(),
+                "{:?} {}: {:?}",
+                mechanism,
+                username,
+                client.err()
+            );
+        }
+
+        std::fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[tokio::test]
+    async fn rejects_invalid_or_missing_credentials() {
+        let dir = temp_dir("sasl-reject");
+        let addr = start_sasl_server(&dir, None).await;
+
+        for (mechanism, username, password) in [
+            (SaslMechanism::ScramSha256, "orders", "wrong"),
+            (SaslMechanism::Plain, "orders", "wrong"),
+            (SaslMechanism::ScramSha256, "unknown", "orders-secret"),
+        ] {
+            let client = Client::connect(sasl_config(&addr, mechanism, username, password)).await;
+            assert!(
+                client.is_err(),
+                "{:?} {} authenticated",
+                mechanism,
+                username
+            );
+        }
+
+        // 인증하지 않은 연결은 요청이 거부된다
+        let client = Client::connect(ClientConfig {
+            bootstrap_servers: vec![addr],
+            max_retries: 0,
+            ..Default::default()
+        })
+        .await;
+        assert!(client.is_err());
+
+        std::fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[tokio::test]
+    async fn rejects_malformed_non_ascii_scram_messages() {
+        let dir = temp_dir("sasl-malformed");
+        let addr = start_sasl_server(&dir, None).await;
+
+        for client_first in ["n,,한=x,r=abc", "n,,n=orders,한", "n,,nn=orders,r=abc"] {
+            let stream = Connector::new(None, None)
+                .unwrap()
+                .connect(&addr)
+                .await
+                .unwrap();
+            let mut framed = Framed::new(stream, MeierCodec::default());
+            framed
+                .send(Frame::SaslHandshake {
+                    mechanism: SaslMechanism::ScramSha256.name().to_string(),
+                })
+                .await
+                .unwrap();
+            framed.next().await.unwrap().unwrap().into_result().unwrap();
+
+            framed
+                .send(Frame::SaslAuthenticate {
+                    auth_bytes: client_first.as_bytes().to_vec(),
+                })
+                .await
+                .unwrap();
+            // 연결 태스크가 중단되지 않고 에러 응답을 보낸다
+            let response = framed.next().await.unwrap().unwrap().into_result();
+            assert!(
+                matches!(&response, Err(e) if e.to_string().contains("invalid attribute")),
+                "{}: {:?}",
+                client_first,
+                response
+            );
+        }
+
+        Client::connect(sasl_config(
+            &addr,
+            SaslMechanism::ScramSha256,
+            "orders",
+            "orders-secret",
+        ))
+        .await
+        .unwrap();
+
+        std::fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[tokio::test]
+    async fn acls_limit_topics_to_their_owner() {
+        let dir = temp_dir("acl");
+        let addr = start_sasl_server(
+            &dir,
+            Some(AclConfig {
+                super_users: vec!["User:billing".to_string()],
+                allow_if_no_acls: false,
+            }),
+        )
+        .await;
+
+        let admin = Client::connect(sasl_config(
+            &addr,
+            SaslMechanism::Plain,
+            "billing",
+            "billing-secret",
+        ))
+        .await
+        .unwrap();
+        for topic in ["orders.events", "billing"] {
+            admin
+                .request(Frame::CreateTopic {
+                    topic: topic.to_string(),
+                    configs: Default::default(),
+                })
+                .await
+                .unwrap();
+        }
+        admin
+            .request(Frame::CreateAcls {
+                acls: vec![AclBinding {
+                    principal: "User:orders".to_string(),
+                    resource_type: ResourceType::Topic,
+                    resource_name: "orders.".to_string(),
+                    pattern_type: PatternType::Prefixed,
+                    operation: AclOperation::Write,
+                    permission: AclPermission::Allow,
+                }],
+            })
+            .await
+            .unwrap();
+
+        let client = Client::connect(sasl_config(
+            &addr,
+            SaslMechanism::ScramSha256,
+            "orders",
+            "orders-secret",
+        ))
+        .await
+        .unwrap();
+        let metadata = client.refresh_metadata(None).await.unwrap();
+        let topics: Vec<_> = metadata.topics.iter().map(|t| t.name.as_str()).collect();
+        assert_eq!(topics, ["orders.events"]);
+
+        client
+            .produce_to("orders.events", 0, b"created".to_vec(), Acks::Leader)
+            .await
+            .unwrap();
+        let denied = client
+            .request(Frame::produce("billing".to_string(), b"paid".to_vec()))
+            .await;
+        assert!(matches!(denied, Err(MeierError::NotAuthorized(_))));
+        let denied = client.consume("orders.events", 0, 0).await;
+        assert!(matches!(denied, Err(MeierError::NotAuthorized(_))));
+
+        // Write 권한만으로는 produce 로 새 토픽을 만들 수 없다
+        let denied = client
+            .request(Frame::produce("orders.x".to_string(), b"created".to_vec()))
+            .await;
+        assert!(matches!(denied, Err(MeierError::NotAuthorized(_))));
+        let metadata = admin.refresh_metadata(None).await.unwrap();
+        assert!(metadata.topics.iter().all(|t| t.name != "orders.x"));
+
+        std::fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[tokio::test]
+    async fn throttles_clients_over_their_quota() {
+        let dir = temp_dir("quota");
+        let addr = start_server(|config| {
+            config.storage.data_dir = dir.clone();
+            config.server.quotas.clients.insert(
+                "noisy".to_string(),
+                QuotaLimits {
+                    produce_bytes_per_sec: Some(10_000),
+                    ..Default::default()
+                },
+            );
+        })
+        .await;
+
+        let connect = |client_id: &str| {
+            Client::connect(ClientConfig {
+                bootstrap_servers: vec![addr.clone()],
+                client_id: Some(client_id.to_string()),
+                ..Default::default()
+            })
+        };
+        let noisy = connect("noisy").await.unwrap();
+        let quiet = connect("quiet").await.unwrap();
+        noisy
+            .request(Frame::CreateTopic {
+                topic: "events".to_string(),
+                configs: Default::default(),
+            })
+            .await
+            .unwrap();
+        noisy.refresh_metadata(None).await.unwrap();
+        quiet.refresh_metadata(None).await.unwrap();
+
+        // 1 초 분량을 넘긴 5,000 바이트만큼 지연된다
+        let started = std::time::Instant::now();
+        let response = noisy
+            .produce_to("events", 0, vec![0; 15_000], Acks::Leader)
+            .await
+            .unwrap();
+        let throttle = response.throttle_time().unwrap();
+        assert!(throttle > Duration::from_millis(400) && throttle <= Duration::from_millis(500));
+        assert!(started.elapsed() >= throttle);
+
+        let response = quiet
+            .produce_to("events", 0, vec![0; 15_000], Acks::Leader)
+            .await
+            .unwrap();
+        assert_eq!(response.throttle_time(), None);
+
+        let invalid = quiet
+            .request(Frame::SetClientId {
+                client_id: "bad id".to_string(),
+            })
+            .await;
+        assert!(invalid.is_err());
+
+        std::fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[tokio::test]
+    async fn redirects_produce_from_stale_leader() {
+        let dir = temp_dir("redirect");
+        let real_addr = start_server(|config| config.storage.data_dir = dir.clone()).await;
+        Client::connect(ClientConfig {
+            bootstrap_servers: vec![real_addr.clone()],
+            ..Default::default()
+        })
+        .await
+        .unwrap()
+        .request(Frame::CreateTopic {
+            topic: "orders".to_string(),
+            configs: Default::default(),
+        })
+        .await
+        .unwrap();
+
+        let produces = Arc::new(AtomicUsize::new(0));
+        let stale_addr = start_stale_leader(real_addr, produces.clone()).await;
+        let client = Client::connect(ClientConfig {
+            bootstrap_servers: vec![stale_addr],
+            max_retries: 1,
+            ..Default::default()
+        })
+        .await
+        .unwrap();
+        assert_eq!(client.metadata.read().await.leader("orders", 0), Some(0));
+
+        // 이전 리더가 알려 준 새 리더로 다시 보내 성공하고 캐시도 갱신된다
+        client
+            .produce_to("orders", 0, b"moved".to_vec(), Acks::Leader)
+            .await
+            .unwrap();
+        assert_eq!(produces.load(Ordering::SeqCst), 1);
+        assert_eq!(client.metadata.read().await.leader("orders", 0), Some(1));
+
+        // 이후 요청은 이전 리더를 거치지 않는다
+        let response = client.consume("orders", 0, 0).await.unwrap();
+        assert!(matches!(
+            response,
+            Frame::Response {
+                record: Some(_),
+                ..
+            }
+        ));
+        client
+            .produce_to("orders", 0, b"again".to_vec(), Acks::Leader)
+            .await
+            .unwrap();
+        assert_eq!(produces.load(Ordering::SeqCst), 1);
+
+        std::fs::remove_dir_all(dir).unwrap();
+    }
+
+    /// 3대의 브로커로 클러스터를 띄우고 (주소, 컨트롤러 리더 ID) 반환
+    async fn start_cluster(
+        dir: &std::path::Path,
+        configure: impl Fn(usize, &mut Config),
+    ) -> (Vec<String>, usize) {
+        let listeners: Vec<_> = (0..3)
+            .map(|_| std::net::TcpListener::bind("127.0.0.1:0").unwrap())
+            .collect();
+        let addrs: Vec<String> = listeners
+            .iter()
+            .map(|listener| listener.local_addr().unwrap().to_string())
+            .collect();
+        drop(listeners);
+
+        for (id, addr) in addrs.iter().enumerate() {
+            let mut config = Config::default();
+            config.server.bind_addr = addr.clone();
+            config.storage.data_dir = dir.join(id.to_string());
+            config.cluster.broker_id = id as u32;
+            config.cluster.peers = addrs
+                .iter()
+                .enumerate()
+                .filter(|&(peer, _)| peer != id)
+                .map(|(peer, addr)| PeerConfig {
+                    id: peer as u32,
+                    addr: addr.clone(),
+                })
+                .collect();
+            configure(id, &mut config);
+
+            let server = Server::new(config).unwrap();
+            tokio::spawn(async move { server.run().await });
+        }
+
+        let client = Client::connect(ClientConfig {
+            bootstrap_servers: addrs.clone(),
+            ..Default::default()
+        })
+        .await
+        .unwrap();
+        for _ in 0..100 {
+            let cluster: Option<ClusterResult> = client
+                .send(&addrs[0], Frame::DescribeCluster)
+                .await
+                .ok()
+                .and_then(|response| response.into_json().ok());
+            if let Some(leader) = cluster.and_then(|cluster| cluster.controller_id) {
+                return (addrs, leader as usize);
+            }
+            tokio::time::sleep(Duration::from_millis(100)).await;
+        }
+        panic!("no controller leader elected");
+    }
+
+    #[tokio::test]
+    async fn creates_topic_through_a_follower_controller() {
+        let dir = temp_dir("cluster");
+        let (addrs, leader) = start_cluster(&dir, |id, config| {
+            // 브로커마다 기본 파티션 수를 달리해 리더의 기본값이 쓰이는지 확인한다
+            config.storage.default_partitions = id + 2;
+            config.cluster.replication_factor = 3;
+        })
+        .await;
+        let client = Client::connect(ClientConfig {
+            bootstrap_servers: addrs.clone(),
+            ..Default::default()
+        })
+        .await
+        .unwrap();
+        let follower = (leader + 1) % addrs.len();
+
+        let response = client
+            .send(
+                &addrs[follower],
+                Frame::CreateTopic {
+                    topic: "orders".to_string(),
+                    configs: Default::default(),
+                },
+            )
+            .await
+            .unwrap()
+            .into_result()
+            .unwrap();
+        let Frame::Response {
+            message: Some(message),
+            ..
+        } = response
+        else {
+            panic!("unexpected response: {:?}", response);
+        };
+        assert_eq!(
+            message,
+            format!("Topic orders created with {} partition(s)", leader + 2)
+        );
+
+        // 모든 브로커가 리더가 정한 설정으로 생성한다
+        for addr in &addrs {
+            let mut partitions = None;
+            for _ in 0..50 {
+                let metadata: MetadataResult = client
+                    .send(
+                        addr,
+                        Frame::Metadata {
+                            topics: Some(vec!["orders".to_string()]),
+                        },
+                    )
+                    .await
+                    .unwrap()
+                    .into_json()
+                    .unwrap();
+                partitions = metadata.topics.first().map(|topic| topic.partitions.len());
+                if partitions.is_some() {
+                    break;
+                }
+                tokio::time::sleep(Duration::from_millis(100)).await;
+            }
+            assert_eq!(partitions, Some(leader + 2));
+        }
+
+        std::fs::remove_dir_all(dir).unwrap();
+    }
+
+    /// 메타데이터와 Ping 에 응답하고 slow 가 켜져 있으면 다음 Ping 응답을 늦추는 브로커
+    async fn start_slow_broker(slow: Arc<AtomicBool>) -> String {
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap().to_string();
+
+        tokio::spawn(async move {
+            while let Ok((stream, _)) = listener.accept().await {
+                let slow = slow.clone();
+                tokio::spawn(async move {
+                    let mut framed = Framed::new(stream, MeierCodec::default());
+                    while let Some(Ok(frame)) = framed.next().await {
+                        let response = match frame {
+                            Frame::Metadata { .. } => Frame::response_ok_json(&MetadataResult {
+                                topics: Vec::new(),
+                                brokers: Vec::new(),
+                                controller_id: None,
+                            })
+                            .unwrap(),
+                            Frame::Ping => {
+                                if slow.swap(false, Ordering::SeqCst) {
+                                    tokio::time::sleep(Duration::from_millis(300)).await;
+                                }
+                                Frame::Pong
+                            }
+                            frame => Frame::response_error(format!(
+                                "Unexpected request {}",
+                                frame.name()
+                            )),
+                        };
+                        if framed.send(response).await.is_err() {
+                            break;
+                        }
+                    }
+                });
+            }
+        });
+        addr
+    }
+
+    #[tokio::test]
+    async fn drops_connections_of_unfinished_requests() {
+        let slow = Arc::new(AtomicBool::new(false));
+        let addr = start_slow_broker(slow.clone()).await;
+        let client = Client::connect(ClientConfig {
+            bootstrap_servers: vec![addr.clone()],
+            request_timeout: Duration::from_millis(100),
+            ..Default::default()
+        })
+        .await
+        .unwrap();
+        let connected = |client: &Client| client.connections.lock().unwrap().contains_key(&addr);
+        assert!(connected(&client));
+
+        // 시간 초과
+        slow.store(true, Ordering::SeqCst);
+        let result = client.send(&addr, Frame::Ping).await;
+        assert!(
+            matches!(&result, Err(MeierError::Io(e)) if e.kind() == std::io::ErrorKind::TimedOut)
+        );
+        assert!(!connected(&client));
+        assert_eq!(client.send(&addr, Frame::Ping).await.unwrap(), Frame::Pong);
+
+        // 호출한 쪽에서 기다리지 않고 취소
+        slow.store(true, Ordering::SeqCst);
+        let cancelled =
+            tokio::time::timeout(Duration::from_millis(20), client.send(&addr, Frame::Ping)).await;
+        assert!(cancelled.is_err());
+        assert!(!connected(&client));
+
+        // 늦게 도착한 응답이 아니라 새 요청의 응답을 받는다
+        tokio::time::sleep(Duration::from_millis(400)).await;
+        assert_eq!(
+            client
+                .send(&addr, Frame::Metadata { topics: None })
+                .await
+                .unwrap()
+                .into_json::<MetadataResult>()
+                .unwrap()
+                .topics,
+            Vec::new()
+        );
+    }
+
+    #[tokio::test]
+    async fn decommission_through_a_follower_returns_started_reassignments() {
+        let dir = temp_dir("decommission");
+        let (addrs, leader) = start_cluster(&dir, |_, config| {
+            config.cluster.replication_factor = 2;
+        })
+        .await;
+        let client = Client::connect(ClientConfig {
+            bootstrap_servers: addrs.clone(),
+            ..Default::default()
+        })
+        .await
+        .unwrap();
+        client
+            .send(
+                &addrs[leader],
+                Frame::CreateTopic {
+                    topic: "orders".to_string(),
+                    configs: Default::default(),
+                },
+            )
+            .await
+            .unwrap()
+            .into_result()
+            .unwrap();
+
+        let removed = (leader + 1) % addrs.len();
+        let follower = (leader + 2) % addrs.len();
+        let mut started: Vec<PartitionReassignment> = Vec::new();
+        // 브로커가 등록될 때까지 다시 시도
+        for _ in 0..50 {
+            match client
+                .send(
+                    &addrs[follower],
+                    Frame::DecommissionBroker {
+                        broker_id: removed as u32,
+                        throttle_bytes_per_sec: None,
+                    },
+                )
+                .await
+                .unwrap()
+                .into_json()
+            {
+                Ok(reassignments) => {
+                    started = reassignments;
+                    break;
+                }
+                Err(_) => tokio::time::sleep(Duration::from_millis(100)).await,
+            }
+        }
+
+        // 3개 파티션에 복제본 2개씩이므로 제거할 브로커는 파티션 2개에 있다
+        assert_eq!(started.len(), 2);
+        assert!(
+            started
+                .iter()
+                .all(|reassignment| !reassignment.replicas.contains(&(removed as u32)))
+        );
+
+        // 응답한 브로커에는 재배치가 이미 반영되어 있다
+        let metadata: MetadataResult = client
+            .send(
+                &addrs[follower],
+                Frame::Metadata {
+                    topics: Some(vec!["orders".to_string()]),
+                },
+            )
+            .await
+            .unwrap()
+            .into_json()
+            .unwrap();
+        for reassignment in &started {
+            let partition = &metadata.topics[0].partitions[reassignment.partition_id];
+            assert!(
+                reassignment
+                    .replicas
+                    .iter()
+                    .all(|id| partition.replicas.contains(id)),
+                "{:?} is not applied: {:?}",
+                reassignment,
+                partition
+            );
+        }
+
+        std::fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[tokio::test]
+    async fn closes_rejected_connections_beyond_pending_handshakes() {
+        let dir = temp_dir("reject");
+        let (cert_path, key_path) = self_signed(&dir, "server");
+        let addr = start_server(|config| {
+            config.server.max_connections = 1;
+            config.server.tls = Some(TlsConfig {
+                cert_path,
+                key_path,
+                client_ca_path: None,
+                peer_ca_path: None,
+            })
+        })
+        .await;
+
+        // TLS 핸드셰이크를 시작하지 않는 연결로 한도를 채운다
+        let _held = tokio::net::TcpStream::connect(&addr).await.unwrap();
+        tokio::time::sleep(Duration::from_millis(100)).await;
+        let mut rejected = Vec::new();
+        for _ in 0..40 {
+            rejected.push(tokio::net::TcpStream::connect(&addr).await.unwrap());
+        }
+
+        // 에러 응답을 위한 핸드셰이크 대기는 일부만 허용하고 나머지는 바로 닫는다
+        let reads = rejected.iter_mut().map(|stream| async move {
+            let mut buf = [0u8; 1];
+            matches!(
+                tokio::time::timeout(
+                    Duration::from_secs(1),
+                    tokio::io::AsyncReadExt::read(stream, &mut buf)
+                )
+                .await,
+                Ok(Ok(0) | Err(_))
+            )
+        });
+        let closed = futures::future::join_all(reads)
+            .await
+            .into_iter()
+            .filter(|&closed| closed)
+            .count();
+        assert!(closed > 0, "every rejected connection is still waiting");
+        assert!(closed < 40, "no rejected connection waited for a handshake");
+
+        std::fs::remove_dir_all(dir).unwrap();
+    }
+}
@@ -1,14 +1,28 @@
-pub fn add(left: u64, right: u64) -> u64 {
-    left + right
-}
+//! Meier 브로커 클라이언트
+//!
+//! ```no_run
+//! use tesseract_sdk_rust::{Acks, Client, ClientConfig};
+//!
+//! # async fn run() -> tesseract_sdk_rust::Result<()> {
+//! let client = Client::connect(ClientConfig {
+//!     bootstrap_servers: vec!["127.0.0.1:2370".to_string()],
+//!     ..Default::default()
+//! })
+//! .await?;
+//!
+//! client.produce_to("orders", 0, b"hello".to_vec(), Acks::All).await?;
+//! let response = client.consume("orders", 0, 0).await?;
+//! # Ok(())
+//! # }
+//! ```
 
-#[cfg(test)]
-mod tests {
-    use super::*;
+pub mod client;
+pub mod metadata;
 
-    #[test]
-    fn it_works() {
-        let result = add(2, 2);
-        assert_eq!(result, 4);
-    }
-}
+pub use client::{Client, ClientConfig};
+pub use meier_core::{
+    Frame, MeierError, Result,
+    config::{ClientTlsConfig, SaslCredentials, SaslMechanism},
+    protocol::{Acks, IsolationLevel, MetadataResult},
+};
+pub use metadata::MetadataCache;
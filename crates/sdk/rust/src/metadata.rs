@@ -0,0 +1,135 @@
+use std::collections::HashMap;
+
+use meier_core::{config::BrokerId, protocol::MetadataResult};
+
+/// 브로커에서 받은 토픽별 파티션 리더와 브로커 주소
+#[derive(Debug, Clone, Default)]
+pub struct MetadataCache {
+    brokers: HashMap<BrokerId, String>,
+    /// 토픽별 파티션 리더(파티션 번호 순)
+    leaders: HashMap<String, Vec<BrokerId>>,
+}
+
+impl MetadataCache {
+    /// Metadata 응답 반영(응답에 포함된 토픽만 교체)
+    pub fn update(&mut self, result: MetadataResult) {
+        for broker in result.brokers {
+            self.brokers.insert(broker.id, broker.addr);
+        }
+
+        for topic in result.topics {
+            let mut partitions = topic.partitions;
+            partitions.sort_by_key(|partition| partition.partition_id);
+            self.leaders.insert(
+                topic.name,
+                partitions
+                    .into_iter()
+                    .map(|partition| partition.leader)
+                    .collect(),
+            );
+        }
+    }
+
+    pub fn contains_topic(&self, topic: &str) -> bool {
+        self.leaders.contains_key(topic)
+    }
+
+    pub fn partition_count(&self, topic: &str) -> Option<usize> {
+        self.leaders.get(topic).map(Vec::len)
+    }
+
+    pub fn leader(&self, topic: &str, partition_id: usize) -> Option<BrokerId> {
+        self.leaders.get(topic)?.get(partition_id).copied()
+    }
+
+    /// NotLeaderForPartition 응답으로 알게 된 리더 반영
+    pub fn set_leader(&mut self, topic: &str, partition_id: usize, leader: BrokerId) {
+        if let Some(leader_id) = self
+            .leaders
+            .get_mut(topic)
+            .and_then(|leaders| leaders.get_mut(partition_id))
+        {
+            *leader_id = leader;
+        }
+    }
+
+    pub fn broker_addr(&self, id: BrokerId) -> Option<&str> {
+        self.brokers.get(&id).map(String::as_str)
+    }
+
+    pub fn broker_addrs(&self) -> impl Iterator<Item = &str> {
+        self.brokers.values().map(String::as_str)
+    }
+
+    pub fn remove_topic(&mut self, topic: &str) {
+        self.leaders.remove(topic);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use meier_core::protocol::{BrokerInfo, PartitionMetadata, TopicMetadata};
+
+    fn metadata(leaders: &[BrokerId]) -> MetadataResult {
+        MetadataResult {
+            topics: vec![TopicMetadata {
+                name: "orders".to_string(),
+                partitions: leaders
+                    .iter()
+                    .enumerate()
+                    .rev()
+                    .map(|(partition_id, &leader)| PartitionMetadata {
+                        partition_id,
+                        leader,
+                        replicas: vec![leader],
+                        isr: vec![leader],
+                    })
+                    .collect(),
+                config: Default::default(),
+            }],
+            brokers: vec![
+                BrokerInfo {
+                    id: 0,
+                    addr: "127.0.0.1:2370".to_string(),
+                    rack: None,
+                    fenced: false,
+                    decommissioned: false,
+                },
+                BrokerInfo {
+                    id: 1,
+                    addr: "127.0.0.1:2371".to_string(),
+                    rack: None,
+                    fenced: false,
+                    decommissioned: false,
+                },
+            ],
+            controller_id: Some(0),
+        }
+    }
+
+    #[test]
+    fn update_orders_leaders_by_partition() {
+        let mut cache = MetadataCache::default();
+        cache.update(metadata(&[0, 1]));
+
+        assert_eq!(cache.partition_count("orders"), Some(2));
+        assert_eq!(cache.leader("orders", 0), Some(0));
+        assert_eq!(cache.leader("orders", 1), Some(1));
+        assert_eq!(cache.broker_addr(1), Some("127.0.0.1:2371"));
+        assert_eq!(cache.leader("payments", 0), None);
+    }
+
+    #[test]
+    fn set_leader_redirects_partition() {
+        let mut cache = MetadataCache::default();
+        cache.update(metadata(&[0, 1]));
+
+        cache.set_leader("orders", 0, 1);
+        assert_eq!(cache.leader("orders", 0), Some(1));
+
+        // 모르는 파티션은 무시
+        cache.set_leader("orders", 5, 1);
+        assert_eq!(cache.partition_count("orders"), Some(2));
+    }
+}